@@ -5,3 +5,20 @@ use chrono::{TimeZone, Utc};
 pub fn create_time() {
     let _ = Utc.ymd(2019, 1, 1).and_hms(0, 0, 0);
 }
+
+pub fn format_time(buf: &mut [u8]) -> Result<&str, core::fmt::Error> {
+    let dt = Utc.ymd(2019, 1, 1).and_hms_milli(0, 0, 0, 120);
+    dt.format("%Y-%m-%dT%H:%M:%S%.3f%:z %Z").format_into(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_time;
+
+    #[test]
+    fn test_format_time() {
+        let mut buf = [0; 64];
+        assert_eq!(format_time(&mut buf), Ok("2019-01-01T00:00:00.120+00:00 UTC"));
+        assert!(format_time(&mut buf[..8]).is_err());
+    }
+}
@@ -6,9 +6,9 @@ set -euo pipefail
 source "${BASH_SOURCE[0]%/*}/_shlib.sh"
 
 TEST_TZS=(ACST-9:30 EST4 UTC0 Asia/Katmandu)
-FEATURES=(std serde clock "alloc serde" unstable-locales)
+FEATURES=(std serde clock "alloc serde" unstable-locales format-description)
 CHECK_FEATURES=(alloc "std unstable-locales" "serde clock" "clock unstable-locales")
-RUST_113_FEATURES=(rustc-serialize serde)
+RUST_MSRV_FEATURES=(rustc-serialize serde)
 
 main() {
     if [[ "$*" =~ "-h" ]]; then
@@ -29,7 +29,7 @@ meaningful in the github actions feature matrix UI.
 
     runv cargo --version
 
-    if [[ ${RUST_VERSION:-} != 1.13.0 ]]; then
+    if [[ ${RUST_VERSION:-} != 1.31.0 ]]; then
         if [[ ${WASM:-} == yes_wasm ]]; then
             test_wasm
         elif [[ ${WASM:-} == wasm_simple ]]; then
@@ -43,8 +43,8 @@ meaningful in the github actions feature matrix UI.
         else
             test_regular UTC0
         fi
-    elif [[ ${RUST_VERSION:-} == 1.13.0 ]]; then
-        test_113
+    elif [[ ${RUST_VERSION:-} == 1.31.0 ]]; then
+        test_msrv
     else
         echo "ERROR: didn't run any tests"
         exit 1
@@ -60,7 +60,7 @@ test_all_tzs() {
 test_regular() {
     tz="$1" && shift
 
-    runt env TZ="$tz" cargo test --features __doctest,unstable-locales --color=always -- --color=always
+    runt env TZ="$tz" cargo test --features __doctest,unstable-locales,format-description --color=always -- --color=always
     for feature in "${FEATURES[@]}"; do
         runt env TZ="$tz" cargo test --no-default-features --features "$feature" --lib --color=always -- --color=always
     done
@@ -74,9 +74,9 @@ check_combinatoric() {
     done
 }
 
-test_113() {
+test_msrv() {
     runv cargo build --color=always
-    for feature in "${RUST_113_FEATURES[@]}"; do
+    for feature in "${RUST_MSRV_FEATURES[@]}"; do
         runt cargo build --features "$feature" --color=always
     done
 }
@@ -85,6 +85,8 @@ test_core() {
     (
         cd ci/core-test
         runt cargo build --target thumbv6m-none-eabi --color=always
+        # the tests use the host target, as there is no test harness without std
+        runt cargo test --color=always
     )
 }
 
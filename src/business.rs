@@ -0,0 +1,867 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Business-day calendars with weekends and holidays.
+//!
+//! A [`BusinessCalendar`](./struct.BusinessCalendar.html) is configured with the weekend days
+//! and a list of [`HolidayRule`](./struct.HolidayRule.html)s, and answers questions like
+//! "is this a business day?" or "what is the date two business days later?".
+//! A [`WorkingHours`](./struct.WorkingHours.html) schedule adds weekly opening hours to it,
+//! for measuring elapsed working time between instants.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono::business::{BusinessCalendar, HolidayRule, Observance};
+//! use chrono::{NaiveDate, Weekday};
+//!
+//! let calendar = BusinessCalendar::new()
+//!     .with_holiday(HolidayRule::fixed(1, 1).observed(Observance::NearestWeekday))
+//!     .with_holiday(HolidayRule::nth_weekday(5, Weekday::Mon, -1)) // last Monday of May
+//!     .with_holiday(HolidayRule::easter(-2)) // Good Friday
+//!     .with_holiday(HolidayRule::fixed(12, 25).observed(Observance::NearestWeekday));
+//!
+//! // T+2 settlement skips the weekend and Christmas (observed on Friday, December 24)
+//! let trade = NaiveDate::from_ymd(2021, 12, 22);
+//! assert_eq!(calendar.add_business_days(trade, 2), Some(NaiveDate::from_ymd(2021, 12, 27)));
+//! assert!(!calendar.is_business_day(NaiveDate::from_ymd(2021, 4, 2))); // Good Friday
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp;
+use num_traits::ToPrimitive;
+use oldtime::Duration as OldDuration;
+
+use datetime::resolve_local;
+use easter::Easter;
+use naive::{NaiveDate, NaiveDateTime, NaiveTime};
+use offset::TimeZone;
+use {DateTime, Datelike, Weekday};
+
+/// How a holiday falling on a weekend is observed.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Observance {
+    /// The holiday is only observed on its actual date.
+    Actual,
+    /// A holiday on a weekend day is observed on the nearest non-weekend day,
+    /// preferring the following day on a tie. With a Saturday and Sunday weekend,
+    /// a Saturday holiday is observed on Friday and a Sunday holiday on Monday.
+    NearestWeekday,
+    /// A holiday on a weekend day is observed on the following non-weekend day
+    /// that is not already a holiday (e.g. Monday, or Tuesday if Monday is a holiday too).
+    NextWeekday,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+enum HolidayKind {
+    Fixed(u32, u32),
+    NthWeekday(u32, Weekday, i32),
+    Easter(Easter, i32),
+    Date(NaiveDate),
+}
+
+/// A rule giving the date of a holiday in every year.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct HolidayRule {
+    kind: HolidayKind,
+    observance: Observance,
+}
+
+impl HolidayRule {
+    /// Makes a holiday on the same month and day every year, e.g. `fixed(12, 25)` for Christmas.
+    ///
+    /// There is no holiday in the years where the date does not exist (e.g. February 29).
+    pub fn fixed(month: u32, day: u32) -> HolidayRule {
+        HolidayRule { kind: HolidayKind::Fixed(month, day), observance: Observance::Actual }
+    }
+
+    /// Makes a holiday on the `n`-th given weekday of the month,
+    /// counting from the end of the month when `n` is negative.
+    ///
+    /// For example `nth_weekday(11, Weekday::Thu, 4)` is the fourth Thursday of November and
+    /// `nth_weekday(5, Weekday::Mon, -1)` is the last Monday of May.
+    ///
+    /// Panics if `n` is zero.
+    pub fn nth_weekday(month: u32, weekday: Weekday, n: i32) -> HolidayRule {
+        assert!(n != 0, "ordinal should not be zero");
+        HolidayRule {
+            kind: HolidayKind::NthWeekday(month, weekday, n),
+            observance: Observance::Actual,
+        }
+    }
+
+    /// Makes a holiday at given number of days from the (Western) Easter Sunday,
+    /// e.g. `easter(-2)` for Good Friday and `easter(1)` for Easter Monday.
+    pub fn easter(days: i32) -> HolidayRule {
+        HolidayRule {
+            kind: HolidayKind::Easter(Easter::Western, days),
+            observance: Observance::Actual,
+        }
+    }
+
+    /// Makes a holiday at given number of days from the Orthodox Easter Sunday.
+    pub fn orthodox_easter(days: i32) -> HolidayRule {
+        HolidayRule {
+            kind: HolidayKind::Easter(Easter::Orthodox, days),
+            observance: Observance::Actual,
+        }
+    }
+
+    /// Makes a one-off holiday on given date.
+    pub fn date(date: NaiveDate) -> HolidayRule {
+        HolidayRule { kind: HolidayKind::Date(date), observance: Observance::Actual }
+    }
+
+    /// Sets how the holiday is observed when it falls on a weekend.
+    pub fn observed(mut self, observance: Observance) -> HolidayRule {
+        self.observance = observance;
+        self
+    }
+
+    /// Returns the actual date of the holiday in given year, if any.
+    pub fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        match self.kind {
+            HolidayKind::Fixed(month, day) => NaiveDate::from_ymd_opt(year, month, day),
+            HolidayKind::NthWeekday(month, weekday, n) if n > 0 => {
+                NaiveDate::from_weekday_of_month_opt(year, month, weekday, try_opt!(n.to_u8()))
+            }
+            HolidayKind::NthWeekday(month, weekday, n) => {
+                let last = try_opt!((1..6)
+                    .rev()
+                    .filter_map(|n| NaiveDate::from_weekday_of_month_opt(year, month, weekday, n))
+                    .next());
+                let date = try_opt!(last.checked_add_signed(OldDuration::weeks(i64::from(n) + 1)));
+                if date.month() == month {
+                    Some(date)
+                } else {
+                    None
+                }
+            }
+            HolidayKind::Easter(easter, days) => easter.offset(year, days),
+            HolidayKind::Date(date) if date.year() == year => Some(date),
+            HolidayKind::Date(_) => None,
+        }
+    }
+}
+
+/// A calendar of business days, i.e. days which are neither weekend days nor holidays.
+///
+/// See the [module documentation](./index.html) for an example.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BusinessCalendar {
+    /// bits 0 (Monday) to 6 (Sunday)
+    weekend: u8,
+    holidays: Vec<HolidayRule>,
+}
+
+impl BusinessCalendar {
+    /// Makes a new calendar with Saturday and Sunday as the weekend and no holidays.
+    pub fn new() -> BusinessCalendar {
+        BusinessCalendar::default()
+    }
+
+    /// Sets the weekend days.
+    pub fn with_weekend(mut self, weekend: &[Weekday]) -> BusinessCalendar {
+        self.weekend = weekend.iter().fold(0, |bits, wd| bits | 1 << wd.num_days_from_monday());
+        self
+    }
+
+    /// Adds a holiday rule.
+    pub fn with_holiday(mut self, rule: HolidayRule) -> BusinessCalendar {
+        self.holidays.push(rule);
+        self
+    }
+
+    /// Returns `true` if the date is a weekend day.
+    #[inline]
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.weekend & (1 << date.weekday().num_days_from_monday()) != 0
+    }
+
+    /// Returns `true` if a holiday is observed on the date.
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays(date.year()).contains(&date)
+    }
+
+    /// Returns `true` if the date is neither a weekend day nor a holiday.
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    /// Returns the sorted dates on which holidays are observed in given year.
+    ///
+    /// This includes holidays moved from an adjacent year by their observance,
+    /// e.g. a New Year's Day on Saturday observed on the preceding Friday.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::business::{BusinessCalendar, HolidayRule, Observance};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = BusinessCalendar::new()
+    ///     .with_holiday(HolidayRule::fixed(12, 25).observed(Observance::NextWeekday))
+    ///     .with_holiday(HolidayRule::fixed(12, 26).observed(Observance::NextWeekday));
+    /// assert_eq!(calendar.holidays(2021), [NaiveDate::from_ymd(2021, 12, 27),
+    ///                                      NaiveDate::from_ymd(2021, 12, 28)]);
+    /// ```
+    pub fn holidays(&self, year: i32) -> Vec<NaiveDate> {
+        let mut dates = Vec::new();
+        // observed dates never move by more than a few days
+        for y in year - 1..year + 2 {
+            let mut observed = self.observed_holidays(y);
+            dates.append(&mut observed);
+        }
+        dates.retain(|date| date.year() == year);
+        dates.sort();
+        dates.dedup();
+        dates
+    }
+
+    /// Returns the dates on which the holidays of given year are observed (in no particular order).
+    fn observed_holidays(&self, year: i32) -> Vec<NaiveDate> {
+        let mut actual = self
+            .holidays
+            .iter()
+            .filter_map(|rule| rule.date_in(year).map(|date| (date, rule.observance)))
+            .collect::<Vec<_>>();
+        actual.sort_by_key(|&(date, _)| date);
+
+        // holidays on business days and those only observed on their date do not move
+        let mut observed = actual
+            .iter()
+            .filter(|&&(date, observance)| {
+                !self.is_weekend(date) || observance == Observance::Actual
+            })
+            .map(|&(date, _)| date)
+            .collect::<Vec<_>>();
+        for &(date, observance) in &actual {
+            if !self.is_weekend(date) {
+                continue;
+            }
+            let moved = match observance {
+                Observance::Actual => continue,
+                Observance::NearestWeekday => {
+                    let next = self.next_weekday(date, 1);
+                    let prev = self.next_weekday(date, -1);
+                    match (prev, next) {
+                        (Some(prev), Some(next)) if date - prev < next - date => Some(prev),
+                        (_, Some(next)) => Some(next),
+                        (prev, None) => prev,
+                    }
+                }
+                Observance::NextWeekday => {
+                    let mut next = self.next_weekday(date, 1);
+                    while let Some(date) = next {
+                        if !observed.contains(&date) {
+                            break;
+                        }
+                        next = self.next_weekday(date, 1);
+                    }
+                    next
+                }
+            };
+            if let Some(moved) = moved {
+                observed.push(moved);
+            }
+        }
+        observed
+    }
+
+    /// Returns the nearest non-weekend day after (`step` is 1) or before (`step` is -1) the date.
+    fn next_weekday(&self, date: NaiveDate, step: i64) -> Option<NaiveDate> {
+        if self.weekend == 0x7f {
+            return None;
+        }
+        let mut date = date;
+        loop {
+            date = try_opt!(date.checked_add_signed(OldDuration::days(step)));
+            if !self.is_weekend(date) {
+                return Some(date);
+            }
+        }
+    }
+
+    /// Returns the first business day after the date.
+    ///
+    /// Returns `None` if there is no business day until the last representable date.
+    pub fn next_business_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.step_business_day(date, 1, &mut YearCache::new())
+    }
+
+    /// Returns the last business day before the date.
+    ///
+    /// Returns `None` if there is no business day since the first representable date.
+    pub fn prev_business_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.step_business_day(date, -1, &mut YearCache::new())
+    }
+
+    fn step_business_day(
+        &self,
+        date: NaiveDate,
+        step: i64,
+        cache: &mut YearCache,
+    ) -> Option<NaiveDate> {
+        let mut date = date;
+        loop {
+            date = try_opt!(self.next_weekday(date, step));
+            if !cache.contains(self, date) {
+                return Some(date);
+            }
+        }
+    }
+
+    /// Adds given number of business days to the date, counting backward when `days` is negative.
+    ///
+    /// The date itself does not have to be a business day:
+    /// adding one business day to a Saturday gives the following Monday (without holidays).
+    /// Adding zero days gives the date itself if it is a business day,
+    /// or otherwise the following business day.
+    ///
+    /// Returns `None` when the result is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::business::BusinessCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = BusinessCalendar::new();
+    /// let friday = NaiveDate::from_ymd(2021, 6, 4);
+    /// assert_eq!(calendar.add_business_days(friday, 1), Some(NaiveDate::from_ymd(2021, 6, 7)));
+    /// assert_eq!(calendar.add_business_days(friday, -5), Some(NaiveDate::from_ymd(2021, 5, 28)));
+    /// assert_eq!(calendar.add_business_days(friday + chrono::Duration::days(1), 0),
+    ///            Some(NaiveDate::from_ymd(2021, 6, 7)));
+    /// ```
+    pub fn add_business_days(&self, date: NaiveDate, days: i64) -> Option<NaiveDate> {
+        let mut cache = YearCache::new();
+        if days == 0 {
+            if !self.is_weekend(date) && !cache.contains(self, date) {
+                return Some(date);
+            }
+            return self.step_business_day(date, 1, &mut cache);
+        }
+        let step = days.signum();
+        let mut date = date;
+        for _ in 0..days.abs() {
+            date = try_opt!(self.step_business_day(date, step, &mut cache));
+        }
+        Some(date)
+    }
+
+    /// Returns the number of business days from `start` (inclusive) to `end` (exclusive),
+    /// or its negation if `end` is before `start`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::business::{BusinessCalendar, HolidayRule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = BusinessCalendar::new().with_holiday(HolidayRule::fixed(1, 1));
+    /// let (start, end) = (NaiveDate::from_ymd(2021, 1, 1), NaiveDate::from_ymd(2021, 2, 1));
+    /// assert_eq!(calendar.business_days_between(start, end), 20);
+    /// assert_eq!(calendar.business_days_between(end, start), -20);
+    /// ```
+    pub fn business_days_between(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        if end < start {
+            return -self.business_days_between(end, start);
+        }
+        // whole weeks have the same number of weekend days
+        let weeks = end.signed_duration_since(start).num_weeks();
+        let weekdays_per_week = 7 - i64::from(self.weekend.count_ones());
+        let mut count = weeks * weekdays_per_week;
+        let mut date = start + OldDuration::weeks(weeks);
+        while date < end {
+            if !self.is_weekend(date) {
+                count += 1;
+            }
+            date = date.succ();
+        }
+        for year in start.year()..end.year() + 1 {
+            count -= self
+                .holidays(year)
+                .iter()
+                .filter(|&&date| start <= date && date < end && !self.is_weekend(date))
+                .count() as i64;
+        }
+        count
+    }
+}
+
+impl Default for BusinessCalendar {
+    fn default() -> BusinessCalendar {
+        BusinessCalendar { weekend: 0x60, holidays: Vec::new() }
+    }
+}
+
+/// A weekly schedule of working hours, e.g. Monday to Friday from 09:00 to 17:30,
+/// evaluated in the local time of a time zone.
+///
+/// Days which are not business days of the [calendar](#method.with_calendar)
+/// (by default every day is) have no working hours.
+///
+/// The working time is the actual elapsed time, so an opening interval spanning
+/// a daylight saving time transition is shorter or longer than its wall-clock length.
+/// Local times skipped by a transition are shifted forward by its length,
+/// and repeated local times resolve to their earliest occurrence.
+///
+/// # Example
+///
+/// ```
+/// use chrono::business::WorkingHours;
+/// use chrono::{Duration, NaiveTime, TimeZone, Utc, Weekday};
+///
+/// let schedule = WorkingHours::new().with_weekdays(
+///     &[Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+///     NaiveTime::from_hms(9, 0, 0),
+///     NaiveTime::from_hms(17, 30, 0),
+/// );
+///
+/// let opened = Utc.ymd(2021, 6, 4).and_hms(16, 0, 0); // Friday
+/// let deadline = schedule.add_working_time(&opened, Duration::hours(4)).unwrap();
+/// assert_eq!(deadline, Utc.ymd(2021, 6, 7).and_hms(11, 30, 0));
+/// assert_eq!(schedule.working_time_between(&opened, &deadline), Duration::hours(4));
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WorkingHours {
+    /// sorted opening intervals from Monday to Sunday, where an end at midnight is the end of day
+    hours: [Vec<(NaiveTime, NaiveTime)>; 7],
+    calendar: BusinessCalendar,
+}
+
+impl WorkingHours {
+    /// Makes a new schedule without any working hours and holidays.
+    pub fn new() -> WorkingHours {
+        WorkingHours::default()
+    }
+
+    /// Adds an opening interval from `start` to `end` local time on given weekday.
+    /// An `end` at midnight means the end of the day.
+    ///
+    /// Panics if `end` is before or at `start` (except for midnight),
+    /// or if the interval overlaps another interval of the weekday.
+    pub fn with_hours(
+        mut self,
+        weekday: Weekday,
+        start: NaiveTime,
+        end: NaiveTime,
+    ) -> WorkingHours {
+        let midnight = NaiveTime::from_hms(0, 0, 0);
+        assert!(start < end || end == midnight, "working hours should end after they start");
+        {
+            let hours = &mut self.hours[weekday.num_days_from_monday() as usize];
+            let overlaps = hours
+                .iter()
+                .any(|&(s, e)| (e == midnight || start < e) && (end == midnight || s < end));
+            assert!(!overlaps, "working hours should not overlap");
+            hours.push((start, end));
+            hours.sort();
+        }
+        self
+    }
+
+    /// Adds the same opening interval on each of given weekdays.
+    pub fn with_weekdays(
+        mut self,
+        weekdays: &[Weekday],
+        start: NaiveTime,
+        end: NaiveTime,
+    ) -> WorkingHours {
+        for &weekday in weekdays {
+            self = self.with_hours(weekday, start, end);
+        }
+        self
+    }
+
+    /// Sets the calendar whose non-business days (weekends and holidays) have no working hours.
+    pub fn with_calendar(mut self, calendar: BusinessCalendar) -> WorkingHours {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Returns `true` if the instant is within the working hours.
+    pub fn is_open<Tz: TimeZone>(&self, dt: &DateTime<Tz>) -> bool {
+        let tz = dt.timezone();
+        let utc = dt.naive_utc();
+        let date = dt.naive_local().date();
+        let mut cache = YearCache::new();
+        [date.pred_opt(), Some(date), date.succ_opt()].iter().filter_map(|&date| date).any(|date| {
+            self.intervals(&tz, date, &mut cache).iter().any(|&(s, e)| s <= utc && utc < e)
+        })
+    }
+
+    /// Returns the working time from `start` to `end`,
+    /// or its negation if `end` is before `start`.
+    pub fn working_time_between<Tz: TimeZone>(
+        &self,
+        start: &DateTime<Tz>,
+        end: &DateTime<Tz>,
+    ) -> OldDuration {
+        if end < start {
+            return -self.working_time_between(end, start);
+        }
+        let tz = start.timezone();
+        let (start, end) = (start.naive_utc(), end.naive_utc());
+        let last = tz.from_utc_datetime(&end).naive_local().date();
+        let mut date = tz.from_utc_datetime(&start).naive_local().date();
+        date = date.pred_opt().unwrap_or(date);
+        let mut cache = YearCache::new();
+        let mut total = OldDuration::zero();
+        while date <= last {
+            for &(s, e) in &self.intervals(&tz, date, &mut cache) {
+                let (s, e) = (cmp::max(s, start), cmp::min(e, end));
+                if s < e {
+                    total = total + (e - s);
+                }
+            }
+            date = match date.succ_opt() {
+                Some(date) => date,
+                None => break,
+            };
+        }
+        total
+    }
+
+    /// Adds given working time to the instant, going backward when `duration` is negative.
+    ///
+    /// The result is the instant when the working time has elapsed. It is the end of
+    /// an opening interval if that interval uses up the working time exactly.
+    ///
+    /// Returns `None` if there are no working hours at all, if there is no working time
+    /// for more than a year (e.g. when every open day is a holiday),
+    /// or when the result is out of range.
+    pub fn add_working_time<Tz: TimeZone>(
+        &self,
+        start: &DateTime<Tz>,
+        duration: OldDuration,
+    ) -> Option<DateTime<Tz>> {
+        if duration.is_zero() {
+            return Some(start.clone());
+        }
+        let open = self
+            .hours
+            .iter()
+            .enumerate()
+            .any(|(i, hours)| !hours.is_empty() && self.calendar.weekend & (1 << i) == 0);
+        if !open {
+            return None;
+        }
+
+        let tz = start.timezone();
+        let mut cur = start.naive_utc();
+        let mut remaining = if duration < OldDuration::zero() { -duration } else { duration };
+        let mut date = start.naive_local().date();
+        let mut cache = YearCache::new();
+        // the number of consecutive days without working time
+        let mut idle = 0;
+        if duration > OldDuration::zero() {
+            date = date.pred_opt().unwrap_or(date);
+            loop {
+                let intervals = self.intervals(&tz, date, &mut cache);
+                idle = if intervals.is_empty() { idle + 1 } else { 0 };
+                if idle > 366 {
+                    return None;
+                }
+                for &(s, e) in &intervals {
+                    if e <= cur {
+                        continue;
+                    }
+                    let s = cmp::max(s, cur);
+                    if remaining <= e - s {
+                        return Some(tz.from_utc_datetime(&(s + remaining)));
+                    }
+                    remaining = remaining - (e - s);
+                    cur = e;
+                }
+                date = try_opt!(date.succ_opt());
+            }
+        } else {
+            date = date.succ_opt().unwrap_or(date);
+            loop {
+                let intervals = self.intervals(&tz, date, &mut cache);
+                idle = if intervals.is_empty() { idle + 1 } else { 0 };
+                if idle > 366 {
+                    return None;
+                }
+                for &(s, e) in intervals.iter().rev() {
+                    if s >= cur {
+                        continue;
+                    }
+                    let e = cmp::min(e, cur);
+                    if remaining <= e - s {
+                        return Some(tz.from_utc_datetime(&(e - remaining)));
+                    }
+                    remaining = remaining - (e - s);
+                    cur = s;
+                }
+                date = try_opt!(date.pred_opt());
+            }
+        }
+    }
+
+    /// Returns the opening intervals of the local date as UTC date and times.
+    fn intervals<Tz: TimeZone>(
+        &self,
+        tz: &Tz,
+        date: NaiveDate,
+        cache: &mut YearCache,
+    ) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        let hours = &self.hours[date.weekday().num_days_from_monday() as usize];
+        if hours.is_empty()
+            || self.calendar.is_weekend(date)
+            || cache.contains(&self.calendar, date)
+        {
+            return Vec::new();
+        }
+        let midnight = NaiveTime::from_hms(0, 0, 0);
+        hours
+            .iter()
+            .filter_map(|&(start, end)| {
+                let end = if end == midnight {
+                    date.succ_opt()?.and_time(end)
+                } else {
+                    date.and_time(end)
+                };
+                let start = resolve_local(tz, &date.and_time(start))?.naive_utc();
+                let end = resolve_local(tz, &end)?.naive_utc();
+                if start < end {
+                    Some((start, end))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for WorkingHours {
+    fn default() -> WorkingHours {
+        WorkingHours {
+            hours: Default::default(),
+            calendar: BusinessCalendar::new().with_weekend(&[]),
+        }
+    }
+}
+
+/// The observed holidays of the last looked up year.
+struct YearCache {
+    year: Option<i32>,
+    holidays: Vec<NaiveDate>,
+}
+
+impl YearCache {
+    fn new() -> YearCache {
+        YearCache { year: None, holidays: Vec::new() }
+    }
+
+    fn contains(&mut self, calendar: &BusinessCalendar, date: NaiveDate) -> bool {
+        if self.year != Some(date.year()) {
+            self.year = Some(date.year());
+            self.holidays = calendar.holidays(date.year());
+        }
+        self.holidays.binary_search(&date).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BusinessCalendar, HolidayRule, Observance, WorkingHours};
+    use naive::{NaiveDate, NaiveTime};
+    use offset::test_zone::Eastern;
+    use offset::{FixedOffset, TimeZone};
+    use oldtime::Duration;
+    use {DateTime, Weekday};
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd(y, m, d)
+    }
+
+    #[test]
+    fn test_holiday_rules() {
+        assert_eq!(HolidayRule::fixed(2, 29).date_in(2021), None);
+        assert_eq!(HolidayRule::fixed(2, 29).date_in(2024), Some(ymd(2024, 2, 29)));
+        assert_eq!(
+            HolidayRule::nth_weekday(11, Weekday::Thu, 4).date_in(2021),
+            Some(ymd(2021, 11, 25))
+        );
+        assert_eq!(
+            HolidayRule::nth_weekday(5, Weekday::Mon, -1).date_in(2021),
+            Some(ymd(2021, 5, 31))
+        );
+        assert_eq!(
+            HolidayRule::nth_weekday(5, Weekday::Mon, -2).date_in(2021),
+            Some(ymd(2021, 5, 24))
+        );
+        assert_eq!(
+            HolidayRule::nth_weekday(5, Weekday::Mon, 5).date_in(2021),
+            Some(ymd(2021, 5, 31))
+        );
+        assert_eq!(HolidayRule::nth_weekday(5, Weekday::Tue, 5).date_in(2021), None);
+        assert_eq!(HolidayRule::nth_weekday(5, Weekday::Tue, -5).date_in(2021), None);
+        assert_eq!(HolidayRule::easter(1).date_in(2021), Some(ymd(2021, 4, 5)));
+        assert_eq!(HolidayRule::date(ymd(2022, 6, 3)).date_in(2021), None);
+        assert_eq!(HolidayRule::orthodox_easter(1).date_in(2021), Some(ymd(2021, 5, 3)));
+    }
+
+    #[test]
+    fn test_observance() {
+        let nearest = BusinessCalendar::new()
+            .with_holiday(HolidayRule::fixed(1, 1).observed(Observance::NearestWeekday))
+            .with_holiday(HolidayRule::fixed(7, 4).observed(Observance::NearestWeekday));
+        // New Year's Day 2022 is a Saturday, observed on Friday, December 31
+        assert_eq!(nearest.holidays(2021), [ymd(2021, 1, 1), ymd(2021, 7, 5), ymd(2021, 12, 31)]);
+        assert_eq!(nearest.holidays(2022), [ymd(2022, 7, 4)]);
+
+        let uk = BusinessCalendar::new()
+            .with_holiday(HolidayRule::fixed(12, 25).observed(Observance::NextWeekday))
+            .with_holiday(HolidayRule::fixed(12, 26).observed(Observance::NextWeekday));
+        assert_eq!(uk.holidays(2022), [ymd(2022, 12, 26), ymd(2022, 12, 27)]);
+        assert_eq!(uk.holidays(2023), [ymd(2023, 12, 25), ymd(2023, 12, 26)]);
+
+        let actual = BusinessCalendar::new().with_holiday(HolidayRule::fixed(12, 25));
+        assert_eq!(actual.holidays(2021), [ymd(2021, 12, 25)]);
+        assert!(!actual.is_holiday(ymd(2021, 12, 24)));
+    }
+
+    #[test]
+    fn test_business_days() {
+        let calendar = BusinessCalendar::new()
+            .with_weekend(&[Weekday::Fri, Weekday::Sat])
+            .with_holiday(HolidayRule::date(ymd(2021, 6, 8)));
+        assert!(calendar.is_business_day(ymd(2021, 6, 6))); // Sunday
+        assert!(!calendar.is_business_day(ymd(2021, 6, 4)));
+        assert!(!calendar.is_business_day(ymd(2021, 6, 8)));
+        assert_eq!(calendar.next_business_day(ymd(2021, 6, 3)), Some(ymd(2021, 6, 6)));
+        assert_eq!(calendar.prev_business_day(ymd(2021, 6, 9)), Some(ymd(2021, 6, 7)));
+        assert_eq!(calendar.add_business_days(ymd(2021, 6, 3), 3), Some(ymd(2021, 6, 9)));
+        assert_eq!(calendar.add_business_days(ymd(2021, 6, 9), -3), Some(ymd(2021, 6, 3)));
+        assert_eq!(calendar.add_business_days(ymd(2021, 6, 8), 0), Some(ymd(2021, 6, 9)));
+
+        for &(start, end) in
+            &[(ymd(2021, 5, 30), ymd(2021, 6, 30)), (ymd(2021, 6, 1), ymd(2021, 6, 9))]
+        {
+            let mut expected = 0;
+            let mut date = start;
+            while date < end {
+                if calendar.is_business_day(date) {
+                    expected += 1;
+                }
+                date = date.succ();
+            }
+            assert_eq!(calendar.business_days_between(start, end), expected);
+            assert_eq!(calendar.business_days_between(end, start), -expected);
+        }
+
+        let never = BusinessCalendar::new().with_weekend(&[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]);
+        assert_eq!(never.next_business_day(ymd(2021, 6, 1)), None);
+        assert_eq!(never.add_business_days(ymd(2021, 6, 1), 0), None);
+        assert_eq!(never.business_days_between(ymd(2021, 6, 1), ymd(2022, 6, 1)), 0);
+    }
+
+    fn office() -> WorkingHours {
+        let weekdays = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+        WorkingHours::new()
+            .with_weekdays(&weekdays, NaiveTime::from_hms(9, 0, 0), NaiveTime::from_hms(12, 0, 0))
+            .with_weekdays(&weekdays, NaiveTime::from_hms(13, 0, 0), NaiveTime::from_hms(17, 30, 0))
+            .with_calendar(
+                BusinessCalendar::new()
+                    .with_holiday(HolidayRule::fixed(12, 25).observed(Observance::NearestWeekday)),
+            )
+    }
+
+    fn at(m: u32, d: u32, h: u32, n: u32) -> DateTime<Eastern> {
+        Eastern.ymd(2021, m, d).and_hms(h, n, 0)
+    }
+
+    #[test]
+    fn test_working_hours() {
+        let office = office();
+        assert!(office.is_open(&at(3, 12, 9, 0)));
+        assert!(!office.is_open(&at(3, 12, 12, 30)));
+        assert!(!office.is_open(&at(3, 12, 17, 30)));
+        assert!(!office.is_open(&at(3, 13, 10, 0)));
+        assert!(!office.is_open(&at(12, 24, 10, 0)));
+        // the schedule is evaluated in the time zone of the instant
+        assert!(!office.is_open(&at(3, 12, 8, 0)));
+        assert!(office.is_open(&at(3, 12, 8, 0).with_timezone(&FixedOffset::east(0))));
+
+        // over the weekend with the start of daylight saving time
+        let (start, end) = (at(3, 12, 16, 0), at(3, 15, 10, 0));
+        assert_eq!(office.working_time_between(&start, &end), Duration::minutes(150));
+        assert_eq!(office.working_time_between(&end, &start), Duration::minutes(-150));
+        assert_eq!(office.add_working_time(&start, Duration::minutes(150)), Some(end));
+        assert_eq!(office.add_working_time(&end, Duration::minutes(-150)), Some(start));
+
+        // exactly at the end of an interval, and across the lunch break
+        assert_eq!(
+            office.add_working_time(&at(3, 15, 9, 0), Duration::hours(3)),
+            Some(at(3, 15, 12, 0))
+        );
+        assert_eq!(
+            office.add_working_time(&at(3, 15, 13, 0), Duration::hours(-3)),
+            Some(at(3, 15, 9, 0))
+        );
+        assert_eq!(
+            office.add_working_time(&at(3, 15, 11, 0), Duration::hours(2)),
+            Some(at(3, 15, 14, 0))
+        );
+        assert_eq!(
+            office.add_working_time(&at(3, 15, 11, 0), Duration::zero()),
+            Some(at(3, 15, 11, 0))
+        );
+
+        // over the Christmas holiday observed on Friday
+        let start = at(12, 23, 17, 0);
+        assert_eq!(office.add_working_time(&start, Duration::hours(1)), Some(at(12, 27, 9, 30)));
+        assert_eq!(office.working_time_between(&start, &at(12, 27, 9, 30)), Duration::hours(1));
+
+        let closed = office.clone().with_calendar(BusinessCalendar::new().with_weekend(&[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]));
+        assert_eq!(closed.add_working_time(&start, Duration::hours(1)), None);
+        assert_eq!(WorkingHours::new().add_working_time(&start, Duration::hours(1)), None);
+
+        // every Monday is a holiday, so the search gives up after a year
+        let mut mondays = BusinessCalendar::new();
+        for month in 1..13 {
+            for n in 1..6 {
+                mondays = mondays.with_holiday(HolidayRule::nth_weekday(month, Weekday::Mon, n));
+            }
+        }
+        let closed = WorkingHours::new()
+            .with_hours(Weekday::Mon, NaiveTime::from_hms(9, 0, 0), NaiveTime::from_hms(17, 0, 0))
+            .with_calendar(mondays);
+        assert_eq!(closed.add_working_time(&start, Duration::hours(1)), None);
+        assert_eq!(closed.add_working_time(&start, Duration::hours(-1)), None);
+    }
+
+    #[test]
+    fn test_working_hours_dst() {
+        let midnight = NaiveTime::from_hms(0, 0, 0);
+        let always = WorkingHours::new().with_hours(Weekday::Sun, midnight, midnight);
+        let day = |m, d| (at(m, d, 0, 0), at(m, d + 1, 0, 0));
+
+        let (start, end) = day(3, 14);
+        assert_eq!(always.working_time_between(&start, &end), Duration::hours(23));
+        assert_eq!(always.add_working_time(&start, Duration::hours(3)), Some(at(3, 14, 4, 0)));
+        let (start, end) = day(11, 7);
+        assert_eq!(always.working_time_between(&start, &end), Duration::hours(25));
+        assert_eq!(always.add_working_time(&end, Duration::hours(-25)), Some(start));
+        let (start, end) = day(11, 14);
+        assert_eq!(always.working_time_between(&start, &end), Duration::hours(24));
+    }
+}
@@ -0,0 +1,225 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Serde `with`-module for a compact representation of dates and times,
+//! re-exported from `chrono::serde`.
+
+/// Ser/de dates and times to/from a compact representation made of integers
+///
+/// | Type                         | Representation                                             |
+/// |------------------------------|------------------------------------------------------------|
+/// | `NaiveDate`                  | days since January 1, 1 CE (`num_days_from_ce`) as `i32`   |
+/// | `NaiveTime`                  | seconds since midnight and nanoseconds as `(u32, u32)`     |
+/// | `NaiveDateTime`              | UNIX timestamp and nanoseconds as `(i64, u32)`             |
+/// | `DateTime<Utc>`, `DateTime<FixedOffset>`, `DateTime<Local>` | UNIX timestamp, nanoseconds and offset in seconds east of UTC as `(i64, u32, i32)` |
+///
+/// This is much smaller than the default string representation in binary formats.
+/// Nanoseconds beyond 999,999,999 represent a leap second, which round-trips in any position.
+///
+/// The representation is a different one than that of the `Serialize` implementations,
+/// so it has to be opted in per field. Only some self-describing formats (like CBOR) still
+/// accept the default string representation when deserializing; others (like JSON) and
+/// formats which are not self-describing (like bincode) don't, so a field can't be switched
+/// to this module while data written without it still has to be read.
+/// Intended for use with `serde`'s `with` attribute.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate serde_json;
+/// # extern crate chrono;
+/// # use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+/// use chrono::serde::compact;
+/// #[derive(Deserialize, Serialize)]
+/// struct S {
+///     #[serde(with = "compact")]
+///     date: NaiveDate,
+///     #[serde(with = "compact")]
+///     time: DateTime<Utc>,
+/// }
+///
+/// # fn example() -> Result<S, serde_json::Error> {
+/// let my_s = S {
+///     date: NaiveDate::from_ymd(2014, 7, 24),
+///     time: Utc.ymd(2018, 5, 17).and_hms_milli(2, 4, 59, 500),
+/// };
+/// let as_string = serde_json::to_string(&my_s)?;
+/// assert_eq!(as_string, r#"{"date":735438,"time":[1526522699,500000000,0]}"#);
+/// let my_s: S = serde_json::from_str(&as_string)?;
+/// assert_eq!(my_s.date, NaiveDate::from_ymd(2014, 7, 24));
+/// assert_eq!(my_s.time, Utc.ymd(2018, 5, 17).and_hms_milli(2, 4, 59, 500));
+/// # Ok(my_s)
+/// # }
+/// # fn main() { example().unwrap(); }
+/// ```
+pub mod compact {
+    use serdelib::{de, ser};
+
+    /// A date or time type with a compact serde representation.
+    ///
+    /// This is implemented for the types listed in [the module documentation](./index.html).
+    pub trait Compact: Sized {
+        #[doc(hidden)]
+        fn serialize_compact<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer;
+
+        #[doc(hidden)]
+        fn deserialize_compact<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>;
+    }
+
+    /// Serialize a date or time into its compact representation
+    ///
+    /// Intended for use with `serde`s `serialize_with` attribute.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Compact,
+        S: ser::Serializer,
+    {
+        value.serialize_compact(serializer)
+    }
+
+    /// Deserialize a date or time from its compact representation
+    ///
+    /// Intended for use with `serde`s `deserialize_with` attribute.
+    pub fn deserialize<'de, T, D>(d: D) -> Result<T, D::Error>
+    where
+        T: Compact,
+        D: de::Deserializer<'de>,
+    {
+        T::deserialize_compact(d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate bincode1;
+    extern crate serde_cbor;
+    extern crate serde_derive;
+
+    use self::serde_derive::{Deserialize, Serialize};
+    use naive::{NaiveDate, NaiveDateTime, NaiveTime, MAX_DATE, MIN_DATE};
+    use offset::{FixedOffset, TimeZone, Utc};
+    use DateTime;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Compact {
+        #[serde(with = "::serde::compact")]
+        date: NaiveDate,
+        #[serde(with = "::serde::compact")]
+        time: NaiveTime,
+        #[serde(with = "::serde::compact")]
+        naive: NaiveDateTime,
+        #[serde(with = "::serde::compact")]
+        utc: DateTime<Utc>,
+        #[serde(with = "::serde::compact")]
+        fixed: DateTime<FixedOffset>,
+    }
+
+    fn value(date: NaiveDate) -> Compact {
+        let time = NaiveTime::from_hms_nano(3, 5, 7, 98_765_432);
+        Compact {
+            date: date,
+            time: time,
+            naive: date.and_time(time),
+            utc: Utc.from_utc_datetime(&date.and_time(time)),
+            fixed: FixedOffset::east(3600).from_utc_datetime(&date.and_time(time)),
+        }
+    }
+
+    #[test]
+    fn test_compact_cbor() {
+        use self::serde_cbor::{from_slice, to_vec};
+
+        let v = value(NaiveDate::from_ymd(2014, 7, 24));
+        let encoded = to_vec(&v).unwrap();
+        let decoded = from_slice::<Compact>(&encoded).unwrap();
+        assert_eq!(decoded, v);
+        assert_eq!(decoded.fixed.offset(), v.fixed.offset());
+
+        #[derive(Serialize)]
+        struct Expected {
+            date: i32,
+            time: (u32, u32),
+            naive: (i64, u32),
+            utc: (i64, u32, i32),
+            fixed: (i64, u32, i32),
+        }
+        let expected = Expected {
+            date: 735_438,
+            time: (11_107, 98_765_432),
+            naive: (1_406_171_107, 98_765_432),
+            utc: (1_406_171_107, 98_765_432, 0),
+            fixed: (1_406_171_107, 98_765_432, 3600),
+        };
+        assert_eq!(encoded, to_vec(&expected).unwrap());
+
+        // the default representation is still accepted, as CBOR deserializes any value
+        assert_eq!(from_slice::<Compact>(&to_vec(&DefaultRepr::from(&v)).unwrap()).unwrap(), v);
+
+        #[derive(Serialize)]
+        struct Invalid {
+            date: i64,
+            time: (u32, u32),
+            naive: (i64, u32),
+            utc: (i64, u32, i32),
+            fixed: (i64, u32, i32),
+        }
+        let valid = Invalid {
+            date: 735_438,
+            time: (0, 0),
+            naive: (0, 0),
+            utc: (0, 0, 0),
+            fixed: (0, 0, 0),
+        };
+        assert!(from_slice::<Compact>(&to_vec(&valid).unwrap()).is_ok());
+        let invalid = [
+            Invalid { date: i64::from(i32::MAX) + 1, ..valid },
+            Invalid { date: i64::from(i32::MAX), ..valid },
+            Invalid { time: (86_400, 0), ..valid },
+            Invalid { time: (0, 2_000_000_000), ..valid },
+            Invalid { naive: (i64::MAX, 0), ..valid },
+            Invalid { utc: (0, 0, 86_400), ..valid },
+        ];
+        for invalid in &invalid {
+            assert!(from_slice::<Compact>(&to_vec(invalid).unwrap()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_compact_bincode() {
+        use self::bincode1::{deserialize, serialize};
+
+        for &date in &[NaiveDate::from_ymd(2014, 7, 24), MIN_DATE, MAX_DATE] {
+            let v = value(date);
+            let encoded = serialize(&v).unwrap();
+            // 4 + (4 + 4) + (8 + 4) + 2 * (8 + 4 + 4) bytes
+            assert_eq!(encoded.len(), 56);
+            assert_eq!(deserialize::<Compact>(&encoded).unwrap(), v);
+        }
+
+        // including leap seconds which are not at the end of a minute
+        let mut v = value(NaiveDate::from_ymd(2014, 7, 24));
+        v.time = NaiveTime::from_hms_nano(3, 5, 7, 1_098_765_432);
+        assert_eq!(deserialize::<Compact>(&serialize(&v).unwrap()).unwrap(), v);
+    }
+
+    /// The same values with the default representation.
+    #[derive(Serialize)]
+    struct DefaultRepr {
+        date: NaiveDate,
+        time: NaiveTime,
+        naive: NaiveDateTime,
+        utc: DateTime<Utc>,
+        fixed: DateTime<FixedOffset>,
+    }
+
+    impl<'a> From<&'a Compact> for DefaultRepr {
+        fn from(v: &'a Compact) -> DefaultRepr {
+            DefaultRepr { date: v.date, time: v.time, naive: v.naive, utc: v.utc, fixed: v.fixed }
+        }
+    }
+}
@@ -0,0 +1,734 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Cron expressions for scheduling recurring jobs.
+//!
+//! A [`Schedule`](./struct.Schedule.html) is parsed from a cron expression
+//! with five (`minute hour day-of-month month day-of-week`)
+//! or six (`second minute hour day-of-month month day-of-week`) fields,
+//! and computes the next or previous fire time around a `DateTime<Tz>`.
+//!
+//! Each field accepts `*`, single values, ranges (`1-5`), steps (`*/15`, `10-50/20`, `5/10`)
+//! and comma-separated lists of them.
+//! Months and weekdays accept names (`JAN`, `MON`) as well as numbers (1 to 12, and 0 to 7
+//! where both 0 and 7 are Sunday).
+//! The day-of-month and day-of-week fields additionally accept:
+//!
+//! - `?`, which is the same as `*`;
+//! - `L` in the day of month for the last day of the month,
+//!   `15W` for the weekday nearest to the 15th (within the same month)
+//!   and `LW` for the last weekday of the month;
+//! - `5L` or `FRIL` in the day of week for the last Friday of the month,
+//!   and `FRI#3` for the third Friday of the month.
+//!
+//! As in most cron implementations, a day matches when either the day of month or
+//! the day of week matches if both fields are restricted (i.e. not `*` or `?`).
+//!
+//! # Daylight saving time
+//!
+//! Schedules are evaluated in the local time of the given time zone. Around transitions:
+//!
+//! - A job at a fixed hour whose local time is skipped (e.g. 02:30 when clocks jump from 02:00
+//!   to 03:00) fires once, shifted forward by the length of the gap (at 03:30).
+//!   A job running every hour (`*` in the hour field) simply does not fire in the skipped hour.
+//! - A job at a fixed hour whose local time is repeated (e.g. 01:30 when clocks go back from
+//!   02:00 to 01:00) fires once, at the first occurrence.
+//!   A job running every hour fires in both occurrences of the repeated hour.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono::cron::Schedule;
+//! use chrono::{TimeZone, Utc};
+//!
+//! // at 09:30 on the last Friday of every month
+//! let schedule = "30 9 * * FRIL".parse::<Schedule>().unwrap();
+//! let now = Utc.ymd(2021, 4, 15).and_hms(12, 0, 0);
+//! assert_eq!(schedule.next_after(&now), Some(Utc.ymd(2021, 4, 30).and_hms(9, 30, 0)));
+//! assert_eq!(schedule.prev_before(&now), Some(Utc.ymd(2021, 3, 26).and_hms(9, 30, 0)));
+//! ```
+
+use core::str::{self, FromStr};
+use oldtime::Duration as OldDuration;
+
+use datetime::resolve_local;
+use format::{ParseError, ParseResult, INVALID, OUT_OF_RANGE};
+use naive::{NaiveDate, NaiveDateTime, NaiveTime};
+use offset::{LocalResult, Offset, TimeZone};
+use {DateTime, Datelike, Month, Timelike, Weekday};
+
+/// A parsed cron expression.
+///
+/// See the [module documentation](./index.html) for the syntax and the handling of
+/// daylight saving time transitions.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Schedule {
+    seconds: u64,
+    minutes: u64,
+    hours: u32,
+    /// bits 1 to 31
+    days: u32,
+    /// bits 1 to 12
+    months: u16,
+    /// bits 0 (Sunday) to 6 (Saturday)
+    weekdays: u8,
+    /// `L` in the day of month
+    last_day: bool,
+    /// `LW` in the day of month
+    last_weekday: bool,
+    /// `W` in the day of month, bits 1 to 31
+    nearest_weekdays: u32,
+    /// `L` in the day of week, bits 0 (Sunday) to 6 (Saturday)
+    last_weekdays: u8,
+    /// `#` in the day of week, bits 1 to 5 for each weekday from Sunday
+    nth_weekdays: [u8; 7],
+    /// whether the day of month is `*` or `?`
+    any_day: bool,
+    /// whether the day of week is `*` or `?`
+    any_weekday: bool,
+}
+
+const ALL_HOURS: u32 = (1 << 24) - 1;
+
+/// The year offset after which a schedule is assumed to never fire again.
+/// The Gregorian calendar repeats every 400 years.
+const MAX_YEARS: i32 = 400;
+
+impl Schedule {
+    /// Returns the first fire time strictly after `after`, or `None` if the schedule never fires
+    /// (e.g. on February 30) or the fire time is out of range.
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let (min_offset, max_offset) = offset_range(&tz, after);
+        let mut best: Option<DateTime<Tz>> = None;
+        // the earliest local time which can be later than `after`
+        let mut local = try_opt!(after.naive_utc().checked_add_signed(min_offset));
+        loop {
+            local = try_opt!(self.next_local(local));
+            if let Some(ref best) = best {
+                // no later local time can be earlier than the best one
+                if local - max_offset >= best.naive_utc() {
+                    break;
+                }
+            }
+            for dt in self.resolve(&tz, &local).iter().filter_map(|dt| dt.clone()) {
+                let better = match best {
+                    Some(ref best) => dt < *best,
+                    None => true,
+                };
+                if dt > *after && better {
+                    best = Some(dt);
+                }
+            }
+            local = try_opt!(local.checked_add_signed(OldDuration::seconds(1)));
+        }
+        best
+    }
+
+    /// Returns the last fire time strictly before `before`, or `None` if the schedule never fires
+    /// or the fire time is out of range.
+    pub fn prev_before<Tz: TimeZone>(&self, before: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = before.timezone();
+        let (min_offset, max_offset) = offset_range(&tz, before);
+        let mut best: Option<DateTime<Tz>> = None;
+        // the latest local time which can be earlier than `before`
+        let mut local = try_opt!(before.naive_utc().checked_add_signed(max_offset));
+        loop {
+            local = try_opt!(self.prev_local(local));
+            if let Some(ref best) = best {
+                // no earlier local time can be later than the best one
+                if local - min_offset <= best.naive_utc() {
+                    break;
+                }
+            }
+            for dt in self.resolve(&tz, &local).iter().filter_map(|dt| dt.clone()) {
+                let better = match best {
+                    Some(ref best) => dt > *best,
+                    None => true,
+                };
+                if dt < *before && better {
+                    best = Some(dt);
+                }
+            }
+            local = try_opt!(local.checked_sub_signed(OldDuration::seconds(1)));
+        }
+        best
+    }
+
+    /// Returns an iterator over the fire times strictly after `after`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::cron::Schedule;
+    /// use chrono::{TimeZone, Utc};
+    ///
+    /// let schedule = "0 */6 * * MON-FRI".parse::<Schedule>().unwrap();
+    /// let now = Utc.ymd(2021, 4, 16).and_hms(13, 0, 0); // Friday
+    /// assert_eq!(schedule.iter_after(&now).take(3).collect::<Vec<_>>(),
+    ///            [Utc.ymd(2021, 4, 16).and_hms(18, 0, 0),
+    ///             Utc.ymd(2021, 4, 19).and_hms(0, 0, 0),
+    ///             Utc.ymd(2021, 4, 19).and_hms(6, 0, 0)]);
+    /// ```
+    pub fn iter_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> ScheduleIter<Tz> {
+        ScheduleIter { schedule: *self, last: Some(after.clone()) }
+    }
+
+    /// Returns the instants at which the local date and time fires.
+    fn resolve<Tz: TimeZone>(&self, tz: &Tz, local: &NaiveDateTime) -> [Option<DateTime<Tz>>; 2] {
+        let every_hour = self.hours == ALL_HOURS;
+        match tz.from_local_datetime(local) {
+            LocalResult::Single(dt) => [Some(dt), None],
+            LocalResult::Ambiguous(earliest, latest) => {
+                [Some(earliest), if every_hour { Some(latest) } else { None }]
+            }
+            LocalResult::None if every_hour => [None, None],
+            LocalResult::None => [resolve_local(tz, local), None],
+        }
+    }
+
+    /// Returns the earliest matching local date and time not before `from`.
+    fn next_local(&self, from: NaiveDateTime) -> Option<NaiveDateTime> {
+        let from = if from.nanosecond() > 0 {
+            try_opt!(from.with_nanosecond(0).unwrap().checked_add_signed(OldDuration::seconds(1)))
+        } else {
+            from
+        };
+        let limit = from.year() + MAX_YEARS;
+        let mut date = from.date();
+        let mut time = from.time();
+        while date.year() <= limit {
+            if self.months & (1 << date.month()) == 0 {
+                let (year, month) = if date.month() == 12 {
+                    (date.year() + 1, 1)
+                } else {
+                    (date.year(), date.month() + 1)
+                };
+                date = try_opt!(NaiveDate::from_ymd_opt(year, month, 1));
+                time = NaiveTime::from_hms(0, 0, 0);
+                continue;
+            }
+            if self.matches_date(date) {
+                if let Some(time) = self.next_time(time) {
+                    return Some(date.and_time(time));
+                }
+            }
+            date = try_opt!(date.succ_opt());
+            time = NaiveTime::from_hms(0, 0, 0);
+        }
+        None
+    }
+
+    /// Returns the latest matching local date and time not after `from`.
+    fn prev_local(&self, from: NaiveDateTime) -> Option<NaiveDateTime> {
+        let limit = from.year() - MAX_YEARS;
+        let mut date = from.date();
+        let mut time = from.time().with_nanosecond(0).unwrap();
+        let end_of_day = NaiveTime::from_hms(23, 59, 59);
+        while date.year() >= limit {
+            if self.months & (1 << date.month()) == 0 {
+                date = try_opt!(try_opt!(date.with_day(1)).pred_opt());
+                time = end_of_day;
+                continue;
+            }
+            if self.matches_date(date) {
+                if let Some(time) = self.prev_time(time) {
+                    return Some(date.and_time(time));
+                }
+            }
+            date = try_opt!(date.pred_opt());
+            time = end_of_day;
+        }
+        None
+    }
+
+    /// Returns the earliest matching time of day not before `from`.
+    fn next_time(&self, from: NaiveTime) -> Option<NaiveTime> {
+        let (from_hour, from_minute, from_second) = (from.hour(), from.minute(), from.second());
+        for hour in (from_hour..24).filter(|&h| self.hours & (1 << h) != 0) {
+            let first_minute = if hour == from_hour { from_minute } else { 0 };
+            for minute in (first_minute..60).filter(|&m| self.minutes & (1 << m) != 0) {
+                let first_second =
+                    if hour == from_hour && minute == from_minute { from_second } else { 0 };
+                if let Some(second) = (first_second..60).find(|&s| self.seconds & (1 << s) != 0) {
+                    return Some(NaiveTime::from_hms(hour, minute, second));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the latest matching time of day not after `from`.
+    fn prev_time(&self, from: NaiveTime) -> Option<NaiveTime> {
+        let (from_hour, from_minute, from_second) = (from.hour(), from.minute(), from.second());
+        for hour in (0..from_hour + 1).rev().filter(|&h| self.hours & (1 << h) != 0) {
+            let last_minute = if hour == from_hour { from_minute } else { 59 };
+            for minute in (0..last_minute + 1).rev().filter(|&m| self.minutes & (1 << m) != 0) {
+                let last_second =
+                    if hour == from_hour && minute == from_minute { from_second } else { 59 };
+                if let Some(second) =
+                    (0..last_second + 1).rev().find(|&s| self.seconds & (1 << s) != 0)
+                {
+                    return Some(NaiveTime::from_hms(hour, minute, second));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if the day of month or the day of week fields match the date.
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => self.matches_weekday(date),
+            (false, true) => self.matches_day(date),
+            (false, false) => self.matches_day(date) || self.matches_weekday(date),
+        }
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let (day, last) = (date.day(), last_day_of_month(date));
+        if self.days & (1 << day) != 0 || (self.last_day && day == last) {
+            return true;
+        }
+        if self.last_weekday && day == nearest_weekday(date, last) {
+            return true;
+        }
+        (1..last + 1)
+            .any(|n| self.nearest_weekdays & (1 << n) != 0 && day == nearest_weekday(date, n))
+    }
+
+    fn matches_weekday(&self, date: NaiveDate) -> bool {
+        let weekday = date.weekday().num_days_from_sunday();
+        let (day, last) = (date.day(), last_day_of_month(date));
+        self.weekdays & (1 << weekday) != 0
+            || (self.last_weekdays & (1 << weekday) != 0 && day + 7 > last)
+            || self.nth_weekdays[weekday as usize] & (1 << ((day - 1) / 7 + 1)) != 0
+    }
+}
+
+/// Returns the smallest and largest offsets from UTC within a day around the given time.
+fn offset_range<Tz: TimeZone>(tz: &Tz, dt: &DateTime<Tz>) -> (OldDuration, OldDuration) {
+    let utc = dt.naive_utc();
+    let day = OldDuration::days(1);
+    let mut offsets = [utc, utc, utc];
+    if let Some(before) = utc.checked_sub_signed(day) {
+        offsets[0] = before;
+    }
+    if let Some(after) = utc.checked_add_signed(day) {
+        offsets[2] = after;
+    }
+    let offsets = offsets.iter().map(|utc| {
+        OldDuration::seconds(i64::from(tz.offset_from_utc_datetime(utc).fix().local_minus_utc()))
+    });
+    let (mut min, mut max) = (OldDuration::max_value(), OldDuration::min_value());
+    for offset in offsets {
+        min = if offset < min { offset } else { min };
+        max = if offset > max { offset } else { max };
+    }
+    (min, max)
+}
+
+fn last_day_of_month(date: NaiveDate) -> u32 {
+    (28..32)
+        .rev()
+        .find(|&day| NaiveDate::from_ymd_opt(date.year(), date.month(), day).is_some())
+        .unwrap_or(28)
+}
+
+/// Returns the weekday (Monday to Friday) nearest to the given day of the date's month,
+/// without crossing the month boundary.
+fn nearest_weekday(date: NaiveDate, day: u32) -> u32 {
+    let target = match date.with_day(day) {
+        Some(target) => target,
+        None => return 0,
+    };
+    let last = last_day_of_month(date);
+    match target.weekday() {
+        Weekday::Sat if day == 1 => day + 2,
+        Weekday::Sat => day - 1,
+        Weekday::Sun if day == last => day - 2,
+        Weekday::Sun => day + 1,
+        _ => day,
+    }
+}
+
+/// Iterator over the fire times of a [`Schedule`](./struct.Schedule.html).
+///
+/// This is created by [`Schedule::iter_after`](./struct.Schedule.html#method.iter_after).
+#[derive(Clone, Debug)]
+pub struct ScheduleIter<Tz: TimeZone> {
+    schedule: Schedule,
+    last: Option<DateTime<Tz>>,
+}
+
+impl<Tz: TimeZone> Iterator for ScheduleIter<Tz> {
+    type Item = DateTime<Tz>;
+
+    fn next(&mut self) -> Option<DateTime<Tz>> {
+        let next = self.schedule.next_after(try_opt!(self.last.as_ref()));
+        self.last = next.clone();
+        next
+    }
+}
+
+/// Parses a single number or name (via `name`) in the field.
+fn parse_value<F>(s: &str, min: u32, max: u32, name: &F) -> ParseResult<u32>
+where
+    F: Fn(&str) -> Option<u32>,
+{
+    let value = match s.parse::<u32>() {
+        Ok(value) => value,
+        Err(_) => name(s).ok_or(INVALID)?,
+    };
+    if value < min || value > max {
+        return Err(OUT_OF_RANGE);
+    }
+    Ok(value)
+}
+
+/// Parses a list of values, ranges and steps into a bit set.
+fn parse_field<F>(s: &str, min: u32, max: u32, name: F) -> ParseResult<u64>
+where
+    F: Fn(&str) -> Option<u32>,
+{
+    let mut bits = 0;
+    for item in s.split(',') {
+        bits |= parse_item(item, min, max, &name)?;
+    }
+    Ok(bits)
+}
+
+fn parse_item<F>(item: &str, min: u32, max: u32, name: &F) -> ParseResult<u64>
+where
+    F: Fn(&str) -> Option<u32>,
+{
+    let mut parts = item.splitn(2, '/');
+    let range = parts.next().unwrap_or("");
+    let step = match parts.next() {
+        Some(step) => step.parse::<u32>().map_err(|_| INVALID)?,
+        None => 1,
+    };
+    if step == 0 {
+        return Err(OUT_OF_RANGE);
+    }
+    let (start, end) = if range == "*" || range == "?" {
+        (min, max)
+    } else {
+        let mut bounds = range.splitn(2, '-');
+        let start = parse_value(bounds.next().unwrap_or(""), min, max, name)?;
+        match bounds.next() {
+            Some(end) => (start, parse_value(end, min, max, name)?),
+            // `5/10` means from 5 to the maximum by 10
+            None if step > 1 || item.contains('/') => (start, max),
+            None => (start, start),
+        }
+    };
+    if start > end {
+        return Err(OUT_OF_RANGE);
+    }
+    Ok((start..end + 1).step_by(step as usize).fold(0, |bits, value| bits | 1 << value))
+}
+
+fn month_number(s: &str) -> Option<u32> {
+    Month::from_str(s).ok().map(|month| month.number_from_month())
+}
+
+fn weekday_number(s: &str) -> Option<u32> {
+    Weekday::from_str(s).ok().map(|weekday| weekday.num_days_from_sunday())
+}
+
+impl Schedule {
+    fn parse_days(&mut self, s: &str) -> ParseResult<()> {
+        self.any_day = s == "*" || s == "?";
+        for item in s.split(',') {
+            if item.eq_ignore_ascii_case("L") {
+                self.last_day = true;
+            } else if item.eq_ignore_ascii_case("LW") {
+                self.last_weekday = true;
+            } else if item.ends_with('W') || item.ends_with('w') {
+                let day = parse_value(&item[..item.len() - 1], 1, 31, &|_| None)?;
+                self.nearest_weekdays |= 1 << day;
+            } else {
+                self.days |= parse_item(item, 1, 31, &|_| None)? as u32;
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_weekdays(&mut self, s: &str) -> ParseResult<()> {
+        self.any_weekday = s == "*" || s == "?";
+        for item in s.split(',') {
+            if let Some(hash) = item.find('#') {
+                let weekday = parse_value(&item[..hash], 0, 7, &weekday_number)? % 7;
+                let n = parse_value(&item[hash + 1..], 1, 5, &|_| None)?;
+                self.nth_weekdays[weekday as usize] |= 1 << n;
+            } else if item.len() > 1 && (item.ends_with('L') || item.ends_with('l')) {
+                let weekday = parse_value(&item[..item.len() - 1], 0, 7, &weekday_number)? % 7;
+                self.last_weekdays |= 1 << weekday;
+            } else {
+                let bits = parse_item(item, 0, 7, &weekday_number)?;
+                // both 0 and 7 are Sunday
+                self.weekdays |= ((bits | bits >> 7) & 0x7f) as u8;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parsing a `str` into a `Schedule` accepts a cron expression with five or six fields,
+/// separated by whitespace. Names are case-insensitive.
+///
+/// # Example
+///
+/// ```
+/// use chrono::cron::Schedule;
+///
+/// assert!("*/5 * * * *".parse::<Schedule>().is_ok());
+/// assert!("0 0 12 ? JAN-MAR MON#2".parse::<Schedule>().is_ok());
+/// assert!("0 0 * *".parse::<Schedule>().is_err());
+/// assert!("60 * * * *".parse::<Schedule>().is_err());
+/// ```
+impl FromStr for Schedule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> ParseResult<Schedule> {
+        let mut fields = [""; 6];
+        let mut count = 0;
+        for field in s.split_whitespace() {
+            if count == 6 {
+                return Err(INVALID);
+            }
+            fields[count] = field;
+            count += 1;
+        }
+        let fields = match count {
+            5 => ["0", fields[0], fields[1], fields[2], fields[3], fields[4]],
+            6 => fields,
+            _ => return Err(INVALID),
+        };
+        // `?` is only meaningful for the day of month and the day of week
+        if [fields[0], fields[1], fields[2], fields[4]].iter().any(|field| field.contains('?')) {
+            return Err(INVALID);
+        }
+
+        let mut schedule = Schedule {
+            seconds: parse_field(fields[0], 0, 59, |_| None)?,
+            minutes: parse_field(fields[1], 0, 59, |_| None)?,
+            hours: parse_field(fields[2], 0, 23, |_| None)? as u32,
+            days: 0,
+            months: parse_field(fields[4], 1, 12, month_number)? as u16,
+            weekdays: 0,
+            last_day: false,
+            last_weekday: false,
+            nearest_weekdays: 0,
+            last_weekdays: 0,
+            nth_weekdays: [0; 7],
+            any_day: false,
+            any_weekday: false,
+        };
+        schedule.parse_days(fields[3])?;
+        schedule.parse_weekdays(fields[5])?;
+        Ok(schedule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Schedule;
+    use naive::NaiveDate;
+    use offset::test_zone::Eastern;
+    use offset::{FixedOffset, TimeZone, Utc};
+    use DateTime;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, n: u32, s: u32) -> DateTime<Utc> {
+        Utc.ymd(y, m, d).and_hms(h, n, s)
+    }
+
+    fn next(expr: &str, after: DateTime<Utc>, count: usize) -> Vec<DateTime<Utc>> {
+        expr.parse::<Schedule>().unwrap().iter_after(&after).take(count).collect()
+    }
+
+    #[test]
+    fn test_cron_from_str() {
+        for s in &[
+            "* * * * *",
+            "*/15 0-12/3 1,15 JAN-jun,Dec mon-FRI",
+            "0 0 0 ? * SUN#1,7L",
+            "0 0 L,LW,15W * ?",
+            "5/10 * * * * 0-7",
+            "  0 0  1 1 *  ",
+        ] {
+            assert!(s.parse::<Schedule>().is_ok(), "{:?} should parse", s);
+        }
+        for s in &[
+            "",
+            "* * * *",
+            "* * * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * 32 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "* * * * MON#6",
+            "* * * * FOO",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "? * * * *",
+            "* * 32W * *",
+            "1,,2 * * * *",
+        ] {
+            assert!(s.parse::<Schedule>().is_err(), "{:?} should not parse", s);
+        }
+        assert_eq!("0 0 * * 7".parse::<Schedule>(), "0 0 * * SUN".parse::<Schedule>());
+        assert_eq!("0 0 * * 0".parse::<Schedule>(), "0 0 * * sunday".parse::<Schedule>());
+        assert_eq!("0 0 * * 5-7".parse::<Schedule>(), "0 0 * * 0,5,6".parse::<Schedule>());
+        assert_eq!("0 0 * * *".parse::<Schedule>(), "0 0 0 ? * ?".parse::<Schedule>());
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        let start = utc(2021, 5, 1, 10, 7, 30);
+        assert_eq!(
+            next("*/15 * * * *", start, 3),
+            [utc(2021, 5, 1, 10, 15, 0), utc(2021, 5, 1, 10, 30, 0), utc(2021, 5, 1, 10, 45, 0)]
+        );
+        assert_eq!(
+            next("*/20 7 10 * * *", start, 3),
+            [utc(2021, 5, 1, 10, 7, 40), utc(2021, 5, 2, 10, 7, 0), utc(2021, 5, 2, 10, 7, 20)]
+        );
+        assert_eq!(
+            next("0 12 L * *", start, 3),
+            [utc(2021, 5, 31, 12, 0, 0), utc(2021, 6, 30, 12, 0, 0), utc(2021, 7, 31, 12, 0, 0)]
+        );
+        // the last weekday of July 2021 is Friday 30
+        assert_eq!(next("0 12 LW * *", utc(2021, 7, 1, 0, 0, 0), 1), [utc(2021, 7, 30, 12, 0, 0)]);
+        // May 15 2021 is a Saturday and May 1 too; October 31 2021 is a Sunday
+        assert_eq!(next("0 12 15W 5 *", start, 1), [utc(2021, 5, 14, 12, 0, 0)]);
+        assert_eq!(next("0 12 1W 5 *", start, 1), [utc(2021, 5, 3, 12, 0, 0)]);
+        assert_eq!(next("0 12 31W 10 *", start, 1), [utc(2021, 10, 29, 12, 0, 0)]);
+        assert_eq!(
+            next("0 9 ? * MON#2", start, 3),
+            [utc(2021, 5, 10, 9, 0, 0), utc(2021, 6, 14, 9, 0, 0), utc(2021, 7, 12, 9, 0, 0)]
+        );
+        assert_eq!(
+            next("0 9 * * 5L", start, 2),
+            [utc(2021, 5, 28, 9, 0, 0), utc(2021, 6, 25, 9, 0, 0)]
+        );
+        // both day fields restricted: either matches
+        assert_eq!(
+            next("0 0 13 * FRI", start, 3),
+            [utc(2021, 5, 7, 0, 0, 0), utc(2021, 5, 13, 0, 0, 0), utc(2021, 5, 14, 0, 0, 0)]
+        );
+        assert_eq!(
+            next("0 0 29 2 *", start, 2),
+            [utc(2024, 2, 29, 0, 0, 0), utc(2028, 2, 29, 0, 0, 0)]
+        );
+        assert!(next("0 0 30 2 *", start, 1).is_empty());
+        assert_eq!(next("0 0 1 1 *", utc(2020, 12, 31, 23, 59, 59), 1), [utc(2021, 1, 1, 0, 0, 0)]);
+
+        // sub-second precision
+        let start = Utc.ymd(2021, 5, 1).and_hms_milli(10, 0, 0, 1);
+        assert_eq!(next("* * * * * *", start, 1), [utc(2021, 5, 1, 10, 0, 1)]);
+
+        // other time zones use the local time
+        let kst = FixedOffset::east(9 * 3600);
+        let schedule = "0 9 * * *".parse::<Schedule>().unwrap();
+        assert_eq!(
+            schedule.next_after(&kst.ymd(2021, 5, 1).and_hms(9, 0, 0)),
+            Some(kst.ymd(2021, 5, 2).and_hms(9, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_cron_prev_before() {
+        let schedule = "0 */6 * * MON-FRI".parse::<Schedule>().unwrap();
+        let monday = utc(2021, 4, 19, 3, 0, 0);
+        assert_eq!(schedule.prev_before(&monday), Some(utc(2021, 4, 19, 0, 0, 0)));
+        assert_eq!(
+            schedule.prev_before(&utc(2021, 4, 19, 0, 0, 0)),
+            Some(utc(2021, 4, 16, 18, 0, 0))
+        );
+        let schedule = "0 0 L 2 *".parse::<Schedule>().unwrap();
+        assert_eq!(schedule.prev_before(&monday), Some(utc(2021, 2, 28, 0, 0, 0)));
+        assert_eq!(
+            schedule.prev_before(&utc(2020, 6, 1, 0, 0, 0)),
+            Some(utc(2020, 2, 29, 0, 0, 0))
+        );
+        let schedule = "0 0 30 2 *".parse::<Schedule>().unwrap();
+        assert_eq!(schedule.prev_before(&monday), None);
+
+        // next and previous agree
+        let schedule = "*/7 3-5 * * TUE,SAT".parse::<Schedule>().unwrap();
+        let times = schedule.iter_after(&monday).take(50).collect::<Vec<_>>();
+        for pair in times.windows(2) {
+            assert_eq!(schedule.prev_before(&pair[1]), Some(pair[0]));
+        }
+    }
+
+    #[test]
+    fn test_cron_dst() {
+        let local = |m, d, h, n| {
+            Eastern.from_local_datetime(&NaiveDate::from_ymd(2021, m, d).and_hms(h, n, 0))
+        };
+        let at = |m, d, h, n, dst: bool| {
+            let offset = if dst { Eastern::edt() } else { Eastern::est() };
+            let utc = NaiveDate::from_ymd(2021, m, d).and_hms(h, n, 0) - offset;
+            Eastern.from_utc_datetime(&utc)
+        };
+        let next = |expr: &str, after: DateTime<Eastern>, count| {
+            expr.parse::<Schedule>().unwrap().iter_after(&after).take(count).collect::<Vec<_>>()
+        };
+        let prev = |expr: &str, before: DateTime<Eastern>, count| {
+            let schedule = expr.parse::<Schedule>().unwrap();
+            let mut times = Vec::new();
+            let mut before = before;
+            for _ in 0..count {
+                before = schedule.prev_before(&before).unwrap();
+                times.push(before);
+            }
+            times
+        };
+
+        // 02:00 to 03:00 is skipped on March 14
+        let start = local(3, 13, 12, 0).unwrap();
+        assert_eq!(next("30 2 * * *", start, 2), [at(3, 14, 3, 30, true), at(3, 15, 2, 30, true)]);
+        assert_eq!(
+            next("30 * * * *", at(3, 14, 1, 45, false), 2),
+            [at(3, 14, 3, 30, true), at(3, 14, 4, 30, true)]
+        );
+        assert_eq!(
+            next("0,30 2,3 * * *", at(3, 14, 1, 0, false), 3),
+            [at(3, 14, 3, 0, true), at(3, 14, 3, 30, true), at(3, 15, 2, 0, true)]
+        );
+        assert_eq!(
+            prev("30 2 * * *", local(3, 14, 12, 0).unwrap(), 2),
+            [at(3, 14, 3, 30, true), at(3, 13, 2, 30, false)]
+        );
+
+        // 01:00 to 02:00 is repeated on November 7
+        let start = local(11, 7, 0, 0).unwrap();
+        assert_eq!(next("30 1 * * *", start, 2), [at(11, 7, 1, 30, true), at(11, 8, 1, 30, false)]);
+        assert_eq!(
+            next("30 * * * *", start, 4),
+            [
+                at(11, 7, 0, 30, true),
+                at(11, 7, 1, 30, true),
+                at(11, 7, 1, 30, false),
+                at(11, 7, 2, 30, false)
+            ]
+        );
+        assert_eq!(next("30 * * * *", at(11, 7, 1, 50, true), 1), [at(11, 7, 1, 30, false)]);
+        assert_eq!(
+            prev("30 * * * *", at(11, 7, 3, 0, false), 4),
+            [
+                at(11, 7, 2, 30, false),
+                at(11, 7, 1, 30, false),
+                at(11, 7, 1, 30, true),
+                at(11, 7, 0, 30, true)
+            ]
+        );
+        assert_eq!(prev("30 1 * * *", at(11, 7, 1, 45, false), 1), [at(11, 7, 1, 30, true)]);
+    }
+}
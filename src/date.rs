@@ -3,19 +3,20 @@
 
 //! ISO 8601 calendar date with time zone.
 
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use core::borrow::Borrow;
 use core::cmp::Ordering;
 use core::ops::{Add, Sub};
-use core::{fmt, hash};
+use core::{fmt, hash, str};
 use oldtime::Duration as OldDuration;
 
 #[cfg(feature = "unstable-locales")]
 use format::Locale;
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use format::{DelayedFormat, Item, StrftimeItems};
+use format::{ParseError, ParseResult, INVALID, OUT_OF_RANGE};
 use naive::{self, IsoWeek, NaiveDate, NaiveTime};
-use offset::{TimeZone, Utc};
+#[cfg(feature = "clock")]
+use offset::Local;
+use offset::{FixedOffset, TimeZone, Utc};
 use DateTime;
 use {Datelike, Weekday};
 
@@ -286,7 +287,6 @@ where
     Tz::Offset: fmt::Display,
 {
     /// Formats the date with the specified formatting items.
-    #[cfg(any(feature = "alloc", feature = "std", test))]
     #[inline]
     pub fn format_with_items<'a, I, B>(&self, items: I) -> DelayedFormat<I>
     where
@@ -308,7 +308,6 @@ where
     /// let formatted = format!("{}", date_time.format("%d/%m/%Y"));
     /// assert_eq!(formatted, "02/04/2017");
     /// ```
-    #[cfg(any(feature = "alloc", feature = "std", test))]
     #[inline]
     pub fn format<'a>(&self, fmt: &'a str) -> DelayedFormat<StrftimeItems<'a>> {
         self.format_with_items(StrftimeItems::new(fmt))
@@ -494,3 +493,161 @@ where
         write!(f, "{}{}", self.naive_local(), self.offset)
     }
 }
+
+/// Parsing the `Debug` form of a `Date`, i.e. a date followed by an offset like
+/// `2015-09-05+09:00`, or by `Z` or `UTC` for UTC.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Date, FixedOffset, TimeZone};
+///
+/// let date = FixedOffset::east(9 * 3600).ymd(2015, 9, 5);
+/// assert_eq!("2015-09-05+09:00".parse::<Date<FixedOffset>>(), Ok(date));
+/// assert_eq!(format!("{:?}", date).parse::<Date<FixedOffset>>(), Ok(date));
+/// assert!("2015-09-05".parse::<Date<FixedOffset>>().is_err());
+/// ```
+impl str::FromStr for Date<FixedOffset> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> ParseResult<Date<FixedOffset>> {
+        let (date, offset) = if s.ends_with('Z') {
+            (&s[..s.len() - 1], FixedOffset::east(0))
+        } else if s.ends_with("UTC") {
+            (&s[..s.len() - 3], FixedOffset::east(0))
+        } else {
+            let sep = s.rfind(|c| c == '+' || c == '-').ok_or(INVALID)?;
+            (&s[..sep], s[sep..].parse()?)
+        };
+        let date = date.parse::<NaiveDate>()?;
+        offset.from_local_date(&date).single().ok_or(OUT_OF_RANGE)
+    }
+}
+
+impl str::FromStr for Date<Utc> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> ParseResult<Date<Utc>> {
+        s.parse::<Date<FixedOffset>>().map(|date| date.with_timezone(&Utc))
+    }
+}
+
+#[cfg(feature = "clock")]
+impl str::FromStr for Date<Local> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> ParseResult<Date<Local>> {
+        s.parse::<Date<FixedOffset>>().map(|date| date.with_timezone(&Local))
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::Date;
+    use core::fmt;
+    #[cfg(feature = "clock")]
+    use offset::Local;
+    use offset::{FixedOffset, TimeZone, Utc};
+    use serdelib::{de, ser};
+
+    /// Serialize into the `Debug` form, e.g. `2015-09-05+09:00` or `2015-09-05Z`
+    impl<Tz: TimeZone> ser::Serialize for Date<Tz> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            struct FormatWrapped<'a, D: 'a> {
+                inner: &'a D,
+            }
+
+            impl<'a, D: fmt::Debug> fmt::Display for FormatWrapped<'a, D> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    self.inner.fmt(f)
+                }
+            }
+
+            serializer.collect_str(&FormatWrapped { inner: &self })
+        }
+    }
+
+    struct DateVisitor;
+
+    impl<'de> de::Visitor<'de> for DateVisitor {
+        type Value = Date<FixedOffset>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a formatted date with an offset like `2015-09-05+09:00`")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Date<FixedOffset>, E>
+        where
+            E: de::Error,
+        {
+            value.parse().map_err(E::custom)
+        }
+    }
+
+    impl<'de> de::Deserialize<'de> for Date<FixedOffset> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_str(DateVisitor)
+        }
+    }
+
+    impl<'de> de::Deserialize<'de> for Date<Utc> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_str(DateVisitor).map(|date| date.with_timezone(&Utc))
+        }
+    }
+
+    #[cfg(feature = "clock")]
+    impl<'de> de::Deserialize<'de> for Date<Local> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_str(DateVisitor).map(|date| date.with_timezone(&Local))
+        }
+    }
+
+    #[cfg(test)]
+    extern crate bincode;
+    #[cfg(test)]
+    extern crate serde_json;
+
+    #[test]
+    fn test_serde_json() {
+        use self::serde_json::{from_str, to_string};
+
+        let date = FixedOffset::west(5 * 3600).ymd(2014, 7, 24);
+        assert_eq!(to_string(&date).ok(), Some(r#""2014-07-24-05:00""#.into()));
+        assert_eq!(from_str::<Date<FixedOffset>>(r#""2014-07-24-05:00""#).ok(), Some(date));
+        assert_eq!(to_string(&Utc.ymd(2014, 7, 24)).ok(), Some(r#""2014-07-24Z""#.into()));
+        assert_eq!(from_str::<Date<Utc>>(r#""2014-07-24Z""#).ok(), Some(Utc.ymd(2014, 7, 24)));
+        assert_eq!(from_str::<Date<Utc>>(r#""2014-07-24UTC""#).ok(), Some(Utc.ymd(2014, 7, 24)));
+        assert_eq!(from_str::<Date<Utc>>(r#""-0001-12-31+00:00""#).ok(), Some(Utc.ymd(-1, 12, 31)));
+        assert!(from_str::<Date<Utc>>(r#""2014-07-24""#).is_err());
+        assert!(from_str::<Date<Utc>>(r#""2014-07-32Z""#).is_err());
+        assert!(from_str::<Date<Utc>>(r#""2014-07-24+25:00""#).is_err());
+        #[cfg(feature = "clock")]
+        assert!(from_str::<Date<Local>>(r#""2014-07-24+01:00""#).is_ok());
+    }
+
+    #[test]
+    fn test_serde_bincode() {
+        // Bincode is relevant to test separately from JSON because
+        // it is not self-describing.
+        use self::bincode::{deserialize, serialize, Infinite};
+
+        let date = FixedOffset::east(3600).ymd(2014, 7, 24);
+        let encoded = serialize(&date, Infinite).unwrap();
+        let decoded: Date<FixedOffset> = deserialize(&encoded).unwrap();
+        assert_eq!(date, decoded);
+        assert_eq!(date.offset(), decoded.offset());
+    }
+}
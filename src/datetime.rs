@@ -4,6 +4,7 @@
 //! ISO 8601 date and time with time zone.
 
 use core::cmp::Ordering;
+use core::iter::FusedIterator;
 use core::ops::{Add, Sub};
 use core::{fmt, hash, str};
 use oldtime::Duration as OldDuration;
@@ -15,20 +16,19 @@ use alloc::string::{String, ToString};
 #[cfg(feature = "std")]
 use std::string::ToString;
 
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use core::borrow::Borrow;
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use format::DelayedFormat;
 #[cfg(feature = "unstable-locales")]
 use format::Locale;
 use format::{parse, ParseError, ParseResult, Parsed, StrftimeItems};
 use format::{Fixed, Item};
 use naive::{self, IsoWeek, NaiveDate, NaiveDateTime, NaiveTime};
+use num_traits::ToPrimitive;
 #[cfg(feature = "clock")]
 use offset::Local;
-use offset::{FixedOffset, Offset, TimeZone, Utc};
+use offset::{FixedOffset, LocalResult, Offset, TimeZone, Utc};
 use Date;
-use {Datelike, Timelike, Weekday};
+use {Datelike, Period, Timelike, Weekday};
 
 /// Specific formatting options for seconds. This may be extended in the
 /// future, so exhaustive matching in external code is not recommended.
@@ -297,6 +297,108 @@ impl<Tz: TimeZone> DateTime<Tz> {
         self.datetime.signed_duration_since(rhs.datetime)
     }
 
+    /// Subtracts another `DateTime` from the current date and time,
+    /// returning the calendar [`Period`](./struct.Period.html) between their wall-clock times.
+    ///
+    /// `rhs` is first converted to the time zone of `self`,
+    /// then the difference of the local date and time is computed with
+    /// [`NaiveDateTime::period_since`](./naive/struct.NaiveDateTime.html#method.period_since).
+    /// As such, a day across a daylight saving time transition still counts as one day
+    /// and the time remainder is the difference in wall-clock time, not the elapsed time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate chrono; fn main() {
+    /// use chrono::{Duration, FixedOffset, Period, TimeZone, Utc};
+    ///
+    /// let start = Utc.ymd(2020, 1, 15).and_hms(23, 0, 0);
+    /// let end = FixedOffset::east(9 * 3600).ymd(2020, 4, 17).and_hms(10, 0, 0);
+    /// assert_eq!(end.period_since(start), Period::with_time(0, 3, 1, Duration::hours(2)));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn period_since<Tz2: TimeZone>(self, rhs: DateTime<Tz2>) -> Period {
+        self.naive_local().period_since(rhs.with_timezone(&self.timezone()).naive_local())
+    }
+
+    /// Adds a [`Period`](./struct.Period.html) to the current date and time.
+    ///
+    /// The years, months and days are added to the local date as in
+    /// [`NaiveDate::checked_add_period`](./naive/struct.NaiveDate.html#method.checked_add_period),
+    /// keeping the local time of day, and then the time remainder of the period
+    /// is added as an exact `Duration`.
+    ///
+    /// Returns `None` when it will result in overflow,
+    /// or when the intermediate local date and time does not exist or is ambiguous
+    /// in the time zone (e.g. because of a daylight saving time transition).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate chrono; fn main() {
+    /// use chrono::{Duration, Period, TimeZone, Utc};
+    ///
+    /// let dt = Utc.ymd(2019, 1, 31).and_hms(18, 0, 0);
+    /// assert_eq!(dt.checked_add_period(Period::new(0, 1, 0)),
+    ///            Some(Utc.ymd(2019, 2, 28).and_hms(18, 0, 0)));
+    /// assert_eq!(dt.checked_add_period(Period::with_time(0, 0, 1, Duration::hours(7))),
+    ///            Some(Utc.ymd(2019, 2, 2).and_hms(1, 0, 0)));
+    /// # }
+    /// ```
+    pub fn checked_add_period(self, rhs: Period) -> Option<DateTime<Tz>> {
+        let date_part = Period::new(rhs.years(), rhs.months(), rhs.days());
+        let dt = try_opt!(map_local(&self, |dt| dt.checked_add_period(date_part)));
+        dt.checked_add_signed(rhs.time())
+    }
+
+    /// Returns an iterator from the current date and time (inclusive)
+    /// to `end` (exclusive) with the given step.
+    ///
+    /// Calendar steps are evaluated in the local wall-clock time;
+    /// see [`DateTimeIterator`](./struct.DateTimeIterator.html) for the handling of
+    /// daylight saving time transitions.
+    ///
+    /// Panics if the step is not positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate chrono; fn main() {
+    /// use chrono::{DateTimeStep, Duration, TimeZone, Utc};
+    ///
+    /// let start = Utc.ymd(2020, 1, 31).and_hms(12, 0, 0);
+    /// let end = Utc.ymd(2020, 5, 1).and_hms(0, 0, 0);
+    /// let months = start.iter_until(end, DateTimeStep::Months(1)).collect::<Vec<_>>();
+    /// assert_eq!(months, [Utc.ymd(2020, 1, 31).and_hms(12, 0, 0),
+    ///                     Utc.ymd(2020, 2, 29).and_hms(12, 0, 0),
+    ///                     Utc.ymd(2020, 3, 31).and_hms(12, 0, 0),
+    ///                     Utc.ymd(2020, 4, 30).and_hms(12, 0, 0)]);
+    ///
+    /// let end = start + Duration::hours(1);
+    /// let quarters = start.iter_until(end, DateTimeStep::Exact(Duration::minutes(15)));
+    /// assert_eq!(quarters.count(), 4);
+    /// # }
+    /// ```
+    pub fn iter_until(&self, end: DateTime<Tz>, step: DateTimeStep) -> DateTimeIterator<Tz> {
+        let positive = match step {
+            DateTimeStep::Exact(duration) => duration > OldDuration::zero(),
+            DateTimeStep::Hours(n)
+            | DateTimeStep::Days(n)
+            | DateTimeStep::Weeks(n)
+            | DateTimeStep::Months(n) => n > 0,
+        };
+        assert!(positive, "step should be positive");
+        DateTimeIterator {
+            start: self.clone(),
+            end: end,
+            step: step,
+            index: 0,
+            last: None,
+            finished: false,
+        }
+    }
+
     /// Returns a view to the naive UTC datetime.
     #[inline]
     pub fn naive_utc(&self) -> NaiveDateTime {
@@ -406,6 +508,129 @@ where
     f(dt.naive_local()).and_then(|datetime| dt.timezone().from_local_datetime(&datetime).single())
 }
 
+/// Converts the local date and time to the timezone-aware date and time,
+/// resolving daylight saving time transitions like most calendar software does.
+///
+/// An ambiguous local time (in a fold) resolves to the earlier of both instants.
+/// A nonexistent local time (in a gap) is shifted forward by the length of the gap,
+/// i.e. it is interpreted with the offset in effect just before the gap.
+pub(crate) fn resolve_local<Tz: TimeZone>(tz: &Tz, local: &NaiveDateTime) -> Option<DateTime<Tz>> {
+    match tz.from_local_datetime(local) {
+        LocalResult::Single(dt) => Some(dt),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest),
+        LocalResult::None => {
+            let before = try_opt!(local.checked_sub_signed(OldDuration::days(1)));
+            let offset = try_opt!(tz.offset_from_local_datetime(&before).earliest()).fix();
+            Some(tz.from_utc_datetime(&(*local - offset)))
+        }
+    }
+}
+
+/// The step size of [`DateTimeIterator`](./struct.DateTimeIterator.html).
+///
+/// Calendar units are evaluated in the local wall-clock time of the time zone,
+/// so a daily step keeps the local time of day across daylight saving time transitions
+/// (and the elapsed time between two items may be 23 or 25 hours).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DateTimeStep {
+    /// The exact amount of elapsed time, regardless of the local time. Should be positive.
+    Exact(OldDuration),
+    /// The given number of hours in the local wall-clock time. Should be positive.
+    Hours(u32),
+    /// The given number of days in the local wall-clock time. Should be positive.
+    Days(u32),
+    /// The given number of weeks in the local wall-clock time. Should be positive.
+    Weeks(u32),
+    /// The given number of months in the local wall-clock time. Should be positive.
+    ///
+    /// The day of month is clamped to the last day of shorter months,
+    /// as in [`NaiveDate::checked_add_period`](./naive/struct.NaiveDate.html#method.checked_add_period).
+    Months(u32),
+}
+
+/// Iterator over `DateTime<Tz>` from a start (inclusive) to an end (exclusive) with a given step.
+///
+/// This is created by [`DateTime::iter_until`](./struct.DateTime.html#method.iter_until).
+///
+/// Every item is computed from the start rather than from the previous item,
+/// so a local time adjusted by a daylight saving time transition or a short month
+/// is restored in the following items.
+/// When stepping by calendar units, local times are resolved as follows:
+///
+/// - An ambiguous local time (e.g. when clocks are turned back) uses the earlier offset,
+///   so the repeated hour appears only once when stepping by hours.
+/// - A nonexistent local time (e.g. when clocks are turned forward) is shifted forward
+///   by the length of the gap, so 02:30 becomes 03:30 when clocks skip from 02:00 to 03:00.
+/// - Items are strictly increasing; an item that resolves to the same instant as
+///   (or an earlier instant than) the previous item is skipped.
+#[derive(Clone, Debug)]
+pub struct DateTimeIterator<Tz: TimeZone> {
+    start: DateTime<Tz>,
+    end: DateTime<Tz>,
+    step: DateTimeStep,
+    index: i64,
+    last: Option<DateTime<Tz>>,
+    finished: bool,
+}
+
+impl<Tz: TimeZone> DateTimeIterator<Tz> {
+    /// Returns the item at given index, without checking the end or the previous item.
+    fn item(&self, index: i64) -> Option<DateTime<Tz>> {
+        if index == 0 {
+            return Some(self.start.clone());
+        }
+        let local = self.start.naive_local();
+        let local = match self.step {
+            DateTimeStep::Exact(duration) => {
+                // elapsed time is simply accumulated
+                let last = self.last.clone().unwrap_or_else(|| self.start.clone());
+                return last.checked_add_signed(duration);
+            }
+            DateTimeStep::Hours(n) => {
+                local.checked_add_signed(OldDuration::hours(i64::from(n) * index))
+            }
+            DateTimeStep::Days(n) => {
+                local.checked_add_signed(OldDuration::days(i64::from(n) * index))
+            }
+            DateTimeStep::Weeks(n) => {
+                local.checked_add_signed(OldDuration::weeks(i64::from(n) * index))
+            }
+            DateTimeStep::Months(n) => {
+                let months = try_opt!((i64::from(n) * index).to_i32());
+                local.checked_add_period(Period::new(0, months, 0))
+            }
+        };
+        resolve_local(&self.start.timezone(), &try_opt!(local))
+    }
+}
+
+impl<Tz: TimeZone> Iterator for DateTimeIterator<Tz> {
+    type Item = DateTime<Tz>;
+
+    fn next(&mut self) -> Option<DateTime<Tz>> {
+        while !self.finished {
+            let item = match self.item(self.index).filter(|item| *item < self.end) {
+                Some(item) => item,
+                None => {
+                    self.finished = true;
+                    break;
+                }
+            };
+            self.index += 1;
+            if let Some(ref last) = self.last {
+                if item <= *last {
+                    continue;
+                }
+            }
+            self.last = Some(item.clone());
+            return Some(item);
+        }
+        None
+    }
+}
+
+impl<Tz: TimeZone> FusedIterator for DateTimeIterator<Tz> {}
+
 impl DateTime<FixedOffset> {
     /// Parses an RFC 2822 date and time string such as `Tue, 1 Jul 2003 10:52:37 +0200`,
     /// then returns a new [`DateTime`] with a parsed [`FixedOffset`].
@@ -554,7 +779,6 @@ where
     }
 
     /// Formats the combined date and time with the specified formatting items.
-    #[cfg(any(feature = "alloc", feature = "std", test))]
     #[inline]
     pub fn format_with_items<'a, I, B>(&self, items: I) -> DelayedFormat<I>
     where
@@ -577,7 +801,6 @@ where
     /// let formatted = format!("{}", date_time.format("%d/%m/%Y %H:%M"));
     /// assert_eq!(formatted, "02/04/2017 12:50");
     /// ```
-    #[cfg(any(feature = "alloc", feature = "std", test))]
     #[inline]
     pub fn format<'a>(&self, fmt: &'a str) -> DelayedFormat<StrftimeItems<'a>> {
         self.format_with_items(StrftimeItems::new(fmt))
@@ -1180,10 +1403,12 @@ pub mod rustc_serialize {
 #[cfg(feature = "serde")]
 pub mod serde {
     use super::DateTime;
+    use compact_serde::compact::Compact;
     use core::fmt;
+    use naive::NaiveDateTime;
     #[cfg(feature = "clock")]
     use offset::Local;
-    use offset::{FixedOffset, LocalResult, TimeZone, Utc};
+    use offset::{FixedOffset, LocalResult, Offset, TimeZone, Utc};
     use serdelib::{de, ser};
     use {ne_timestamp, SerdeError};
 
@@ -1199,6 +1424,14 @@ pub mod serde {
     #[derive(Debug)]
     pub struct MilliSecondsTimestampVisitor;
 
+    #[doc(hidden)]
+    #[derive(Debug)]
+    pub struct MicroSecondsTimestampVisitor;
+
+    #[doc(hidden)]
+    #[derive(Debug)]
+    pub struct FloatSecondsTimestampVisitor;
+
     // lik? function to convert a LocalResult into a serde-ish Result
     fn serde_from<T, E, V>(me: LocalResult<T>, ts: &V) -> Result<T, E>
     where
@@ -1255,12 +1488,13 @@ pub mod serde {
         use core::fmt;
         use serdelib::{de, ser};
 
+        use div::div_mod_floor;
         use offset::TimeZone;
         use {DateTime, Utc};
 
         use super::{serde_from, NanoSecondsTimestampVisitor};
 
-        /// Serialize a UTC datetime into an integer number of nanoseconds since the epoch
+        /// Serialize a datetime into an integer number of nanoseconds since the epoch
         ///
         /// Intended for use with `serde`s `serialize_with` attribute.
         ///
@@ -1292,9 +1526,10 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn serialize<S>(dt: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+        pub fn serialize<S, Tz>(dt: &DateTime<Tz>, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: ser::Serializer,
+            Tz: TimeZone,
         {
             serializer.serialize_i64(dt.timestamp_nanos())
         }
@@ -1327,11 +1562,13 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn deserialize<'de, D>(d: D) -> Result<DateTime<Utc>, D::Error>
+        pub fn deserialize<'de, D, Tz>(d: D) -> Result<DateTime<Tz>, D::Error>
         where
             D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<Utc>>,
         {
-            Ok(d.deserialize_i64(NanoSecondsTimestampVisitor)?)
+            d.deserialize_i64(NanoSecondsTimestampVisitor).map(DateTime::from)
         }
 
         impl<'de> de::Visitor<'de> for NanoSecondsTimestampVisitor {
@@ -1346,10 +1583,8 @@ pub mod serde {
             where
                 E: de::Error,
             {
-                serde_from(
-                    Utc.timestamp_opt(value / 1_000_000_000, (value % 1_000_000_000) as u32),
-                    &value,
-                )
+                let (secs, nsecs) = div_mod_floor(value, 1_000_000_000);
+                serde_from(Utc.timestamp_opt(secs, nsecs as u32), &value)
             }
 
             /// Deserialize a timestamp in nanoseconds since the epoch
@@ -1409,12 +1644,13 @@ pub mod serde {
 
         use serdelib::{de, ser};
 
+        use div::div_mod_floor;
         use offset::TimeZone;
         use {DateTime, Utc};
 
-        use super::serde_from;
+        use super::{serde_from, MicroSecondsTimestampVisitor};
 
-        /// Serialize a UTC datetime into an integer number of microseconds since the epoch
+        /// Serialize a datetime into an integer number of microseconds since the epoch
         ///
         /// Intended for use with `serde`s `serialize_with` attribute.
         ///
@@ -1446,9 +1682,10 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn serialize<S>(dt: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+        pub fn serialize<S, Tz>(dt: &DateTime<Tz>, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: ser::Serializer,
+            Tz: TimeZone,
         {
             serializer.serialize_i64(dt.timestamp_micros())
         }
@@ -1481,16 +1718,15 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn deserialize<'de, D>(d: D) -> Result<DateTime<Utc>, D::Error>
+        pub fn deserialize<'de, D, Tz>(d: D) -> Result<DateTime<Tz>, D::Error>
         where
             D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<Utc>>,
         {
-            #[allow(deprecated)]
-            Ok(try!(d.deserialize_i64(MicroSecondsTimestampVisitor)))
+            d.deserialize_i64(MicroSecondsTimestampVisitor).map(DateTime::from)
         }
 
-        struct MicroSecondsTimestampVisitor;
-
         impl<'de> de::Visitor<'de> for MicroSecondsTimestampVisitor {
             type Value = DateTime<Utc>;
 
@@ -1503,10 +1739,8 @@ pub mod serde {
             where
                 E: de::Error,
             {
-                serde_from(
-                    Utc.timestamp_opt(value / 1000_000, ((value % 1000_000) * 1_000) as u32),
-                    &value,
-                )
+                let (secs, micros) = div_mod_floor(value, 1_000_000);
+                serde_from(Utc.timestamp_opt(secs, micros as u32 * 1_000), &value)
             }
 
             /// Deserialize a timestamp in milliseconds since the epoch
@@ -1565,11 +1799,12 @@ pub mod serde {
         use core::fmt;
         use serdelib::{de, ser};
 
+        use offset::TimeZone;
         use {DateTime, Utc};
 
         use super::NanoSecondsTimestampVisitor;
 
-        /// Serialize a UTC datetime into an integer number of nanoseconds since the epoch or none
+        /// Serialize a datetime into an integer number of nanoseconds since the epoch or none
         ///
         /// Intended for use with `serde`s `serialize_with` attribute.
         ///
@@ -1601,9 +1836,13 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn serialize<S>(opt: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+        pub fn serialize<S, Tz>(
+            opt: &Option<DateTime<Tz>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
         where
             S: ser::Serializer,
+            Tz: TimeZone,
         {
             match *opt {
                 Some(ref dt) => serializer.serialize_some(&dt.timestamp_nanos()),
@@ -1639,11 +1878,14 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn deserialize<'de, D>(d: D) -> Result<Option<DateTime<Utc>>, D::Error>
+        pub fn deserialize<'de, D, Tz>(d: D) -> Result<Option<DateTime<Tz>>, D::Error>
         where
             D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<Utc>>,
         {
-            Ok(d.deserialize_option(OptionNanoSecondsTimestampVisitor)?)
+            d.deserialize_option(OptionNanoSecondsTimestampVisitor)
+                .map(|opt| opt.map(DateTime::from))
         }
 
         struct OptionNanoSecondsTimestampVisitor;
@@ -1681,6 +1923,158 @@ pub mod serde {
         }
     }
 
+    /// Ser/de to/from optional timestamps in microseconds
+    ///
+    /// Intended for use with `serde`'s `with` attribute.
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate serde_derive;
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate chrono;
+    /// # use chrono::{TimeZone, DateTime, Utc};
+    /// use chrono::serde::ts_microseconds_option;
+    /// #[derive(Deserialize, Serialize)]
+    /// struct S {
+    ///     #[serde(with = "ts_microseconds_option")]
+    ///     time: Option<DateTime<Utc>>
+    /// }
+    ///
+    /// # fn example() -> Result<S, serde_json::Error> {
+    /// let time = Some(Utc.ymd(2018, 5, 17).and_hms_micro(02, 04, 59, 918355));
+    /// let my_s = S {
+    ///     time: time.clone(),
+    /// };
+    ///
+    /// let as_string = serde_json::to_string(&my_s)?;
+    /// assert_eq!(as_string, r#"{"time":1526522699918355}"#);
+    /// let my_s: S = serde_json::from_str(&as_string)?;
+    /// assert_eq!(my_s.time, time);
+    /// # Ok(my_s)
+    /// # }
+    /// # fn main() { example().unwrap(); }
+    /// ```
+    pub mod ts_microseconds_option {
+        use core::fmt;
+        use serdelib::{de, ser};
+
+        use offset::TimeZone;
+        use {DateTime, Utc};
+
+        use super::MicroSecondsTimestampVisitor;
+
+        /// Serialize a datetime into an integer number of microseconds since the epoch or none
+        ///
+        /// Intended for use with `serde`s `serialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate serde_derive;
+        /// # #[macro_use] extern crate serde_json;
+        /// # extern crate chrono;
+        /// # use chrono::{TimeZone, DateTime, Utc};
+        /// use chrono::serde::ts_microseconds_option::serialize as to_micro_tsopt;
+        /// #[derive(Serialize)]
+        /// struct S {
+        ///     #[serde(serialize_with = "to_micro_tsopt")]
+        ///     time: Option<DateTime<Utc>>
+        /// }
+        ///
+        /// # fn example() -> Result<String, serde_json::Error> {
+        /// let my_s = S {
+        ///     time: Some(Utc.ymd(2018, 5, 17).and_hms_micro(02, 04, 59, 918355)),
+        /// };
+        /// let as_string = serde_json::to_string(&my_s)?;
+        /// assert_eq!(as_string, r#"{"time":1526522699918355}"#);
+        /// # Ok(as_string)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn serialize<S, Tz>(
+            opt: &Option<DateTime<Tz>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+            Tz: TimeZone,
+        {
+            match *opt {
+                Some(ref dt) => serializer.serialize_some(&dt.timestamp_micros()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        /// Deserialize a `DateTime` from a microsecond timestamp or none
+        ///
+        /// Intended for use with `serde`s `deserialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate serde_derive;
+        /// # #[macro_use] extern crate serde_json;
+        /// # extern crate chrono;
+        /// # use chrono::{DateTime, Utc};
+        /// use chrono::serde::ts_microseconds_option::deserialize as from_micro_tsopt;
+        /// #[derive(Deserialize)]
+        /// struct S {
+        ///     #[serde(deserialize_with = "from_micro_tsopt")]
+        ///     time: Option<DateTime<Utc>>
+        /// }
+        ///
+        /// # fn example() -> Result<S, serde_json::Error> {
+        /// let my_s: S = serde_json::from_str(r#"{ "time": 1526522699918355 }"#)?;
+        /// # Ok(my_s)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn deserialize<'de, D, Tz>(d: D) -> Result<Option<DateTime<Tz>>, D::Error>
+        where
+            D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<Utc>>,
+        {
+            d.deserialize_option(OptionMicroSecondsTimestampVisitor)
+                .map(|opt| opt.map(DateTime::from))
+        }
+
+        struct OptionMicroSecondsTimestampVisitor;
+
+        impl<'de> de::Visitor<'de> for OptionMicroSecondsTimestampVisitor {
+            type Value = Option<DateTime<Utc>>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a unix timestamp in microseconds or none")
+            }
+
+            /// Deserialize a timestamp in microseconds since the epoch
+            fn visit_some<D>(self, d: D) -> Result<Option<DateTime<Utc>>, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                d.deserialize_i64(MicroSecondsTimestampVisitor).map(Some)
+            }
+
+            /// Deserialize a timestamp in microseconds since the epoch
+            fn visit_none<E>(self) -> Result<Option<DateTime<Utc>>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+
+            /// Deserialize a timestamp in microseconds since the epoch
+            fn visit_unit<E>(self) -> Result<Option<DateTime<Utc>>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+        }
+    }
+
     /// Ser/de to/from timestamps in milliseconds
     ///
     /// Intended for use with `serde`s `with` attribute.
@@ -1721,12 +2115,13 @@ pub mod serde {
         use core::fmt;
         use serdelib::{de, ser};
 
+        use div::div_mod_floor;
         use offset::TimeZone;
         use {DateTime, Utc};
 
         use super::{serde_from, MilliSecondsTimestampVisitor};
 
-        /// Serialize a UTC datetime into an integer number of milliseconds since the epoch
+        /// Serialize a datetime into an integer number of milliseconds since the epoch
         ///
         /// Intended for use with `serde`s `serialize_with` attribute.
         ///
@@ -1758,9 +2153,10 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn serialize<S>(dt: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+        pub fn serialize<S, Tz>(dt: &DateTime<Tz>, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: ser::Serializer,
+            Tz: TimeZone,
         {
             serializer.serialize_i64(dt.timestamp_millis())
         }
@@ -1793,11 +2189,13 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn deserialize<'de, D>(d: D) -> Result<DateTime<Utc>, D::Error>
+        pub fn deserialize<'de, D, Tz>(d: D) -> Result<DateTime<Tz>, D::Error>
         where
             D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<Utc>>,
         {
-            Ok(d.deserialize_i64(MilliSecondsTimestampVisitor).map(|dt| dt.with_timezone(&Utc))?)
+            d.deserialize_i64(MilliSecondsTimestampVisitor).map(DateTime::from)
         }
 
         impl<'de> de::Visitor<'de> for MilliSecondsTimestampVisitor {
@@ -1812,10 +2210,8 @@ pub mod serde {
             where
                 E: de::Error,
             {
-                serde_from(
-                    Utc.timestamp_opt(value / 1000, ((value % 1000) * 1_000_000) as u32),
-                    &value,
-                )
+                let (secs, millis) = div_mod_floor(value, 1000);
+                serde_from(Utc.timestamp_opt(secs, millis as u32 * 1_000_000), &value)
             }
 
             /// Deserialize a timestamp in milliseconds since the epoch
@@ -1871,11 +2267,12 @@ pub mod serde {
         use core::fmt;
         use serdelib::{de, ser};
 
+        use offset::TimeZone;
         use {DateTime, Utc};
 
         use super::MilliSecondsTimestampVisitor;
 
-        /// Serialize a UTC datetime into an integer number of milliseconds since the epoch or none
+        /// Serialize a datetime into an integer number of milliseconds since the epoch or none
         ///
         /// Intended for use with `serde`s `serialize_with` attribute.
         ///
@@ -1907,9 +2304,13 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn serialize<S>(opt: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+        pub fn serialize<S, Tz>(
+            opt: &Option<DateTime<Tz>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
         where
             S: ser::Serializer,
+            Tz: TimeZone,
         {
             match *opt {
                 Some(ref dt) => serializer.serialize_some(&dt.timestamp_millis()),
@@ -1957,12 +2358,14 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn deserialize<'de, D>(d: D) -> Result<Option<DateTime<Utc>>, D::Error>
+        pub fn deserialize<'de, D, Tz>(d: D) -> Result<Option<DateTime<Tz>>, D::Error>
         where
             D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<Utc>>,
         {
-            Ok(d.deserialize_option(OptionMilliSecondsTimestampVisitor)
-                .map(|opt| opt.map(|dt| dt.with_timezone(&Utc)))?)
+            d.deserialize_option(OptionMilliSecondsTimestampVisitor)
+                .map(|opt| opt.map(DateTime::from))
         }
 
         struct OptionMilliSecondsTimestampVisitor;
@@ -2045,7 +2448,7 @@ pub mod serde {
 
         use super::{serde_from, SecondsTimestampVisitor};
 
-        /// Serialize a UTC datetime into an integer number of seconds since the epoch
+        /// Serialize a datetime into an integer number of seconds since the epoch
         ///
         /// Intended for use with `serde`s `serialize_with` attribute.
         ///
@@ -2077,9 +2480,10 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn serialize<S>(dt: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+        pub fn serialize<S, Tz>(dt: &DateTime<Tz>, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: ser::Serializer,
+            Tz: TimeZone,
         {
             serializer.serialize_i64(dt.timestamp())
         }
@@ -2112,11 +2516,13 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn deserialize<'de, D>(d: D) -> Result<DateTime<Utc>, D::Error>
+        pub fn deserialize<'de, D, Tz>(d: D) -> Result<DateTime<Tz>, D::Error>
         where
             D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<Utc>>,
         {
-            Ok(d.deserialize_i64(SecondsTimestampVisitor)?)
+            d.deserialize_i64(SecondsTimestampVisitor).map(DateTime::from)
         }
 
         impl<'de> de::Visitor<'de> for SecondsTimestampVisitor {
@@ -2184,11 +2590,12 @@ pub mod serde {
         use core::fmt;
         use serdelib::{de, ser};
 
+        use offset::TimeZone;
         use {DateTime, Utc};
 
         use super::SecondsTimestampVisitor;
 
-        /// Serialize a UTC datetime into an integer number of seconds since the epoch or none
+        /// Serialize a datetime into an integer number of seconds since the epoch or none
         ///
         /// Intended for use with `serde`s `serialize_with` attribute.
         ///
@@ -2220,9 +2627,13 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn serialize<S>(opt: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+        pub fn serialize<S, Tz>(
+            opt: &Option<DateTime<Tz>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
         where
             S: ser::Serializer,
+            Tz: TimeZone,
         {
             match *opt {
                 Some(ref dt) => serializer.serialize_some(&dt.timestamp()),
@@ -2258,11 +2669,13 @@ pub mod serde {
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn deserialize<'de, D>(d: D) -> Result<Option<DateTime<Utc>>, D::Error>
+        pub fn deserialize<'de, D, Tz>(d: D) -> Result<Option<DateTime<Tz>>, D::Error>
         where
             D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<Utc>>,
         {
-            Ok(d.deserialize_option(OptionSecondsTimestampVisitor)?)
+            d.deserialize_option(OptionSecondsTimestampVisitor).map(|opt| opt.map(DateTime::from))
         }
 
         struct OptionSecondsTimestampVisitor;
@@ -2300,56 +2713,566 @@ pub mod serde {
         }
     }
 
-    impl<Tz: TimeZone> ser::Serialize for DateTime<Tz> {
-        /// Serialize into a rfc3339 time string
-        ///
-        /// See [the `serde` module](./serde/index.html) for alternate
-        /// serializations.
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: ser::Serializer,
-        {
-            struct FormatWrapped<'a, D: 'a> {
-                inner: &'a D,
-            }
-
-            impl<'a, D: fmt::Debug> fmt::Display for FormatWrapped<'a, D> {
-                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                    self.inner.fmt(f)
-                }
-            }
-
-            // Debug formatting is correct RFC3339, and it allows Zulu.
-            serializer.collect_str(&FormatWrapped { inner: &self })
-        }
-    }
-
-    struct DateTimeVisitor;
-
-    impl<'de> de::Visitor<'de> for DateTimeVisitor {
-        type Value = DateTime<FixedOffset>;
-
-        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            write!(formatter, "a formatted date and time string or a unix timestamp")
-        }
-
-        fn visit_str<E>(self, value: &str) -> Result<DateTime<FixedOffset>, E>
-        where
-            E: de::Error,
-        {
-            value.parse().map_err(|err: ::format::ParseError| E::custom(err))
-        }
-    }
-
-    /// Deserialize a value that optionally includes a timezone offset in its
-    /// string representation
+    /// Ser/de to/from timestamps in fractional seconds
     ///
-    /// The value to be deserialized must be an rfc3339 string.
+    /// This is the representation of `time.time()` and `datetime.timestamp()` in Python.
+    /// A `f64` only has a precision of about a microsecond for current dates, so
+    /// nanoseconds do not survive a round-trip. Integers are accepted as well.
     ///
-    /// See [the `serde` module](./serde/index.html) for alternate
-    /// deserialization formats.
-    impl<'de> de::Deserialize<'de> for DateTime<FixedOffset> {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    /// Intended for use with `serde`s `with` attribute.
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate serde_derive;
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate chrono;
+    /// # use chrono::{TimeZone, DateTime, Utc};
+    /// use chrono::serde::ts_seconds_f64;
+    /// #[derive(Deserialize, Serialize)]
+    /// struct S {
+    ///     #[serde(with = "ts_seconds_f64")]
+    ///     time: DateTime<Utc>
+    /// }
+    ///
+    /// # fn example() -> Result<S, serde_json::Error> {
+    /// let time = Utc.ymd(2018, 5, 17).and_hms_milli(02, 04, 59, 500);
+    /// let my_s = S {
+    ///     time: time.clone(),
+    /// };
+    ///
+    /// let as_string = serde_json::to_string(&my_s)?;
+    /// assert_eq!(as_string, r#"{"time":1526522699.5}"#);
+    /// let my_s: S = serde_json::from_str(&as_string)?;
+    /// assert_eq!(my_s.time, time);
+    /// # Ok(my_s)
+    /// # }
+    /// # fn main() { example().unwrap(); }
+    /// ```
+    pub mod ts_seconds_f64 {
+        use core::fmt;
+        use serdelib::{de, ser};
+
+        use offset::TimeZone;
+        use {from_timestamp_f64, DateTime, Utc};
+
+        use super::{serde_from, FloatSecondsTimestampVisitor};
+
+        /// Serialize a datetime into a floating point number of seconds since the epoch
+        ///
+        /// Intended for use with `serde`s `serialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate serde_derive;
+        /// # #[macro_use] extern crate serde_json;
+        /// # extern crate chrono;
+        /// # use chrono::{TimeZone, DateTime, Utc};
+        /// use chrono::serde::ts_seconds_f64::serialize as to_float_ts;
+        /// #[derive(Serialize)]
+        /// struct S {
+        ///     #[serde(serialize_with = "to_float_ts")]
+        ///     time: DateTime<Utc>
+        /// }
+        ///
+        /// # fn example() -> Result<String, serde_json::Error> {
+        /// let my_s = S {
+        ///     time: Utc.ymd(2015, 5, 15).and_hms_milli(10, 0, 0, 250),
+        /// };
+        /// let as_string = serde_json::to_string(&my_s)?;
+        /// assert_eq!(as_string, r#"{"time":1431684000.25}"#);
+        /// # Ok(as_string)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn serialize<S, Tz>(dt: &DateTime<Tz>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+            Tz: TimeZone,
+        {
+            serializer
+                .serialize_f64(dt.timestamp() as f64 + dt.timestamp_subsec_nanos() as f64 / 1e9)
+        }
+
+        /// Deserialize a `DateTime` from a floating point seconds timestamp
+        ///
+        /// Intended for use with `serde`s `deserialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate serde_derive;
+        /// # #[macro_use] extern crate serde_json;
+        /// # extern crate chrono;
+        /// # use chrono::{DateTime, Utc};
+        /// use chrono::serde::ts_seconds_f64::deserialize as from_float_ts;
+        /// #[derive(Deserialize)]
+        /// struct S {
+        ///     #[serde(deserialize_with = "from_float_ts")]
+        ///     time: DateTime<Utc>
+        /// }
+        ///
+        /// # fn example() -> Result<S, serde_json::Error> {
+        /// let my_s: S = serde_json::from_str(r#"{ "time": 1431684000.25 }"#)?;
+        /// # Ok(my_s)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn deserialize<'de, D, Tz>(d: D) -> Result<DateTime<Tz>, D::Error>
+        where
+            D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<Utc>>,
+        {
+            d.deserialize_f64(FloatSecondsTimestampVisitor).map(DateTime::from)
+        }
+
+        impl<'de> de::Visitor<'de> for FloatSecondsTimestampVisitor {
+            type Value = DateTime<Utc>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a unix timestamp in fractional seconds")
+            }
+
+            /// Deserialize a timestamp in fractional seconds since the epoch
+            fn visit_f64<E>(self, value: f64) -> Result<DateTime<Utc>, E>
+            where
+                E: de::Error,
+            {
+                from_timestamp_f64(value).map(|dt| DateTime::from_utc(dt, Utc))
+            }
+
+            /// Deserialize a timestamp in seconds since the epoch
+            fn visit_i64<E>(self, value: i64) -> Result<DateTime<Utc>, E>
+            where
+                E: de::Error,
+            {
+                serde_from(Utc.timestamp_opt(value, 0), &value)
+            }
+
+            /// Deserialize a timestamp in seconds since the epoch
+            fn visit_u64<E>(self, value: u64) -> Result<DateTime<Utc>, E>
+            where
+                E: de::Error,
+            {
+                serde_from(Utc.timestamp_opt(value as i64, 0), &value)
+            }
+        }
+    }
+
+    /// Ser/de to/from optional timestamps in fractional seconds
+    ///
+    /// See [`ts_seconds_f64`](./ts_seconds_f64/index.html) for the representation.
+    ///
+    /// Intended for use with `serde`s `with` attribute.
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate serde_derive;
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate chrono;
+    /// # use chrono::{TimeZone, DateTime, Utc};
+    /// use chrono::serde::ts_seconds_f64_option;
+    /// #[derive(Deserialize, Serialize)]
+    /// struct S {
+    ///     #[serde(with = "ts_seconds_f64_option")]
+    ///     time: Option<DateTime<Utc>>
+    /// }
+    ///
+    /// # fn example() -> Result<S, serde_json::Error> {
+    /// let time = Some(Utc.ymd(2018, 5, 17).and_hms_milli(02, 04, 59, 500));
+    /// let my_s = S {
+    ///     time: time.clone(),
+    /// };
+    ///
+    /// let as_string = serde_json::to_string(&my_s)?;
+    /// assert_eq!(as_string, r#"{"time":1526522699.5}"#);
+    /// let my_s: S = serde_json::from_str(&as_string)?;
+    /// assert_eq!(my_s.time, time);
+    /// # Ok(my_s)
+    /// # }
+    /// # fn main() { example().unwrap(); }
+    /// ```
+    pub mod ts_seconds_f64_option {
+        use core::fmt;
+        use serdelib::{de, ser};
+
+        use offset::TimeZone;
+        use {DateTime, Utc};
+
+        use super::FloatSecondsTimestampVisitor;
+
+        /// Serialize a datetime into a floating point number of seconds since the epoch or none
+        ///
+        /// Intended for use with `serde`s `serialize_with` attribute.
+        pub fn serialize<S, Tz>(
+            opt: &Option<DateTime<Tz>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+            Tz: TimeZone,
+        {
+            match *opt {
+                Some(ref dt) => serializer.serialize_some(
+                    &(dt.timestamp() as f64 + dt.timestamp_subsec_nanos() as f64 / 1e9),
+                ),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        /// Deserialize a `DateTime` from a floating point seconds timestamp or none
+        ///
+        /// Intended for use with `serde`s `deserialize_with` attribute.
+        pub fn deserialize<'de, D, Tz>(d: D) -> Result<Option<DateTime<Tz>>, D::Error>
+        where
+            D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<Utc>>,
+        {
+            d.deserialize_option(OptionFloatSecondsTimestampVisitor)
+                .map(|opt| opt.map(DateTime::from))
+        }
+
+        struct OptionFloatSecondsTimestampVisitor;
+
+        impl<'de> de::Visitor<'de> for OptionFloatSecondsTimestampVisitor {
+            type Value = Option<DateTime<Utc>>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a unix timestamp in fractional seconds or none")
+            }
+
+            /// Deserialize a timestamp in fractional seconds since the epoch
+            fn visit_some<D>(self, d: D) -> Result<Option<DateTime<Utc>>, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                d.deserialize_f64(FloatSecondsTimestampVisitor).map(Some)
+            }
+
+            /// Deserialize a timestamp in fractional seconds since the epoch
+            fn visit_none<E>(self) -> Result<Option<DateTime<Utc>>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+
+            /// Deserialize a timestamp in fractional seconds since the epoch
+            fn visit_unit<E>(self) -> Result<Option<DateTime<Utc>>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+        }
+    }
+
+    /// How [`lenient`](./lenient/index.html) interprets integer timestamps
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum TimestampUnit {
+        /// Integers are always seconds since the epoch
+        Seconds,
+        /// Integers are always milliseconds since the epoch
+        Milliseconds,
+        /// Integers are milliseconds since the epoch if their magnitude is at least the given
+        /// threshold, and seconds otherwise
+        Guess(u64),
+    }
+
+    /// The default threshold of [`TimestampUnit::Guess`](./enum.TimestampUnit.html).
+    ///
+    /// This treats integers as seconds up to the year 5138, and as milliseconds
+    /// from March 1973 onwards (or before November 1966).
+    pub const DEFAULT_MILLISECONDS_THRESHOLD: u64 = 100_000_000_000;
+
+    impl Default for TimestampUnit {
+        fn default() -> TimestampUnit {
+            TimestampUnit::Guess(DEFAULT_MILLISECONDS_THRESHOLD)
+        }
+    }
+
+    /// Deserialize from whatever representation a timestamp happens to arrive in
+    ///
+    /// This accepts RFC 3339 and RFC 2822 strings, integers of seconds or milliseconds
+    /// since the epoch (see [`TimestampUnit`](./enum.TimestampUnit.html)), floating point
+    /// seconds since the epoch, and strings containing such numbers. The offset of a string
+    /// is kept for a `DateTime<FixedOffset>`, while timestamps always have an offset of zero.
+    ///
+    /// Serialization uses the default RFC 3339 representation.
+    ///
+    /// Deserialization relies on `Deserializer::deserialize_any` to find out which kind of
+    /// value is present, so it only works with self-describing formats like JSON, YAML or
+    /// CBOR. Formats which are not self-describing, like bincode, always fail to
+    /// deserialize a field using this module.
+    ///
+    /// Intended for use with `serde`s `with` attribute.
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate serde_derive;
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate chrono;
+    /// # use chrono::{TimeZone, DateTime, Utc};
+    /// use chrono::serde::lenient;
+    /// #[derive(Deserialize, Serialize)]
+    /// struct S {
+    ///     #[serde(with = "lenient")]
+    ///     time: DateTime<Utc>
+    /// }
+    ///
+    /// # fn example() -> Result<(), serde_json::Error> {
+    /// let time = Utc.ymd(2018, 5, 17).and_hms(2, 4, 59);
+    /// for json in &[
+    ///     r#"{"time":"2018-05-17T02:04:59Z"}"#,
+    ///     r#"{"time":"Thu, 17 May 2018 04:04:59 +0200"}"#,
+    ///     r#"{"time":1526522699}"#,
+    ///     r#"{"time":1526522699000}"#,
+    ///     r#"{"time":1526522699.0}"#,
+    ///     r#"{"time":"1526522699"}"#,
+    /// ] {
+    ///     let my_s: S = serde_json::from_str(json)?;
+    ///     assert_eq!(my_s.time, time);
+    /// }
+    /// assert_eq!(serde_json::to_string(&S { time: time })?, r#"{"time":"2018-05-17T02:04:59Z"}"#);
+    /// # Ok(())
+    /// # }
+    /// # fn main() { example().unwrap(); }
+    /// ```
+    pub mod lenient {
+        use core::fmt;
+        use serdelib::{de, ser};
+
+        use offset::{FixedOffset, TimeZone};
+        use {DateTime, Utc};
+
+        use super::{
+            FloatSecondsTimestampVisitor, MilliSecondsTimestampVisitor, SecondsTimestampVisitor,
+            TimestampUnit,
+        };
+
+        /// Serialize a datetime into an RFC 3339 string
+        ///
+        /// Intended for use with `serde`s `serialize_with` attribute.
+        pub fn serialize<S, Tz>(dt: &DateTime<Tz>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+            Tz: TimeZone,
+        {
+            ser::Serialize::serialize(dt, serializer)
+        }
+
+        /// Deserialize a `DateTime` from any supported representation, guessing the unit of
+        /// integer timestamps with the default threshold
+        ///
+        /// Intended for use with `serde`s `deserialize_with` attribute.
+        pub fn deserialize<'de, D, Tz>(d: D) -> Result<DateTime<Tz>, D::Error>
+        where
+            D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<FixedOffset>>,
+        {
+            deserialize_with_unit(d, TimestampUnit::default())
+        }
+
+        /// Deserialize a `DateTime` from any supported representation, interpreting
+        /// integer timestamps as given by `unit`
+        ///
+        /// This is meant to be wrapped in a function used with `serde`s `deserialize_with`
+        /// attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate serde_derive;
+        /// # #[macro_use] extern crate serde_json;
+        /// # extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::{TimeZone, DateTime, Utc};
+        /// use chrono::serde::{lenient, TimestampUnit};
+        ///
+        /// fn from_any_millis<'de, D>(d: D) -> Result<DateTime<Utc>, D::Error>
+        /// where
+        ///     D: serde::Deserializer<'de>,
+        /// {
+        ///     lenient::deserialize_with_unit(d, TimestampUnit::Milliseconds)
+        /// }
+        ///
+        /// #[derive(Deserialize)]
+        /// struct S {
+        ///     #[serde(deserialize_with = "from_any_millis")]
+        ///     time: DateTime<Utc>
+        /// }
+        ///
+        /// # fn example() -> Result<S, serde_json::Error> {
+        /// let my_s: S = serde_json::from_str(r#"{ "time": 1000 }"#)?;
+        /// assert_eq!(my_s.time, Utc.ymd(1970, 1, 1).and_hms(0, 0, 1));
+        /// # Ok(my_s)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn deserialize_with_unit<'de, D, Tz>(
+            d: D,
+            unit: TimestampUnit,
+        ) -> Result<DateTime<Tz>, D::Error>
+        where
+            D: de::Deserializer<'de>,
+            Tz: TimeZone,
+            DateTime<Tz>: From<DateTime<FixedOffset>>,
+        {
+            d.deserialize_any(LenientVisitor { unit: unit }).map(DateTime::from)
+        }
+
+        struct LenientVisitor {
+            unit: TimestampUnit,
+        }
+
+        impl LenientVisitor {
+            fn is_milliseconds(&self, magnitude: u64) -> bool {
+                match self.unit {
+                    TimestampUnit::Seconds => false,
+                    TimestampUnit::Milliseconds => true,
+                    TimestampUnit::Guess(threshold) => magnitude >= threshold,
+                }
+            }
+        }
+
+        fn fixed<E>(dt: Result<DateTime<Utc>, E>) -> Result<DateTime<FixedOffset>, E> {
+            dt.map(DateTime::from)
+        }
+
+        impl<'de> de::Visitor<'de> for LenientVisitor {
+            type Value = DateTime<FixedOffset>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an RFC 3339 or RFC 2822 string, or a unix timestamp")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<DateTime<FixedOffset>, E>
+            where
+                E: de::Error,
+            {
+                if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+                    return Ok(dt);
+                }
+                if let Ok(dt) = DateTime::parse_from_rfc2822(value) {
+                    return Ok(dt);
+                }
+                let trimmed = value.trim();
+                if let Ok(value) = trimmed.parse::<i64>() {
+                    return self.visit_i64(value);
+                }
+                if let Ok(value) = trimmed.parse::<u64>() {
+                    return self.visit_u64(value);
+                }
+                match trimmed.parse::<f64>() {
+                    Ok(value) if !trimmed.is_empty() => self.visit_f64(value),
+                    _ => Err(de::Error::invalid_value(de::Unexpected::Str(value), &self)),
+                }
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<DateTime<FixedOffset>, E>
+            where
+                E: de::Error,
+            {
+                let magnitude =
+                    if value < 0 { (value as u64).wrapping_neg() } else { value as u64 };
+                if self.is_milliseconds(magnitude) {
+                    fixed(MilliSecondsTimestampVisitor.visit_i64(value))
+                } else {
+                    fixed(SecondsTimestampVisitor.visit_i64(value))
+                }
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<DateTime<FixedOffset>, E>
+            where
+                E: de::Error,
+            {
+                if self.is_milliseconds(value) {
+                    fixed(MilliSecondsTimestampVisitor.visit_u64(value))
+                } else {
+                    fixed(SecondsTimestampVisitor.visit_u64(value))
+                }
+            }
+
+            /// Floating point timestamps are always in seconds
+            fn visit_f64<E>(self, value: f64) -> Result<DateTime<FixedOffset>, E>
+            where
+                E: de::Error,
+            {
+                fixed(FloatSecondsTimestampVisitor.visit_f64(value))
+            }
+        }
+    }
+
+    impl<Tz: TimeZone> ser::Serialize for DateTime<Tz> {
+        /// Serialize into a rfc3339 time string
+        ///
+        /// See [the `serde` module](./serde/index.html) for alternate
+        /// serializations.
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            struct FormatWrapped<'a, D: 'a> {
+                inner: &'a D,
+            }
+
+            impl<'a, D: fmt::Debug> fmt::Display for FormatWrapped<'a, D> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    self.inner.fmt(f)
+                }
+            }
+
+            // Debug formatting is correct RFC3339, and it allows Zulu.
+            serializer.collect_str(&FormatWrapped { inner: &self })
+        }
+    }
+
+    struct DateTimeVisitor;
+
+    impl<'de> de::Visitor<'de> for DateTimeVisitor {
+        type Value = DateTime<FixedOffset>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a formatted date and time string or a unix timestamp")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<DateTime<FixedOffset>, E>
+        where
+            E: de::Error,
+        {
+            value.parse().map_err(|err: ::format::ParseError| E::custom(err))
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<DateTime<FixedOffset>, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            let secs = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let nsecs = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            let offset = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+            let utc = NaiveDateTime::from_timestamp_opt(secs, nsecs)
+                .ok_or_else(|| de::Error::custom(ne_timestamp(secs)))?;
+            let offset = FixedOffset::east_opt(offset)
+                .ok_or_else(|| de::Error::custom("offset out of range"))?;
+            Ok(DateTime::from_utc(utc, offset))
+        }
+    }
+
+    /// Deserialize a value that optionally includes a timezone offset in its
+    /// string representation
+    ///
+    /// The value to be deserialized must be an rfc3339 string.
+    ///
+    /// See [the `serde` module](./serde/index.html) for alternate
+    /// deserialization formats.
+    impl<'de> de::Deserialize<'de> for DateTime<FixedOffset> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
             D: de::Deserializer<'de>,
         {
@@ -2389,9 +3312,43 @@ pub mod serde {
         }
     }
 
+    /// A tuple of the UNIX timestamp, the nanoseconds and the offset in seconds east of UTC.
+    macro_rules! impl_compact {
+        ($tz:ty, $to_tz:expr) => {
+            impl Compact for DateTime<$tz> {
+                fn serialize_compact<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: ser::Serializer,
+                {
+                    let offset = self.offset().fix().local_minus_utc();
+                    ser::Serialize::serialize(
+                        &(self.timestamp(), self.timestamp_subsec_nanos(), offset),
+                        serializer,
+                    )
+                }
+
+                fn deserialize_compact<'de, D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: de::Deserializer<'de>,
+                {
+                    deserializer.deserialize_tuple(3, DateTimeVisitor).map($to_tz)
+                }
+            }
+        };
+    }
+
+    impl_compact!(FixedOffset, |dt| dt);
+    impl_compact!(Utc, |dt: DateTime<FixedOffset>| dt.with_timezone(&Utc));
+    #[cfg(feature = "clock")]
+    impl_compact!(Local, |dt: DateTime<FixedOffset>| dt.with_timezone(&Local));
+
     #[cfg(test)]
     extern crate bincode;
     #[cfg(test)]
+    extern crate bincode1;
+    #[cfg(test)]
+    extern crate serde_derive;
+    #[cfg(test)]
     extern crate serde_json;
 
     #[test]
@@ -2421,16 +3378,180 @@ pub mod serde {
         assert_eq!(dt, decoded);
         assert_eq!(dt.offset(), decoded.offset());
     }
+
+    #[test]
+    fn test_serde_bincode_1() {
+        // bincode 1.x is not human-readable, but still uses the string
+        // to read data written by earlier versions
+        use self::bincode1::{deserialize, serialize};
+
+        let dt = Utc.ymd(2014, 7, 24).and_hms(12, 34, 6);
+        let encoded = serialize(&dt).unwrap();
+        assert_eq!(encoded, serialize(&"2014-07-24T12:34:06Z").unwrap());
+        assert_eq!(deserialize::<DateTime<Utc>>(&encoded).unwrap(), dt);
+
+        let dt = FixedOffset::east(3600).ymd(2014, 7, 24).and_hms(12, 34, 6);
+        let encoded = serialize(&dt).unwrap();
+        assert_eq!(encoded, serialize(&"2014-07-24T12:34:06+01:00").unwrap());
+        let decoded = deserialize::<DateTime<FixedOffset>>(&encoded).unwrap();
+        assert_eq!(decoded, dt);
+        assert_eq!(decoded.offset(), dt.offset());
+    }
+
+    #[test]
+    fn test_serde_lenient() {
+        use self::serde_derive::{Deserialize, Serialize};
+        use self::serde_json::{from_str, to_string, Deserializer};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Test<Tz: TimeZone>
+        where
+            DateTime<Tz>: From<DateTime<FixedOffset>>,
+        {
+            #[serde(with = "lenient")]
+            time: DateTime<Tz>,
+        }
+
+        fn utc(json: &str) -> Result<DateTime<Utc>, self::serde_json::Error> {
+            from_str::<Test<Utc>>(&format!(r#"{{"time":{}}}"#, json)).map(|t| t.time)
+        }
+
+        let dt = Utc.ymd(2018, 5, 17).and_hms_milli(2, 4, 59, 500);
+        assert_eq!(utc(r#""2018-05-17T02:04:59.5Z""#).unwrap(), dt);
+        assert_eq!(utc(r#""2018-05-17T04:04:59.500+02:00""#).unwrap(), dt);
+        assert_eq!(
+            utc(r#""Thu, 17 May 2018 02:04:59 GMT""#).unwrap(),
+            Utc.timestamp(1_526_522_699, 0)
+        );
+        assert_eq!(utc("1526522699").unwrap(), Utc.timestamp(1_526_522_699, 0));
+        assert_eq!(utc("1526522699500").unwrap(), dt);
+        assert_eq!(utc("1526522699.5").unwrap(), dt);
+        assert_eq!(utc(r#"" 1526522699500 ""#).unwrap(), dt);
+        assert_eq!(utc(r#""1526522699.5""#).unwrap(), dt);
+        assert_eq!(utc("-1").unwrap(), Utc.ymd(1969, 12, 31).and_hms(23, 59, 59));
+        assert_eq!(utc("-100000000500").unwrap(), Utc.timestamp_millis(-100_000_000_500));
+        assert!(utc(r#""yesterday""#).is_err());
+        assert!(utc(r#""""#).is_err());
+        assert!(utc("true").is_err());
+        assert!(utc(r#""NaN""#).is_err());
+
+        // the offset of strings is kept for `FixedOffset`
+        let json = r#"{"time":"Thu, 17 May 2018 04:04:59 +0200"}"#;
+        let fixed = from_str::<Test<FixedOffset>>(json).unwrap().time;
+        assert_eq!(fixed.offset(), &FixedOffset::east(7200));
+        assert_eq!(
+            to_string(&Test { time: fixed }).unwrap(),
+            r#"{"time":"2018-05-17T04:04:59+02:00"}"#
+        );
+
+        let with_unit = |json: &str, unit| -> DateTime<Utc> {
+            lenient::deserialize_with_unit(&mut Deserializer::from_str(json), unit).unwrap()
+        };
+        assert_eq!(with_unit("1000", TimestampUnit::Milliseconds), Utc.timestamp(1, 0));
+        assert_eq!(
+            with_unit("1526522699500", TimestampUnit::Seconds),
+            Utc.timestamp(1_526_522_699_500, 0)
+        );
+        assert_eq!(with_unit("1000", TimestampUnit::Guess(1000)), Utc.timestamp(1, 0));
+        assert_eq!(with_unit("999", TimestampUnit::Guess(1000)), Utc.timestamp(999, 0));
+        assert_eq!(with_unit("-1000", TimestampUnit::Guess(1000)), Utc.timestamp(-1, 0));
+
+        // bincode is not self-describing, so it can't support `deserialize_any`
+        let encoded = self::bincode1::serialize(&Test { time: dt }).unwrap();
+        assert!(self::bincode1::deserialize::<Test<Utc>>(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_serde_ts_modules() {
+        use self::serde_derive::{Deserialize, Serialize};
+        use self::serde_json::{from_str, to_string};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Test<Tz: TimeZone>
+        where
+            DateTime<Tz>: From<DateTime<Utc>>,
+        {
+            #[serde(with = "ts_microseconds")]
+            us: DateTime<Tz>,
+            #[serde(with = "ts_microseconds_option")]
+            us_opt: Option<DateTime<Tz>>,
+            #[serde(with = "ts_seconds_f64")]
+            f: DateTime<Tz>,
+            #[serde(with = "ts_seconds_f64_option")]
+            f_opt: Option<DateTime<Tz>>,
+            #[serde(with = "ts_milliseconds_option")]
+            ms_opt: Option<DateTime<Tz>>,
+        }
+
+        let dt = Utc.ymd(2018, 5, 17).and_hms_micro(2, 4, 59, 918_355);
+        let value = Test { us: dt, us_opt: Some(dt), f: dt, f_opt: None, ms_opt: None };
+        let json = to_string(&value).unwrap();
+        assert_eq!(
+            json,
+            r#"{"us":1526522699918355,"us_opt":1526522699918355,"f":1526522699.918355,"f_opt":null,"ms_opt":null}"#
+        );
+        let decoded: Test<Utc> = from_str(&json).unwrap();
+        assert_eq!(decoded.us, dt);
+        assert_eq!(decoded.us_opt, Some(dt));
+        assert!((decoded.f - dt).num_microseconds().unwrap().abs() <= 1);
+
+        // timestamps do not carry an offset, so other time zones are deserialized as UTC
+        let fixed: Test<FixedOffset> = from_str(&json).unwrap();
+        assert_eq!(fixed.us, dt);
+        assert_eq!(fixed.us.offset(), &FixedOffset::east(0));
+        let kst = dt.with_timezone(&FixedOffset::east(9 * 3600));
+        let value = Test { us: kst, us_opt: None, f: kst, f_opt: Some(kst), ms_opt: Some(kst) };
+        let json2 = to_string(&value).unwrap();
+        assert!(json2.starts_with(r#"{"us":1526522699918355,"us_opt":null,"f":1526522699.918355"#));
+        #[cfg(feature = "clock")]
+        {
+            let local: Test<Local> = from_str(&json).unwrap();
+            assert_eq!(local.us, dt);
+        }
+
+        // Python writes both floats and integral values
+        let f: DateTime<Utc> = ts_seconds_f64::deserialize(
+            &mut self::serde_json::Deserializer::from_str("1431684000.25"),
+        )
+        .unwrap();
+        assert_eq!(f, Utc.ymd(2015, 5, 15).and_hms_milli(10, 0, 0, 250));
+        let f: DateTime<Utc> = ts_seconds_f64::deserialize(
+            &mut self::serde_json::Deserializer::from_str("1431684000"),
+        )
+        .unwrap();
+        assert_eq!(f, Utc.ymd(2015, 5, 15).and_hms(10, 0, 0));
+        let f: DateTime<Utc> =
+            ts_seconds_f64::deserialize(&mut self::serde_json::Deserializer::from_str("-0.5"))
+                .unwrap();
+        assert_eq!(f, Utc.ymd(1969, 12, 31).and_hms_milli(23, 59, 59, 500));
+        assert!(ts_seconds_f64::deserialize::<_, Utc>(
+            &mut self::serde_json::Deserializer::from_str("1e300")
+        )
+        .is_err());
+
+        // negative timestamps are rounded towards negative infinity
+        let before = Utc.ymd(1969, 12, 31).and_hms_micro(23, 59, 59, 500_000);
+        let ns: DateTime<Utc> = ts_nanoseconds::deserialize(
+            &mut self::serde_json::Deserializer::from_str("-500000000"),
+        )
+        .unwrap();
+        assert_eq!(ns, before);
+        let us: DateTime<Utc> =
+            ts_microseconds::deserialize(&mut self::serde_json::Deserializer::from_str("-500000"))
+                .unwrap();
+        assert_eq!(us, before);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::DateTime;
+    use super::{DateTime, DateTimeStep};
     use naive::{NaiveDate, NaiveTime};
     #[cfg(feature = "clock")]
     use offset::Local;
     use offset::{FixedOffset, TimeZone, Utc};
     use oldtime::Duration;
+    use std::fmt;
     use std::time::{SystemTime, UNIX_EPOCH};
     #[cfg(feature = "clock")]
     use Datelike;
@@ -2702,6 +3823,98 @@ mod tests {
         assert_eq!(dt.format("%Y").to_string(), dt.with_timezone(&Utc).format("%Y").to_string());
     }
 
+    #[test]
+    fn test_datetime_iter_until_dst() {
+        use offset::test_zone::Eastern;
+
+        let at = |y, m, d, h, n| NaiveDate::from_ymd(y, m, d).and_hms(h, n, 0);
+        let local =
+            |y, m, d, h, n| Eastern.from_local_datetime(&at(y, m, d, h, n)).earliest().unwrap();
+        let items = |start: DateTime<Eastern>, end, step| {
+            start
+                .iter_until(end, step)
+                .map(|dt| (dt.naive_local(), *dt.offset()))
+                .collect::<Vec<_>>()
+        };
+        let (est, edt) = (Eastern::est(), Eastern::edt());
+
+        // clocks skip from 02:00 to 03:00
+        assert_eq!(
+            items(local(2021, 3, 14, 0, 0), local(2021, 3, 14, 5, 0), DateTimeStep::Hours(1)),
+            [
+                (at(2021, 3, 14, 0, 0), est),
+                (at(2021, 3, 14, 1, 0), est),
+                (at(2021, 3, 14, 3, 0), edt),
+                (at(2021, 3, 14, 4, 0), edt)
+            ]
+        );
+        assert_eq!(
+            items(local(2021, 3, 13, 2, 30), local(2021, 3, 16, 0, 0), DateTimeStep::Days(1)),
+            [
+                (at(2021, 3, 13, 2, 30), est),
+                (at(2021, 3, 14, 3, 30), edt),
+                (at(2021, 3, 15, 2, 30), edt)
+            ]
+        );
+
+        // clocks go back from 02:00 to 01:00
+        let start = local(2021, 11, 7, 0, 0);
+        let end = start + Duration::hours(4);
+        assert_eq!(
+            items(start, end, DateTimeStep::Hours(1)),
+            [
+                (at(2021, 11, 7, 0, 0), edt),
+                (at(2021, 11, 7, 1, 0), edt),
+                (at(2021, 11, 7, 2, 0), est)
+            ]
+        );
+        assert_eq!(
+            items(start, end, DateTimeStep::Exact(Duration::hours(1))),
+            [
+                (at(2021, 11, 7, 0, 0), edt),
+                (at(2021, 11, 7, 1, 0), edt),
+                (at(2021, 11, 7, 1, 0), est),
+                (at(2021, 11, 7, 2, 0), est)
+            ]
+        );
+        let days = local(2021, 11, 6, 1, 30)
+            .iter_until(local(2021, 11, 9, 0, 0), DateTimeStep::Days(1))
+            .collect::<Vec<_>>();
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[1].signed_duration_since(days[0].clone()), Duration::hours(24));
+        assert_eq!(days[2].signed_duration_since(days[1].clone()), Duration::hours(25));
+
+        // months and weeks keep the local time of day
+        let months = local(2021, 1, 31, 12, 0)
+            .iter_until(local(2022, 1, 1, 0, 0), DateTimeStep::Months(2))
+            .map(|dt| dt.naive_local())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            months,
+            [
+                at(2021, 1, 31, 12, 0),
+                at(2021, 3, 31, 12, 0),
+                at(2021, 5, 31, 12, 0),
+                at(2021, 7, 31, 12, 0),
+                at(2021, 9, 30, 12, 0),
+                at(2021, 11, 30, 12, 0)
+            ]
+        );
+        let weeks =
+            local(2021, 3, 7, 9, 0).iter_until(local(2021, 3, 22, 0, 0), DateTimeStep::Weeks(1));
+        assert!(weeks.map(|dt| dt.naive_local().time()).all(|t| t == NaiveTime::from_hms(9, 0, 0)));
+
+        let start = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+        assert_eq!(start.iter_until(start, DateTimeStep::Days(1)).next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_datetime_iter_until_zero_step() {
+        let start = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+        start.iter_until(start + Duration::days(1), DateTimeStep::Exact(Duration::zero()));
+    }
+
     #[test]
     #[cfg(feature = "clock")]
     fn test_datetime_is_copy() {
@@ -2842,4 +4055,25 @@ mod tests {
         assert_eq!(format!("{}  ", ymd_formatted), format!("{:<17}", ymd));
         assert_eq!(format!(" {} ", ymd_formatted), format!("{:^17}", ymd));
     }
+
+    #[test]
+    fn test_datetime_format_into() {
+        let dt = FixedOffset::east(9 * 3600).ymd(2007, 1, 2).and_hms_micro(3, 4, 5, 60);
+        let formatted = dt.format("%Y-%m-%dT%H:%M:%S%.6f%:z %Z");
+        let mut buf = [0; 64];
+        assert_eq!(formatted.format_into(&mut buf), Ok("2007-01-02T03:04:05.000060+09:00 +09:00"));
+        assert_eq!(
+            formatted.format_into(&mut buf[..39]),
+            Ok("2007-01-02T03:04:05.000060+09:00 +09:00")
+        );
+        assert_eq!(formatted.format_into(&mut buf[..38]), Err(fmt::Error));
+
+        let mut s = String::new();
+        formatted.write_to(&mut s).unwrap();
+        assert_eq!(s, formatted.to_string());
+
+        // invalid items and missing fields
+        assert_eq!(dt.format("%Y-%Q").format_into(&mut buf), Err(fmt::Error));
+        assert_eq!(dt.naive_local().format("%z").format_into(&mut buf), Err(fmt::Error));
+    }
 }
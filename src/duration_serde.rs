@@ -0,0 +1,780 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Serde `with`-modules for `Duration`s as integers or ISO 8601 strings,
+//! re-exported from `chrono::serde`.
+
+use core::{fmt, i64};
+use format::duration::parse_iso8601;
+use oldtime::Duration;
+use serdelib::de;
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct SecondsDurationVisitor;
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct MilliSecondsDurationVisitor;
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct NanoSecondsDurationVisitor;
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct Iso8601DurationVisitor;
+
+impl<'de> de::Visitor<'de> for SecondsDurationVisitor {
+    type Value = Duration;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a duration in seconds")
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Duration, E>
+    where
+        E: de::Error,
+    {
+        // `Duration` is limited to `i64` milliseconds
+        match value.checked_mul(1000) {
+            Some(millis) => Ok(Duration::milliseconds(millis)),
+            None => Err(E::custom("duration out of range")),
+        }
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Duration, E>
+    where
+        E: de::Error,
+    {
+        if value > i64::MAX as u64 {
+            return Err(E::custom("duration out of range"));
+        }
+        self.visit_i64(value as i64)
+    }
+}
+
+impl<'de> de::Visitor<'de> for MilliSecondsDurationVisitor {
+    type Value = Duration;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a duration in milliseconds")
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Duration, E>
+    where
+        E: de::Error,
+    {
+        Ok(Duration::milliseconds(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Duration, E>
+    where
+        E: de::Error,
+    {
+        if value > i64::MAX as u64 {
+            return Err(E::custom("duration out of range"));
+        }
+        Ok(Duration::milliseconds(value as i64))
+    }
+}
+
+impl<'de> de::Visitor<'de> for NanoSecondsDurationVisitor {
+    type Value = Duration;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a duration in nanoseconds")
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Duration, E>
+    where
+        E: de::Error,
+    {
+        Ok(Duration::nanoseconds(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Duration, E>
+    where
+        E: de::Error,
+    {
+        if value > i64::MAX as u64 {
+            return Err(E::custom("duration out of range"));
+        }
+        Ok(Duration::nanoseconds(value as i64))
+    }
+}
+
+impl<'de> de::Visitor<'de> for Iso8601DurationVisitor {
+    type Value = Duration;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an ISO 8601 duration")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Duration, E>
+    where
+        E: de::Error,
+    {
+        parse_iso8601(value).map_err(E::custom)
+    }
+}
+
+/// A `Duration` visitor which knows which `deserialize_*` method drives it.
+trait DurationVisitor<'de>: de::Visitor<'de, Value = Duration> {
+    fn deserialize<D>(self, d: D) -> Result<Duration, D::Error>
+    where
+        D: de::Deserializer<'de>;
+}
+
+macro_rules! duration_visitor {
+    ($visitor:ident, $method:ident) => {
+        impl<'de> DurationVisitor<'de> for $visitor {
+            fn deserialize<D>(self, d: D) -> Result<Duration, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                d.$method(self)
+            }
+        }
+    };
+}
+
+duration_visitor!(SecondsDurationVisitor, deserialize_i64);
+duration_visitor!(MilliSecondsDurationVisitor, deserialize_i64);
+duration_visitor!(NanoSecondsDurationVisitor, deserialize_i64);
+duration_visitor!(Iso8601DurationVisitor, deserialize_str);
+
+/// Deserializes an optional `Duration` with the inner visitor.
+struct OptionVisitor<V>(V);
+
+impl<'de, V> de::Visitor<'de> for OptionVisitor<V>
+where
+    V: DurationVisitor<'de>,
+{
+    type Value = Option<Duration>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(formatter)?;
+        formatter.write_str(" or none")
+    }
+
+    fn visit_some<D>(self, d: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.0.deserialize(d).map(Some)
+    }
+
+    fn visit_none<E>(self) -> Result<Option<Duration>, E>
+    where
+        E: de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_unit<E>(self) -> Result<Option<Duration>, E>
+    where
+        E: de::Error,
+    {
+        Ok(None)
+    }
+}
+
+/// Ser/de a `Duration` to/from an integer number of seconds
+///
+/// Serialization truncates any fraction of a second.
+/// Intended for use with `serde`'s `with` attribute.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate serde_json;
+/// # extern crate chrono;
+/// # use chrono::Duration;
+/// use chrono::serde::duration_seconds;
+/// #[derive(Deserialize, Serialize)]
+/// struct S {
+///     #[serde(with = "duration_seconds")]
+///     timeout: Duration
+/// }
+///
+/// # fn example() -> Result<S, serde_json::Error> {
+/// let my_s = S { timeout: Duration::minutes(5) };
+/// let as_string = serde_json::to_string(&my_s)?;
+/// assert_eq!(as_string, r#"{"timeout":300}"#);
+/// let my_s: S = serde_json::from_str(&as_string)?;
+/// assert_eq!(my_s.timeout, Duration::minutes(5));
+/// # Ok(my_s)
+/// # }
+/// # fn main() { example().unwrap(); }
+/// ```
+pub mod duration_seconds {
+    use oldtime::Duration;
+    use serdelib::{de, ser};
+
+    use super::SecondsDurationVisitor;
+
+    /// Serialize a `Duration` into an integer number of whole seconds
+    ///
+    /// Intended for use with `serde`s `serialize_with` attribute.
+    pub fn serialize<S>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_i64(d.num_seconds())
+    }
+
+    /// Deserialize a `Duration` from an integer number of seconds
+    ///
+    /// Intended for use with `serde`s `deserialize_with` attribute.
+    pub fn deserialize<'de, D>(d: D) -> Result<Duration, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        d.deserialize_i64(SecondsDurationVisitor)
+    }
+}
+
+/// Ser/de an optional `Duration` to/from an integer number of seconds or none
+///
+/// Serialization truncates any fraction of a second.
+/// Intended for use with `serde`'s `with` attribute.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate serde_json;
+/// # extern crate chrono;
+/// # use chrono::Duration;
+/// use chrono::serde::duration_seconds_option;
+/// #[derive(Deserialize, Serialize)]
+/// struct S {
+///     #[serde(with = "duration_seconds_option")]
+///     timeout: Option<Duration>
+/// }
+///
+/// # fn example() -> Result<S, serde_json::Error> {
+/// let my_s = S { timeout: Some(Duration::minutes(5)) };
+/// let as_string = serde_json::to_string(&my_s)?;
+/// assert_eq!(as_string, r#"{"timeout":300}"#);
+/// let my_s: S = serde_json::from_str(r#"{"timeout":null}"#)?;
+/// assert_eq!(my_s.timeout, None);
+/// # Ok(my_s)
+/// # }
+/// # fn main() { example().unwrap(); }
+/// ```
+pub mod duration_seconds_option {
+    use oldtime::Duration;
+    use serdelib::{de, ser};
+
+    use super::{OptionVisitor, SecondsDurationVisitor};
+
+    /// Serialize an optional `Duration` into an integer number of whole seconds or none
+    ///
+    /// Intended for use with `serde`s `serialize_with` attribute.
+    pub fn serialize<S>(opt: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *opt {
+            Some(ref d) => serializer.serialize_some(&d.num_seconds()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an optional `Duration` from an integer number of seconds or none
+    ///
+    /// Intended for use with `serde`s `deserialize_with` attribute.
+    pub fn deserialize<'de, D>(d: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        d.deserialize_option(OptionVisitor(SecondsDurationVisitor))
+    }
+}
+
+/// Ser/de a `Duration` to/from an integer number of milliseconds
+///
+/// Serialization truncates any fraction of a millisecond.
+/// Intended for use with `serde`'s `with` attribute.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate serde_json;
+/// # extern crate chrono;
+/// # use chrono::Duration;
+/// use chrono::serde::duration_milliseconds;
+/// #[derive(Deserialize, Serialize)]
+/// struct S {
+///     #[serde(with = "duration_milliseconds")]
+///     interval: Duration
+/// }
+///
+/// # fn example() -> Result<S, serde_json::Error> {
+/// let my_s = S { interval: Duration::milliseconds(1500) };
+/// let as_string = serde_json::to_string(&my_s)?;
+/// assert_eq!(as_string, r#"{"interval":1500}"#);
+/// let my_s: S = serde_json::from_str(&as_string)?;
+/// assert_eq!(my_s.interval, Duration::milliseconds(1500));
+/// # Ok(my_s)
+/// # }
+/// # fn main() { example().unwrap(); }
+/// ```
+pub mod duration_milliseconds {
+    use oldtime::Duration;
+    use serdelib::{de, ser};
+
+    use super::MilliSecondsDurationVisitor;
+
+    /// Serialize a `Duration` into an integer number of whole milliseconds
+    ///
+    /// Intended for use with `serde`s `serialize_with` attribute.
+    pub fn serialize<S>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_i64(d.num_milliseconds())
+    }
+
+    /// Deserialize a `Duration` from an integer number of milliseconds
+    ///
+    /// Intended for use with `serde`s `deserialize_with` attribute.
+    pub fn deserialize<'de, D>(d: D) -> Result<Duration, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        d.deserialize_i64(MilliSecondsDurationVisitor)
+    }
+}
+
+/// Ser/de an optional `Duration` to/from an integer number of milliseconds or none
+///
+/// Serialization truncates any fraction of a millisecond.
+/// Intended for use with `serde`'s `with` attribute.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate serde_json;
+/// # extern crate chrono;
+/// # use chrono::Duration;
+/// use chrono::serde::duration_milliseconds_option;
+/// #[derive(Deserialize, Serialize)]
+/// struct S {
+///     #[serde(with = "duration_milliseconds_option")]
+///     interval: Option<Duration>
+/// }
+///
+/// # fn example() -> Result<S, serde_json::Error> {
+/// let my_s = S { interval: None };
+/// let as_string = serde_json::to_string(&my_s)?;
+/// assert_eq!(as_string, r#"{"interval":null}"#);
+/// let my_s: S = serde_json::from_str(r#"{"interval":1500}"#)?;
+/// assert_eq!(my_s.interval, Some(Duration::milliseconds(1500)));
+/// # Ok(my_s)
+/// # }
+/// # fn main() { example().unwrap(); }
+/// ```
+pub mod duration_milliseconds_option {
+    use oldtime::Duration;
+    use serdelib::{de, ser};
+
+    use super::{MilliSecondsDurationVisitor, OptionVisitor};
+
+    /// Serialize an optional `Duration` into an integer number of whole milliseconds or none
+    ///
+    /// Intended for use with `serde`s `serialize_with` attribute.
+    pub fn serialize<S>(opt: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *opt {
+            Some(ref d) => serializer.serialize_some(&d.num_milliseconds()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an optional `Duration` from an integer number of milliseconds or none
+    ///
+    /// Intended for use with `serde`s `deserialize_with` attribute.
+    pub fn deserialize<'de, D>(d: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        d.deserialize_option(OptionVisitor(MilliSecondsDurationVisitor))
+    }
+}
+
+/// Ser/de a `Duration` to/from an integer number of nanoseconds
+///
+/// Serialization fails for durations exceeding `i64` nanoseconds (about 292 years).
+/// Intended for use with `serde`'s `with` attribute.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate serde_json;
+/// # extern crate chrono;
+/// # use chrono::Duration;
+/// use chrono::serde::duration_nanoseconds;
+/// #[derive(Deserialize, Serialize)]
+/// struct S {
+///     #[serde(with = "duration_nanoseconds")]
+///     elapsed: Duration
+/// }
+///
+/// # fn example() -> Result<S, serde_json::Error> {
+/// let my_s = S { elapsed: Duration::nanoseconds(-1_234_567_891) };
+/// let as_string = serde_json::to_string(&my_s)?;
+/// assert_eq!(as_string, r#"{"elapsed":-1234567891}"#);
+/// let my_s: S = serde_json::from_str(&as_string)?;
+/// assert_eq!(my_s.elapsed, Duration::nanoseconds(-1_234_567_891));
+/// # Ok(my_s)
+/// # }
+/// # fn main() { example().unwrap(); }
+/// ```
+pub mod duration_nanoseconds {
+    use oldtime::Duration;
+    use serdelib::{de, ser};
+
+    use super::NanoSecondsDurationVisitor;
+
+    /// Serialize a `Duration` into an integer number of nanoseconds
+    ///
+    /// Intended for use with `serde`s `serialize_with` attribute.
+    pub fn serialize<S>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match d.num_nanoseconds() {
+            Some(nanos) => serializer.serialize_i64(nanos),
+            None => Err(ser::Error::custom("duration out of range for nanoseconds")),
+        }
+    }
+
+    /// Deserialize a `Duration` from an integer number of nanoseconds
+    ///
+    /// Intended for use with `serde`s `deserialize_with` attribute.
+    pub fn deserialize<'de, D>(d: D) -> Result<Duration, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        d.deserialize_i64(NanoSecondsDurationVisitor)
+    }
+}
+
+/// Ser/de an optional `Duration` to/from an integer number of nanoseconds or none
+///
+/// Serialization fails for durations exceeding `i64` nanoseconds (about 292 years).
+/// Intended for use with `serde`'s `with` attribute.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate serde_json;
+/// # extern crate chrono;
+/// # use chrono::Duration;
+/// use chrono::serde::duration_nanoseconds_option;
+/// #[derive(Deserialize, Serialize)]
+/// struct S {
+///     #[serde(with = "duration_nanoseconds_option")]
+///     elapsed: Option<Duration>
+/// }
+///
+/// # fn example() -> Result<S, serde_json::Error> {
+/// let my_s = S { elapsed: Some(Duration::microseconds(5)) };
+/// let as_string = serde_json::to_string(&my_s)?;
+/// assert_eq!(as_string, r#"{"elapsed":5000}"#);
+/// let my_s: S = serde_json::from_str(&as_string)?;
+/// assert_eq!(my_s.elapsed, Some(Duration::microseconds(5)));
+/// # Ok(my_s)
+/// # }
+/// # fn main() { example().unwrap(); }
+/// ```
+pub mod duration_nanoseconds_option {
+    use oldtime::Duration;
+    use serdelib::{de, ser};
+
+    use super::{NanoSecondsDurationVisitor, OptionVisitor};
+
+    /// Serialize an optional `Duration` into an integer number of nanoseconds or none
+    ///
+    /// Intended for use with `serde`s `serialize_with` attribute.
+    pub fn serialize<S>(opt: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *opt {
+            Some(ref d) => match d.num_nanoseconds() {
+                Some(nanos) => serializer.serialize_some(&nanos),
+                None => Err(ser::Error::custom("duration out of range for nanoseconds")),
+            },
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an optional `Duration` from an integer number of nanoseconds or none
+    ///
+    /// Intended for use with `serde`s `deserialize_with` attribute.
+    pub fn deserialize<'de, D>(d: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        d.deserialize_option(OptionVisitor(NanoSecondsDurationVisitor))
+    }
+}
+
+/// Ser/de a `Duration` to/from an ISO 8601 string like `P1DT2.345S`
+///
+/// Days are the largest unit, and negative durations are preceded by `-`,
+/// as in the `Display` output of `Duration`.
+/// Unlike the `Serialize` implementation of `Duration`, this works for the `Duration`
+/// of the `time` crate re-exported with the `oldtime` feature,
+/// and uses the string for binary formats too.
+/// Intended for use with `serde`'s `with` attribute.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate serde_json;
+/// # extern crate chrono;
+/// # use chrono::Duration;
+/// use chrono::serde::duration_iso8601;
+/// #[derive(Deserialize, Serialize)]
+/// struct S {
+///     #[serde(with = "duration_iso8601")]
+///     timeout: Duration
+/// }
+///
+/// # fn example() -> Result<S, serde_json::Error> {
+/// let my_s = S { timeout: Duration::minutes(90) };
+/// let as_string = serde_json::to_string(&my_s)?;
+/// assert_eq!(as_string, r#"{"timeout":"PT5400S"}"#);
+/// let my_s: S = serde_json::from_str(r#"{"timeout":"PT1H30M"}"#)?;
+/// assert_eq!(my_s.timeout, Duration::minutes(90));
+/// # Ok(my_s)
+/// # }
+/// # fn main() { example().unwrap(); }
+/// ```
+pub mod duration_iso8601 {
+    use oldtime::Duration;
+    use serdelib::{de, ser};
+
+    use super::Iso8601DurationVisitor;
+
+    /// Serialize a `Duration` into an ISO 8601 string
+    ///
+    /// Intended for use with `serde`s `serialize_with` attribute.
+    pub fn serialize<S>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_str(d)
+    }
+
+    /// Deserialize a `Duration` from an ISO 8601 string
+    ///
+    /// Intended for use with `serde`s `deserialize_with` attribute.
+    pub fn deserialize<'de, D>(d: D) -> Result<Duration, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        d.deserialize_str(Iso8601DurationVisitor)
+    }
+}
+
+/// Ser/de an optional `Duration` to/from an ISO 8601 string or none
+///
+/// Intended for use with `serde`'s `with` attribute.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate serde_json;
+/// # extern crate chrono;
+/// # use chrono::Duration;
+/// use chrono::serde::duration_iso8601_option;
+/// #[derive(Deserialize, Serialize)]
+/// struct S {
+///     #[serde(with = "duration_iso8601_option")]
+///     timeout: Option<Duration>
+/// }
+///
+/// # fn example() -> Result<S, serde_json::Error> {
+/// let my_s = S { timeout: Some(Duration::milliseconds(-2345)) };
+/// let as_string = serde_json::to_string(&my_s)?;
+/// assert_eq!(as_string, r#"{"timeout":"-PT2.345S"}"#);
+/// let my_s: S = serde_json::from_str(r#"{"timeout":null}"#)?;
+/// assert_eq!(my_s.timeout, None);
+/// # Ok(my_s)
+/// # }
+/// # fn main() { example().unwrap(); }
+/// ```
+pub mod duration_iso8601_option {
+    use oldtime::Duration;
+    use serdelib::{de, ser};
+
+    use super::{Iso8601DurationVisitor, OptionVisitor};
+
+    /// Serialize an optional `Duration` into an ISO 8601 string or none
+    ///
+    /// Intended for use with `serde`s `serialize_with` attribute.
+    pub fn serialize<S>(opt: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *opt {
+            Some(ref d) => serializer.serialize_some(&format_args!("{}", d)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an optional `Duration` from an ISO 8601 string or none
+    ///
+    /// Intended for use with `serde`s `deserialize_with` attribute.
+    pub fn deserialize<'de, D>(d: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        d.deserialize_option(OptionVisitor(Iso8601DurationVisitor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate bincode;
+    extern crate serde_derive;
+    extern crate serde_json;
+
+    use self::serde_json::{Deserializer, Serializer};
+    use oldtime::Duration;
+
+    macro_rules! to_json {
+        ($module:ident, $value:expr) => {{
+            let mut out = Vec::new();
+            super::$module::serialize(&$value, &mut Serializer::new(&mut out)).map(|_| out)
+        }};
+    }
+
+    macro_rules! from_json {
+        ($module:ident, $json:expr) => {
+            super::$module::deserialize(&mut Deserializer::from_str($json))
+        };
+    }
+
+    #[test]
+    fn test_duration_seconds() {
+        let d = Duration::milliseconds(-90_500);
+        assert_eq!(to_json!(duration_seconds, d).unwrap(), b"-90");
+        assert_eq!(from_json!(duration_seconds, "-90").unwrap(), Duration::seconds(-90));
+        assert_eq!(
+            from_json!(duration_seconds, "9223372036854775").unwrap(),
+            Duration::seconds(9223372036854775)
+        );
+        assert!(from_json!(duration_seconds, "9223372036854776").is_err());
+        assert!(from_json!(duration_seconds, "18446744073709551615").is_err());
+        assert!(from_json!(duration_seconds, "\"90\"").is_err());
+
+        assert_eq!(to_json!(duration_seconds_option, Some(d)).unwrap(), b"-90");
+        assert_eq!(to_json!(duration_seconds_option, None::<Duration>).unwrap(), b"null");
+        assert_eq!(from_json!(duration_seconds_option, "90").unwrap(), Some(Duration::seconds(90)));
+        assert_eq!(from_json!(duration_seconds_option, "null").unwrap(), None);
+    }
+
+    #[test]
+    fn test_duration_milliseconds() {
+        let d = Duration::microseconds(1_500_999);
+        assert_eq!(to_json!(duration_milliseconds, d).unwrap(), b"1500");
+        assert_eq!(
+            to_json!(duration_milliseconds, Duration::max_value()).unwrap(),
+            b"9223372036854775807"
+        );
+        assert_eq!(
+            from_json!(duration_milliseconds, "-1500").unwrap(),
+            Duration::milliseconds(-1500)
+        );
+        assert!(from_json!(duration_milliseconds, "9223372036854775808").is_err());
+
+        assert_eq!(to_json!(duration_milliseconds_option, Some(d)).unwrap(), b"1500");
+        assert_eq!(to_json!(duration_milliseconds_option, None::<Duration>).unwrap(), b"null");
+        assert_eq!(
+            from_json!(duration_milliseconds_option, "1500").unwrap(),
+            Some(Duration::milliseconds(1500))
+        );
+        assert_eq!(from_json!(duration_milliseconds_option, "null").unwrap(), None);
+    }
+
+    #[test]
+    fn test_duration_nanoseconds() {
+        let d = Duration::nanoseconds(i64::MIN);
+        assert_eq!(to_json!(duration_nanoseconds, d).unwrap(), b"-9223372036854775808");
+        assert!(to_json!(duration_nanoseconds, d - Duration::nanoseconds(1)).is_err());
+        assert_eq!(from_json!(duration_nanoseconds, "-9223372036854775808").unwrap(), d);
+        assert_eq!(from_json!(duration_nanoseconds, "5").unwrap(), Duration::nanoseconds(5));
+
+        assert!(to_json!(duration_nanoseconds_option, Some(Duration::max_value())).is_err());
+        assert_eq!(to_json!(duration_nanoseconds_option, None::<Duration>).unwrap(), b"null");
+        assert_eq!(
+            from_json!(duration_nanoseconds_option, "5").unwrap(),
+            Some(Duration::nanoseconds(5))
+        );
+        assert_eq!(from_json!(duration_nanoseconds_option, "null").unwrap(), None);
+    }
+
+    #[test]
+    fn test_duration_iso8601() {
+        let d = Duration::days(1) + Duration::milliseconds(2345);
+        assert_eq!(to_json!(duration_iso8601, d).unwrap(), br#""P1DT2.345S""#);
+        assert_eq!(to_json!(duration_iso8601, -d).unwrap(), br#""-P1DT2.345S""#);
+        assert_eq!(from_json!(duration_iso8601, r#""P1DT2.345S""#).unwrap(), d);
+        assert_eq!(from_json!(duration_iso8601, r#""-PT1H30M""#).unwrap(), Duration::minutes(-90));
+        for &d in &[Duration::zero(), Duration::nanoseconds(-1), Duration::max_value()] {
+            let json = to_json!(duration_iso8601, d).unwrap();
+            let json = String::from_utf8(json).unwrap();
+            assert_eq!(from_json!(duration_iso8601, &json).unwrap(), d);
+        }
+        assert!(from_json!(duration_iso8601, r#""P1Y""#).is_err());
+        assert!(from_json!(duration_iso8601, "90").is_err());
+
+        assert_eq!(
+            to_json!(duration_iso8601_option, Some(Duration::seconds(90))).unwrap(),
+            br#""PT90S""#
+        );
+        assert_eq!(to_json!(duration_iso8601_option, None::<Duration>).unwrap(), b"null");
+        assert_eq!(
+            from_json!(duration_iso8601_option, r#""PT90S""#).unwrap(),
+            Some(Duration::seconds(90))
+        );
+        assert_eq!(from_json!(duration_iso8601_option, "null").unwrap(), None);
+    }
+
+    #[test]
+    fn test_duration_iso8601_bincode() {
+        use self::bincode::{deserialize, serialize, Infinite};
+        use self::serde_derive::{Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct S {
+            #[serde(with = "::serde::duration_iso8601")]
+            d: Duration,
+            #[serde(with = "::serde::duration_iso8601_option")]
+            opt: Option<Duration>,
+        }
+
+        let s = S { d: Duration::milliseconds(-1500), opt: Some(Duration::weeks(2)) };
+        let encoded = serialize(&s, Infinite).unwrap();
+        assert_eq!(deserialize::<S>(&encoded).unwrap(), s);
+        let s = S { d: Duration::zero(), opt: None };
+        let encoded = serialize(&s, Infinite).unwrap();
+        assert_eq!(deserialize::<S>(&encoded).unwrap(), s);
+    }
+}
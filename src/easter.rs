@@ -0,0 +1,164 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Easter and the movable feasts depending on it.
+//!
+//! The date of Easter Sunday is determined differently by the Western churches,
+//! which use the Gregorian calendar, and the Eastern Orthodox churches,
+//! which use the Julian calendar. Both are returned as proleptic Gregorian dates.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono::easter::Easter;
+//! use chrono::NaiveDate;
+//!
+//! assert_eq!(Easter::Western.sunday(2021), Some(NaiveDate::from_ymd(2021, 4, 4)));
+//! assert_eq!(Easter::Orthodox.sunday(2021), Some(NaiveDate::from_ymd(2021, 5, 2)));
+//! assert_eq!(Easter::Western.pentecost(2021), Some(NaiveDate::from_ymd(2021, 5, 23)));
+//! ```
+
+use oldtime::Duration as OldDuration;
+
+use div::{div_floor, div_mod_floor, mod_floor};
+use naive::NaiveDate;
+
+/// The method used to compute the date of Easter.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Easter {
+    /// Easter of the Western churches, computed with the Gregorian calendar.
+    Western,
+    /// Easter of the Eastern Orthodox churches, computed with the Julian calendar.
+    Orthodox,
+}
+
+impl Easter {
+    /// Returns Easter Sunday of given year.
+    ///
+    /// Returns `None` when the date is out of range.
+    pub fn sunday(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            Easter::Western => western(year),
+            Easter::Orthodox => orthodox(year),
+        }
+    }
+
+    /// Returns Good Friday (two days before Easter Sunday) of given year.
+    pub fn good_friday(&self, year: i32) -> Option<NaiveDate> {
+        self.offset(year, -2)
+    }
+
+    /// Returns Ascension Day (39 days after Easter Sunday) of given year.
+    pub fn ascension(&self, year: i32) -> Option<NaiveDate> {
+        self.offset(year, 39)
+    }
+
+    /// Returns Pentecost (49 days after Easter Sunday) of given year.
+    pub fn pentecost(&self, year: i32) -> Option<NaiveDate> {
+        self.offset(year, 49)
+    }
+
+    /// Returns the date at given number of days from Easter Sunday of given year.
+    pub(crate) fn offset(&self, year: i32, days: i32) -> Option<NaiveDate> {
+        try_opt!(self.sunday(year)).checked_add_signed(OldDuration::days(i64::from(days)))
+    }
+}
+
+/// The anonymous Gregorian algorithm (Meeus/Jones/Butcher).
+fn western(year: i32) -> Option<NaiveDate> {
+    let a = mod_floor(year, 19);
+    let (b, c) = div_mod_floor(year, 100);
+    let (d, e) = div_mod_floor(b, 4);
+    let f = div_floor(b + 8, 25);
+    let g = div_floor(b - f + 1, 3);
+    let h = mod_floor(19 * a + b - d - g + 15, 30);
+    let i = c / 4;
+    let k = c % 4;
+    let l = mod_floor(32 + 2 * e + 2 * i - h - k, 7);
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Meeus' Julian algorithm, converted to the Gregorian calendar.
+fn orthodox(year: i32) -> Option<NaiveDate> {
+    let a = mod_floor(year, 4);
+    let b = mod_floor(year, 7);
+    let c = mod_floor(year, 19);
+    let d = (19 * c + 15) % 30;
+    let e = (2 * a + 4 * b - d + 34) % 7;
+    let month = (d + e + 114) / 31;
+    let day = (d + e + 114) % 31 + 1;
+    // the Julian date is always in March or April, i.e. after the possible leap day
+    // (present in the Julian but not in the Gregorian calendar) of the year
+    let julian = try_opt!(NaiveDate::from_ymd_opt(year, month as u32, day as u32));
+    let shift = div_floor(year, 100) - div_floor(year, 400) - 2;
+    julian.checked_add_signed(OldDuration::days(i64::from(shift)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Easter;
+    use naive::{NaiveDate, MAX_DATE};
+    use {Datelike, Weekday};
+
+    #[test]
+    fn test_easter_sunday() {
+        let western = [
+            (1583, 4, 10),
+            (1818, 3, 22),
+            (1943, 4, 25),
+            (2000, 4, 23),
+            (2008, 3, 23),
+            (2019, 4, 21),
+            (2021, 4, 4),
+            (2038, 4, 25),
+            (2285, 3, 22),
+        ];
+        for &(y, m, d) in &western {
+            assert_eq!(Easter::Western.sunday(y), Some(NaiveDate::from_ymd(y, m, d)));
+        }
+
+        let orthodox = [
+            (1900, 4, 22),
+            (2000, 4, 30),
+            (2008, 4, 27),
+            (2021, 5, 2),
+            (2022, 4, 24),
+            (2023, 4, 16),
+            (2024, 5, 5),
+            (2025, 4, 20),
+        ];
+        for &(y, m, d) in &orthodox {
+            assert_eq!(Easter::Orthodox.sunday(y), Some(NaiveDate::from_ymd(y, m, d)));
+        }
+
+        // Western Easter is between March 22 and April 25,
+        // Orthodox Easter between April 4 and May 8 in the 20th and 21st centuries
+        for year in -2000..3000 {
+            let sunday = Easter::Western.sunday(year).unwrap();
+            assert_eq!(sunday.weekday(), Weekday::Sun);
+            assert!(NaiveDate::from_ymd(year, 3, 22) <= sunday);
+            assert!(sunday <= NaiveDate::from_ymd(year, 4, 25));
+
+            let sunday = Easter::Orthodox.sunday(year).unwrap();
+            assert_eq!(sunday.weekday(), Weekday::Sun);
+            if (1900..2100).contains(&year) {
+                assert!(NaiveDate::from_ymd(year, 4, 4) <= sunday);
+                assert!(sunday <= NaiveDate::from_ymd(year, 5, 8));
+            }
+        }
+        assert_eq!(Easter::Western.sunday(MAX_DATE.year() + 1), None);
+    }
+
+    #[test]
+    fn test_movable_feasts() {
+        assert_eq!(Easter::Western.good_friday(2021), Some(NaiveDate::from_ymd(2021, 4, 2)));
+        assert_eq!(Easter::Western.ascension(2021), Some(NaiveDate::from_ymd(2021, 5, 13)));
+        assert_eq!(Easter::Western.pentecost(2021), Some(NaiveDate::from_ymd(2021, 5, 23)));
+        assert_eq!(Easter::Orthodox.good_friday(2021), Some(NaiveDate::from_ymd(2021, 4, 30)));
+        assert_eq!(Easter::Orthodox.ascension(2021), Some(NaiveDate::from_ymd(2021, 6, 10)));
+        assert_eq!(Easter::Orthodox.pentecost(2021), Some(NaiveDate::from_ymd(2021, 6, 20)));
+    }
+}
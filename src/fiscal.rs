@@ -0,0 +1,358 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Fiscal calendars.
+//!
+//! A [`FiscalCalendar`](./struct.FiscalCalendar.html) divides fiscal years into four quarters
+//! of three periods each. A fiscal year either consists of twelve calendar months starting
+//! in a configurable month, or, for retail calendars, of 52 or 53 whole weeks split into
+//! periods of four and five weeks (e.g. 4-4-5).
+//!
+//! # Example
+//!
+//! ```
+//! use chrono::fiscal::FiscalCalendar;
+//! use chrono::NaiveDate;
+//!
+//! // the fiscal year of the U.S. federal government starts on October 1
+//! let federal = FiscalCalendar::new(10);
+//! let date = NaiveDate::from_ymd(2021, 11, 25);
+//! assert_eq!(federal.year(date), 2022);
+//! assert_eq!(federal.quarter(date), 1);
+//! assert_eq!(federal.period(date), 2);
+//! assert_eq!(federal.year_start(2022), Some(NaiveDate::from_ymd(2021, 10, 1)));
+//! assert_eq!(federal.quarter_end(2022, 1), Some(NaiveDate::from_ymd(2021, 12, 31)));
+//! ```
+
+use oldtime::Duration as OldDuration;
+
+use naive::{NaiveDate, MIN_DATE};
+use {Datelike, Weekday};
+
+/// The number of weeks in each of the three periods of a quarter in a 52-53 week fiscal year.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum WeekPattern {
+    /// Four, four and five weeks.
+    FourFourFive,
+    /// Four, five and four weeks.
+    FourFiveFour,
+    /// Five, four and four weeks.
+    FiveFourFour,
+}
+
+impl WeekPattern {
+    fn weeks(&self) -> [u32; 3] {
+        match *self {
+            WeekPattern::FourFourFive => [4, 4, 5],
+            WeekPattern::FourFiveFour => [4, 5, 4],
+            WeekPattern::FiveFourFour => [5, 4, 4],
+        }
+    }
+}
+
+/// How the last day of a 52-53 week fiscal year is chosen.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum YearEnd {
+    /// The last given weekday of the month before the start month.
+    Last,
+    /// The given weekday nearest to the last day of the month before the start month,
+    /// which may fall in the start month.
+    Nearest,
+}
+
+/// A fiscal calendar.
+///
+/// Fiscal years are named by the calendar year in which they end by default.
+/// Periods are numbered from 1 to 12 and quarters from 1 to 4 within a fiscal year.
+///
+/// See the [module documentation](./index.html) for an example.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FiscalCalendar {
+    start_month: u32,
+    named_by_start: bool,
+    weeks: Option<(Weekday, YearEnd, WeekPattern)>,
+}
+
+impl FiscalCalendar {
+    /// Makes a new fiscal calendar with years of twelve months starting in given month.
+    ///
+    /// Panics if `start_month` is not between 1 and 12.
+    pub fn new(start_month: u32) -> FiscalCalendar {
+        assert!(1 <= start_month && start_month <= 12, "invalid start month");
+        FiscalCalendar { start_month: start_month, named_by_start: false, weeks: None }
+    }
+
+    /// Makes the fiscal years consist of 52 or 53 whole weeks, each year ending on `end_weekday`
+    /// at the end of the month before the start month, and quarters split by `pattern`.
+    ///
+    /// The extra week of a 53-week year is added to its last period.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::fiscal::{FiscalCalendar, WeekPattern, YearEnd};
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// // the 4-5-4 calendar of the National Retail Federation
+    /// let retail = FiscalCalendar::new(2)
+    ///     .with_weeks(Weekday::Sat, YearEnd::Nearest, WeekPattern::FourFiveFour)
+    ///     .named_by_start_year();
+    /// assert_eq!(retail.year_start(2023), Some(NaiveDate::from_ymd(2023, 1, 29)));
+    /// assert_eq!(retail.year_end(2023), Some(NaiveDate::from_ymd(2024, 2, 3)));
+    /// assert_eq!(retail.weeks_in_year(2023), Some(53));
+    /// assert_eq!(retail.period_end(2023, 2), Some(NaiveDate::from_ymd(2023, 4, 1)));
+    /// ```
+    pub fn with_weeks(
+        mut self,
+        end_weekday: Weekday,
+        year_end: YearEnd,
+        pattern: WeekPattern,
+    ) -> FiscalCalendar {
+        self.weeks = Some((end_weekday, year_end, pattern));
+        self
+    }
+
+    /// Names the fiscal years by the calendar year in which they start,
+    /// instead of the year in which they end.
+    pub fn named_by_start_year(mut self) -> FiscalCalendar {
+        self.named_by_start = true;
+        self
+    }
+
+    /// Returns the month in which the fiscal years (nominally) start.
+    pub fn start_month(&self) -> u32 {
+        self.start_month
+    }
+
+    /// Returns the fiscal year of the date.
+    pub fn year(&self, date: NaiveDate) -> i32 {
+        let year = date.year();
+        let end_year = match self.last_day(year - 1) {
+            Some(end) if date <= end => year - 1,
+            _ => match self.last_day(year) {
+                Some(end) if date > end => year + 1,
+                _ => year,
+            },
+        };
+        end_year - self.name_offset()
+    }
+
+    /// Returns the fiscal quarter of the date, from 1 to 4.
+    pub fn quarter(&self, date: NaiveDate) -> u32 {
+        (self.period(date) - 1) / 3 + 1
+    }
+
+    /// Returns the fiscal period of the date, from 1 to 12.
+    ///
+    /// A period is a calendar month, or a group of four or five weeks for 52-53 week years.
+    pub fn period(&self, date: NaiveDate) -> u32 {
+        match self.weeks {
+            None => (date.month0() + 12 - (self.start_month - 1)) % 12 + 1,
+            Some((_, _, pattern)) => {
+                let week = self.week(date);
+                let mut end = 0;
+                for period in 1..12 {
+                    end += pattern.weeks()[(period as usize - 1) % 3];
+                    if week <= end {
+                        return period;
+                    }
+                }
+                12
+            }
+        }
+    }
+
+    /// Returns the week of the fiscal year of the date, starting from 1 on the first day of the year.
+    pub fn week(&self, date: NaiveDate) -> u32 {
+        let start = self.year_start(self.year(date)).unwrap_or(MIN_DATE);
+        date.signed_duration_since(start).num_weeks() as u32 + 1
+    }
+
+    /// Returns the number of weeks in given fiscal year, if the year consists of 52 or 53 weeks.
+    pub fn weeks_in_year(&self, year: i32) -> Option<u32> {
+        try_opt!(self.weeks);
+        let start = try_opt!(self.year_start(year));
+        let end = try_opt!(self.year_end(year));
+        Some((end.signed_duration_since(start).num_days() + 1) as u32 / 7)
+    }
+
+    /// Returns the first day of given fiscal year.
+    pub fn year_start(&self, year: i32) -> Option<NaiveDate> {
+        try_opt!(self.last_day(year + self.name_offset() - 1)).succ_opt()
+    }
+
+    /// Returns the last day of given fiscal year.
+    pub fn year_end(&self, year: i32) -> Option<NaiveDate> {
+        self.last_day(year + self.name_offset())
+    }
+
+    /// Returns the first day of given quarter (from 1 to 4) of the fiscal year.
+    pub fn quarter_start(&self, year: i32, quarter: u32) -> Option<NaiveDate> {
+        if quarter < 1 || quarter > 4 {
+            return None;
+        }
+        self.period_start(year, quarter * 3 - 2)
+    }
+
+    /// Returns the last day of given quarter (from 1 to 4) of the fiscal year.
+    pub fn quarter_end(&self, year: i32, quarter: u32) -> Option<NaiveDate> {
+        if quarter < 1 || quarter > 4 {
+            return None;
+        }
+        self.period_end(year, quarter * 3)
+    }
+
+    /// Returns the first day of given period (from 1 to 12) of the fiscal year.
+    pub fn period_start(&self, year: i32, period: u32) -> Option<NaiveDate> {
+        if period < 1 || period > 12 {
+            return None;
+        }
+        let start = try_opt!(self.year_start(year));
+        match self.weeks {
+            None => {
+                let month0 = start.month0() + period - 1;
+                NaiveDate::from_ymd_opt(start.year() + (month0 / 12) as i32, month0 % 12 + 1, 1)
+            }
+            Some((_, _, pattern)) => {
+                let weeks = (1..period).map(|p| pattern.weeks()[(p as usize - 1) % 3]).sum::<u32>();
+                start.checked_add_signed(OldDuration::weeks(i64::from(weeks)))
+            }
+        }
+    }
+
+    /// Returns the last day of given period (from 1 to 12) of the fiscal year.
+    pub fn period_end(&self, year: i32, period: u32) -> Option<NaiveDate> {
+        match period {
+            1..=11 => try_opt!(self.period_start(year, period + 1)).pred_opt(),
+            12 => self.year_end(year),
+            _ => None,
+        }
+    }
+
+    /// Returns the difference between the calendar year of the end of a fiscal year and its name.
+    fn name_offset(&self) -> i32 {
+        if self.named_by_start && self.start_month != 1 {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Returns the last day of the fiscal year ending (nominally) in given calendar year.
+    fn last_day(&self, year: i32) -> Option<NaiveDate> {
+        let next_month = if self.start_month == 1 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(year, self.start_month, 1)
+        };
+        let month_end = try_opt!(try_opt!(next_month).pred_opt());
+        let (weekday, year_end) = match self.weeks {
+            None => return Some(month_end),
+            Some((weekday, year_end, _)) => (weekday, year_end),
+        };
+        let back =
+            (month_end.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+        let days = match year_end {
+            YearEnd::Nearest if back > 3 => 7 - i64::from(back),
+            _ => -i64::from(back),
+        };
+        month_end.checked_add_signed(OldDuration::days(days))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FiscalCalendar, WeekPattern, YearEnd};
+    use naive::NaiveDate;
+    use Weekday;
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd(y, m, d)
+    }
+
+    #[test]
+    fn test_monthly_fiscal_calendar() {
+        let calendar = FiscalCalendar::new(1);
+        assert_eq!(calendar.year(ymd(2021, 1, 1)), 2021);
+        assert_eq!(calendar.year(ymd(2021, 12, 31)), 2021);
+        assert_eq!(calendar.quarter(ymd(2021, 8, 1)), 3);
+        assert_eq!(calendar.year_start(2021), Some(ymd(2021, 1, 1)));
+        assert_eq!(calendar.year_end(2021), Some(ymd(2021, 12, 31)));
+        assert_eq!(calendar.weeks_in_year(2021), None);
+        assert_eq!(calendar.named_by_start_year(), FiscalCalendar::new(1).named_by_start_year());
+        assert_eq!(calendar.named_by_start_year().year(ymd(2021, 6, 1)), 2021);
+
+        let federal = FiscalCalendar::new(10);
+        assert_eq!(federal.year(ymd(2021, 9, 30)), 2021);
+        assert_eq!(federal.year(ymd(2021, 10, 1)), 2022);
+        assert_eq!(federal.period(ymd(2021, 9, 30)), 12);
+        assert_eq!(federal.quarter(ymd(2021, 9, 30)), 4);
+        assert_eq!(federal.period(ymd(2022, 2, 28)), 5);
+        assert_eq!(federal.week(ymd(2021, 10, 8)), 2);
+        assert_eq!(federal.period_start(2022, 5), Some(ymd(2022, 2, 1)));
+        assert_eq!(federal.period_end(2022, 5), Some(ymd(2022, 2, 28)));
+        assert_eq!(federal.quarter_start(2022, 3), Some(ymd(2022, 4, 1)));
+        assert_eq!(federal.quarter_end(2022, 4), Some(ymd(2022, 9, 30)));
+        assert_eq!(federal.quarter_end(2022, 5), None);
+        assert_eq!(federal.period_start(2022, 0), None);
+
+        let april = FiscalCalendar::new(4).named_by_start_year();
+        assert_eq!(april.year(ymd(2022, 3, 31)), 2021);
+        assert_eq!(april.year(ymd(2022, 4, 1)), 2022);
+        assert_eq!(april.year_start(2022), Some(ymd(2022, 4, 1)));
+        assert_eq!(april.year_end(2022), Some(ymd(2023, 3, 31)));
+    }
+
+    #[test]
+    fn test_weekly_fiscal_calendar() {
+        let retail = FiscalCalendar::new(2)
+            .with_weeks(Weekday::Sat, YearEnd::Nearest, WeekPattern::FourFiveFour)
+            .named_by_start_year();
+        assert_eq!(retail.year_start(2021), Some(ymd(2021, 1, 31)));
+        assert_eq!(retail.year_end(2021), Some(ymd(2022, 1, 29)));
+        assert_eq!(retail.weeks_in_year(2021), Some(52));
+        assert_eq!(retail.weeks_in_year(2023), Some(53));
+        assert_eq!(retail.year(ymd(2023, 1, 28)), 2022);
+        assert_eq!(retail.year(ymd(2023, 1, 29)), 2023);
+        assert_eq!(retail.year(ymd(2024, 2, 3)), 2023);
+        assert_eq!(retail.year(ymd(2024, 2, 4)), 2024);
+
+        assert_eq!(retail.period_start(2023, 2), Some(ymd(2023, 2, 26)));
+        assert_eq!(retail.period_start(2023, 12), Some(ymd(2023, 12, 31)));
+        assert_eq!(retail.period_end(2023, 11), Some(ymd(2023, 12, 30)));
+        assert_eq!(retail.period(ymd(2024, 2, 3)), 12);
+        assert_eq!(retail.week(ymd(2024, 2, 3)), 53);
+        assert_eq!(retail.period(ymd(2023, 2, 25)), 1);
+        assert_eq!(retail.period(ymd(2023, 2, 26)), 2);
+        assert_eq!(retail.period(ymd(2023, 4, 1)), 2);
+        assert_eq!(retail.quarter(ymd(2023, 4, 29)), 1);
+        assert_eq!(retail.quarter(ymd(2023, 4, 30)), 2);
+        assert_eq!(retail.quarter_start(2023, 2), Some(ymd(2023, 4, 30)));
+
+        let last = FiscalCalendar::new(1).with_weeks(
+            Weekday::Sun,
+            YearEnd::Last,
+            WeekPattern::FourFourFive,
+        );
+        assert_eq!(last.year_end(2021), Some(ymd(2021, 12, 26)));
+        assert_eq!(last.year_start(2022), Some(ymd(2021, 12, 27)));
+        assert_eq!(last.year(ymd(2021, 12, 31)), 2022);
+        assert_eq!(last.period_end(2022, 3), Some(ymd(2022, 3, 27)));
+
+        // every day belongs to the fiscal year, quarter and period which contain it
+        for calendar in &[retail, last, FiscalCalendar::new(7)] {
+            let mut date = ymd(2019, 1, 1);
+            while date < ymd(2026, 1, 1) {
+                let year = calendar.year(date);
+                let (quarter, period) = (calendar.quarter(date), calendar.period(date));
+                assert!(calendar.year_start(year).unwrap() <= date);
+                assert!(date <= calendar.year_end(year).unwrap());
+                assert!(calendar.quarter_start(year, quarter).unwrap() <= date);
+                assert!(date <= calendar.quarter_end(year, quarter).unwrap());
+                assert!(calendar.period_start(year, period).unwrap() <= date);
+                assert!(date <= calendar.period_end(year, period).unwrap());
+                date = date.succ();
+            }
+        }
+    }
+}
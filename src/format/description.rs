@@ -0,0 +1,422 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Constant evaluation of `strftime`-like format strings for the
+//! [`format_description!`](../macro.format_description.html) macro.
+//!
+//! The parser mirrors [`StrftimeItems::new`](../strftime/struct.StrftimeItems.html),
+//! but reports invalid specifiers as errors which fail the constant evaluation.
+
+use core::mem::{self, ManuallyDrop};
+use core::str;
+
+use super::{Fixed, InternalFixed, InternalInternal, Item, Numeric, Pad};
+use super::{InternalNumeric, InternalNumericInternal};
+
+/// An item with the position and sub-index of the next item, or an error message.
+///
+/// Constant evaluation can not run destructors, even for items owning nothing,
+/// so items are passed around as `ManuallyDrop` until they are stored.
+type Next<'a> = Result<(ManuallyDrop<Item<'a>>, usize, usize), &'static str>;
+
+/// Returns the number of formatting items in `fmt`.
+///
+/// Panics if `fmt` is not a valid format string.
+#[doc(hidden)]
+pub const fn description_len(fmt: &str) -> usize {
+    let (mut count, mut pos, mut sub) = (0, 0, 0);
+    while pos < fmt.len() {
+        match next_item(fmt, pos, sub) {
+            Ok((_, next_pos, next_sub)) => {
+                pos = next_pos;
+                sub = next_sub;
+            }
+            Err(msg) => panic!("{}", msg),
+        }
+        count += 1;
+    }
+    count
+}
+
+/// Returns the formatting items of `fmt`, which should have `N` of them.
+///
+/// Panics if `fmt` is not a valid format string.
+#[doc(hidden)]
+pub const fn description_items<const N: usize>(fmt: &str) -> [Item<'_>; N] {
+    const ERROR: Item<'static> = Item::Error;
+    let mut items = [ERROR; N];
+    let (mut i, mut pos, mut sub) = (0, 0, 0);
+    while i < N {
+        match next_item(fmt, pos, sub) {
+            Ok((item, next_pos, next_sub)) => {
+                mem::forget(mem::replace(&mut items[i], ManuallyDrop::into_inner(item)));
+                pos = next_pos;
+                sub = next_sub;
+            }
+            Err(msg) => panic!("{}", msg),
+        }
+        i += 1;
+    }
+    items
+}
+
+/// Parses the item at byte `pos` of `fmt`, or the `sub`-th item of the composite specifier
+/// (e.g. `%F`) at that position.
+const fn next_item(fmt: &str, pos: usize, sub: usize) -> Next<'_> {
+    let bytes = fmt.as_bytes();
+    let (c, mut end) = char_at(bytes, pos);
+    if c == '%' {
+        return specifier(bytes, pos, sub);
+    }
+
+    // a run of whitespace or literal text, where `%` is not a whitespace
+    let is_space = c.is_whitespace();
+    while end < bytes.len() {
+        let (c, next) = char_at(bytes, end);
+        if c.is_whitespace() != is_space || c == '%' {
+            break;
+        }
+        end = next;
+    }
+    let text = substr(fmt, pos, end);
+    Ok((ManuallyDrop::new(if is_space { sp!(text) } else { lit!(text) }), end, 0))
+}
+
+/// Parses the specifier starting with the `%` at byte `start`.
+const fn specifier(bytes: &[u8], start: usize, sub: usize) -> Next<'static> {
+    macro_rules! next {
+        ($pos:expr) => {
+            if $pos < bytes.len() {
+                char_at(bytes, $pos)
+            } else {
+                return Err("premature end of format string");
+            }
+        };
+    }
+    macro_rules! invalid {
+        () => {
+            return Err("invalid format specifier")
+        };
+    }
+
+    let (mut spec, mut pos) = next!(start + 1);
+    let pad_override = match spec {
+        '-' => Some(Pad::None),
+        '0' => Some(Pad::Zero),
+        '_' => Some(Pad::Space),
+        _ => None,
+    };
+    let is_alternate = spec == '#';
+    if pad_override.is_some() || is_alternate {
+        (spec, pos) = next!(pos);
+    }
+    if is_alternate && spec != 'z' {
+        invalid!();
+    }
+
+    if let Some(last) = composite_len(spec) {
+        if pad_override.is_some() {
+            return Err("padding modifier on a composite specifier");
+        }
+        let item = ManuallyDrop::new(composite_item(spec, sub));
+        return Ok(if sub == last - 1 { (item, pos, 0) } else { (item, start, sub + 1) });
+    }
+
+    let item = match spec {
+        'A' => fix!(LongWeekdayName),
+        'B' => fix!(LongMonthName),
+        'C' => num0!(YearDiv100),
+        'G' => num0!(IsoYear),
+        'H' => num0!(Hour),
+        'I' => num0!(Hour12),
+        'M' => num0!(Minute),
+        'P' => fix!(LowerAmPm),
+        'S' => num0!(Second),
+        'U' => num0!(WeekFromSun),
+        'V' => num0!(IsoWeek),
+        'W' => num0!(WeekFromMon),
+        'Y' => num0!(Year),
+        'Z' => fix!(TimezoneName),
+        'a' => fix!(ShortWeekdayName),
+        'b' | 'h' => fix!(ShortMonthName),
+        'd' => num0!(Day),
+        'e' => nums!(Day),
+        'f' => num0!(Nanosecond),
+        'g' => num0!(IsoYearMod100),
+        'j' => num0!(Ordinal),
+        'k' => nums!(Hour),
+        'l' => nums!(Hour12),
+        'm' => num0!(Month),
+        'n' => sp!("\n"),
+        'p' => fix!(UpperAmPm),
+        'q' => internal_num!(InternalNumericInternal::Quarter),
+        's' => num!(Timestamp),
+        't' => sp!("\t"),
+        'u' => num!(WeekdayFromMon),
+        'w' => num!(NumDaysFromSun),
+        'y' => num0!(YearMod100),
+        'z' => {
+            if is_alternate {
+                internal_fix!(TimezoneOffsetPermissive)
+            } else {
+                fix!(TimezoneOffset)
+            }
+        }
+        '+' => fix!(RFC3339),
+        ':' => {
+            let (c, next) = next!(pos);
+            pos = next;
+            match c {
+                'z' => fix!(TimezoneOffsetColon),
+                _ => invalid!(),
+            }
+        }
+        '.' => {
+            let (c, next) = next!(pos);
+            pos = next;
+            match c {
+                'f' => fix!(Nanosecond),
+                '3' | '6' | '9' => {
+                    let (f, next) = next!(pos);
+                    pos = next;
+                    match (c, f) {
+                        ('3', 'f') => fix!(Nanosecond3),
+                        ('6', 'f') => fix!(Nanosecond6),
+                        ('9', 'f') => fix!(Nanosecond9),
+                        _ => invalid!(),
+                    }
+                }
+                _ => invalid!(),
+            }
+        }
+        '1'..='9' => {
+            // `%3f`, `%6f` and `%9f`, or a fiscal year starting in given month
+            let mut month = spec as u8 - b'0';
+            (spec, pos) = next!(pos);
+            if month == 1 && matches!(spec, '0'..='2') {
+                month = 10 + (spec as u8 - b'0');
+                (spec, pos) = next!(pos);
+            }
+            match (month, spec) {
+                (3, 'f') => internal_fix!(Nanosecond3NoDot),
+                (6, 'f') => internal_fix!(Nanosecond6NoDot),
+                (9, 'f') => internal_fix!(Nanosecond9NoDot),
+                (_, 'Y') => internal_num0!(InternalNumericInternal::FiscalYear(month)),
+                (_, 'q') => internal_num!(InternalNumericInternal::FiscalQuarter(month)),
+                _ => invalid!(),
+            }
+        }
+        '%' => lit!("%"),
+        _ => invalid!(),
+    };
+
+    match (pad_override, item) {
+        (None, item) => Ok((ManuallyDrop::new(item), pos, 0)),
+        (Some(pad), Item::Numeric(kind, _)) => {
+            Ok((ManuallyDrop::new(Item::Numeric(kind, pad)), pos, 0))
+        }
+        (Some(_), _) => Err("padding modifier on a non-numeric specifier"),
+    }
+}
+
+/// Returns the number of items of a composite specifier, or `None` for other specifiers.
+///
+/// `%x`, `%X` and `%c` use the formats of the default locale.
+const fn composite_len(spec: char) -> Option<usize> {
+    match spec {
+        'D' | 'F' | 'T' | 'v' | 'x' | 'X' => Some(5),
+        'R' => Some(3),
+        'r' => Some(7),
+        'c' => Some(13),
+        _ => None,
+    }
+}
+
+/// Returns the `sub`-th item of a composite specifier.
+const fn composite_item(spec: char, sub: usize) -> Item<'static> {
+    match (spec, sub) {
+        ('D', 0) | ('x', 0) | ('F', 2) => num0!(Month),
+        ('D', 1) | ('D', 3) | ('x', 1) | ('x', 3) => lit!("/"),
+        ('D', 2) | ('x', 2) | ('F', 4) => num0!(Day),
+        ('D', 4) | ('x', 4) => num0!(YearMod100),
+        ('F', 0) | ('v', 4) | ('c', 12) => num0!(Year),
+        ('F', 1) | ('F', 3) | ('v', 1) | ('v', 3) => lit!("-"),
+        ('R', 0) | ('T', 0) | ('X', 0) | ('c', 6) => num0!(Hour),
+        ('R', 1) | ('T', 1) | ('T', 3) | ('X', 1) | ('X', 3) => lit!(":"),
+        ('r', 1) | ('r', 3) | ('c', 7) | ('c', 9) => lit!(":"),
+        ('R', 2) | ('T', 2) | ('X', 2) | ('r', 2) | ('c', 8) => num0!(Minute),
+        ('T', 4) | ('X', 4) | ('r', 4) | ('c', 10) => num0!(Second),
+        ('r', 0) => num0!(Hour12),
+        ('r', 5) | ('c', 1) | ('c', 3) | ('c', 5) | ('c', 11) => sp!(" "),
+        ('r', 6) => fix!(UpperAmPm),
+        ('v', 0) | ('c', 4) => nums!(Day),
+        ('v', 2) | ('c', 2) => fix!(ShortMonthName),
+        ('c', 0) => fix!(ShortWeekdayName),
+        _ => Item::Error, // out of `composite_len`
+    }
+}
+
+/// Decodes the character at byte `pos` of a UTF-8 string, returning it with the next position.
+const fn char_at(bytes: &[u8], pos: usize) -> (char, usize) {
+    let first = bytes[pos];
+    let (mut c, len) = match first {
+        0x00..=0x7f => (first as u32, 1),
+        0xc0..=0xdf => ((first & 0x1f) as u32, 2),
+        0xe0..=0xef => ((first & 0x0f) as u32, 3),
+        _ => ((first & 0x07) as u32, 4),
+    };
+    let mut i = 1;
+    while i < len {
+        c = (c << 6) | (bytes[pos + i] & 0x3f) as u32;
+        i += 1;
+    }
+    match char::from_u32(c) {
+        Some(c) => (c, pos + len),
+        None => panic!("invalid UTF-8"),
+    }
+}
+
+/// Returns `&s[start..end]`.
+const fn substr(s: &str, start: usize, end: usize) -> &str {
+    let (head, _) = s.as_bytes().split_at(end);
+    let (_, bytes) = head.split_at(start);
+    match str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(_) => panic!("not a character boundary"),
+    }
+}
+
+/// Validates a `strftime`-like format string at compile time and expands to its
+/// formatting items as a `&'static [Item<'static>]`.
+///
+/// The format string is parsed as by [`StrftimeItems::new`](./format/strftime/struct.StrftimeItems.html)
+/// (see [`format::strftime`](./format/strftime/index.html) for the syntax), but an invalid
+/// specifier is a build error instead of an `Item::Error`, and nothing is parsed at runtime.
+/// The argument can be any constant `&'static str` expression.
+///
+/// The items can be passed to the `format_with_items` methods and [`format::parse`](./format/fn.parse.html).
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate chrono;
+/// use chrono::format::{self, Item, Parsed};
+/// use chrono::NaiveDate;
+///
+/// # fn main() {
+/// static ISO_DATE: &[Item] = format_description!("%Y-%m-%d");
+///
+/// let d = NaiveDate::from_ymd(2020, 1, 31);
+/// assert_eq!(d.format_with_items(ISO_DATE.iter()).to_string(), "2020-01-31");
+///
+/// let mut parsed = Parsed::new();
+/// format::parse(&mut parsed, "2020-01-31", ISO_DATE.iter()).unwrap();
+/// assert_eq!(parsed.to_naive_date(), Ok(d));
+///
+/// let time = format_description!("%H:%M:%S%.3f %:z");
+/// assert_eq!(time.len(), 8);
+/// # }
+/// ```
+///
+/// Typos fail to compile:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate chrono;
+/// # fn main() {
+/// let items = format_description!("%Y-%m-%Q");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! format_description {
+    ($fmt:expr) => {{
+        const DESCRIPTION: &'static str = $fmt;
+        static ITEMS: [$crate::format::Item<'static>;
+            $crate::format::__description_len(DESCRIPTION)] =
+            $crate::format::__description_items(DESCRIPTION);
+        &ITEMS as &'static [$crate::format::Item<'static>]
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::next_item;
+    use core::mem::ManuallyDrop;
+    use format::{Fixed, Item, Numeric, Pad, StrftimeItems};
+
+    /// Collects the items of `fmt` with the constant parser, as the macro would.
+    fn collect(fmt: &str) -> Result<Vec<Item<'_>>, &'static str> {
+        let (mut items, mut pos, mut sub) = (Vec::new(), 0, 0);
+        while pos < fmt.len() {
+            let (item, next_pos, next_sub) = next_item(fmt, pos, sub)?;
+            items.push(ManuallyDrop::into_inner(item));
+            pos = next_pos;
+            sub = next_sub;
+        }
+        Ok(items)
+    }
+
+    #[test]
+    fn test_description_matches_strftime() {
+        let mut fmts = vec![
+            String::new(),
+            "%Y-%m-%dT%H:%M:%S%.3f%:z".to_string(),
+            "  \t%a, %d %b %Y  %T %z\n".to_string(),
+            "année %é".to_string(),
+            "%".to_string(),
+            "%-".to_string(),
+            "%.".to_string(),
+            "%.3".to_string(),
+            "%1".to_string(),
+            "%:".to_string(),
+            "100%%, 日本語 %F".to_string(),
+            "%c|%x|%X|%r|%v|%D|%R".to_string(),
+        ];
+        let specs = "ABCDFGHIMPRSTUVWXYZabcdefghjklmnpqrstuvwxyz+%:.#é 1";
+        for modifier in &["", "-", "0", "_", "#", ":", ".", ".3", ".4", "3", "4", "10", "12", "13"]
+        {
+            for spec in specs.chars() {
+                fmts.push(format!("<%{}{}>", modifier, spec));
+            }
+        }
+
+        for fmt in &fmts {
+            let expected = StrftimeItems::new(fmt).collect::<Vec<_>>();
+            match collect(fmt) {
+                Ok(items) => assert_eq!(items, expected, "for {:?}", fmt),
+                Err(_) => assert!(expected.contains(&Item::Error), "for {:?}", fmt),
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_description() {
+        static ITEMS: &[Item] = format_description!("%Y-%m-%d %-H%.6f");
+        assert_eq!(
+            ITEMS,
+            [
+                Item::Numeric(Numeric::Year, Pad::Zero),
+                Item::Literal("-"),
+                Item::Numeric(Numeric::Month, Pad::Zero),
+                Item::Literal("-"),
+                Item::Numeric(Numeric::Day, Pad::Zero),
+                Item::Space(" "),
+                Item::Numeric(Numeric::Hour, Pad::None),
+                Item::Fixed(Fixed::Nanosecond6),
+            ]
+        );
+
+        const FMT: &str = "%c %#z";
+        let items = format_description!(FMT);
+        assert_eq!(items, &StrftimeItems::new(FMT).collect::<Vec<_>>()[..]);
+        assert_eq!(format_description!(concat!("%F", "T", "%T")).len(), 11);
+        assert!(format_description!("").is_empty());
+    }
+
+    #[test]
+    fn test_description_errors() {
+        assert_eq!(collect("%Y-%Q"), Err("invalid format specifier"));
+        assert_eq!(collect("%Y-%"), Err("premature end of format string"));
+        assert_eq!(collect("%-F"), Err("padding modifier on a composite specifier"));
+        assert_eq!(collect("%_a"), Err("padding modifier on a non-numeric specifier"));
+    }
+}
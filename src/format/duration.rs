@@ -0,0 +1,1163 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Human-friendly parsing and formatting of `Duration`s, such as `1h 30m` or `1.5 hours`.
+//!
+//! [`parse_human`](./fn.parse_human.html) accepts a sequence of numbers with units,
+//! optionally fractional and separated by spaces or commas (`1h30m`, `2d 4h`, `500ms`,
+//! `1.5s`, `1 hour, 30 minutes`), or a clock form `H:MM` or `H:MM:SS[.fraction]`
+//! (`01:30:00`, `36:00`). Either form can be preceded by a sign (`-10m`).
+//!
+//! [`HumanFormat`](./struct.HumanFormat.html) formats a duration either as a sequence of
+//! units between a largest and a smallest unit (`1h 30m`), or as a single decimal number
+//! of the largest unit that fits (`1.5 hours`), rounded as configured.
+//!
+//! The ISO 8601 form written by `Duration`'s `Display` implementation (`P1DT2.345S`) is parsed
+//! by [`parse_iso8601`](./fn.parse_iso8601.html). Durations can also be formatted and parsed
+//! with `strftime`-like patterns such as
+//! `%H:%M:%S%.3f` (see [`DurationItems`](./struct.DurationItems.html)) with
+//! [`format_duration`](./fn.format_duration.html) and [`parse_duration`](./fn.parse_duration.html).
+//!
+//! # Example
+//!
+//! ```
+//! use chrono::format::duration::{parse_human, DurationUnit, HumanFormat};
+//! use chrono::Duration;
+//!
+//! assert_eq!(parse_human("1h30m"), Ok(Duration::minutes(90)));
+//! assert_eq!(parse_human("-1.5s"), Ok(Duration::milliseconds(-1500)));
+//! assert_eq!(parse_human("01:30:00"), Ok(Duration::minutes(90)));
+//!
+//! let d = Duration::seconds(5412);
+//! assert_eq!(HumanFormat::new().format(d).to_string(), "1h 30m 12s");
+//! let minutes = HumanFormat::new().with_smallest(DurationUnit::Minute);
+//! assert_eq!(minutes.format(d).to_string(), "1h 30m");
+//! let decimal = HumanFormat::new().with_decimals(1).with_long_names(true);
+//! assert_eq!(decimal.format(d).to_string(), "1.5 hours");
+//! ```
+
+use core::borrow::Borrow;
+use core::{cmp, fmt};
+use oldtime::Duration as OldDuration;
+
+pub use round::Rounding;
+
+use super::{Pad, ParseResult, BAD_FORMAT, INVALID, NOT_ENOUGH, OUT_OF_RANGE, TOO_LONG, TOO_SHORT};
+
+/// A unit of a `Duration` for human-friendly parsing and formatting.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum DurationUnit {
+    /// Nanoseconds (`ns`).
+    Nanosecond,
+    /// Microseconds (`us` or `µs`).
+    Microsecond,
+    /// Milliseconds (`ms`).
+    Millisecond,
+    /// Seconds (`s`).
+    Second,
+    /// Minutes (`m`).
+    Minute,
+    /// Hours (`h`).
+    Hour,
+    /// Days of 24 hours (`d`).
+    Day,
+    /// Weeks of 7 days (`w`).
+    Week,
+}
+
+impl DurationUnit {
+    const ALL: [DurationUnit; 8] = [
+        DurationUnit::Nanosecond,
+        DurationUnit::Microsecond,
+        DurationUnit::Millisecond,
+        DurationUnit::Second,
+        DurationUnit::Minute,
+        DurationUnit::Hour,
+        DurationUnit::Day,
+        DurationUnit::Week,
+    ];
+
+    /// Returns the number of nanoseconds in the unit.
+    fn nanos(&self) -> i128 {
+        match *self {
+            DurationUnit::Nanosecond => 1,
+            DurationUnit::Microsecond => 1_000,
+            DurationUnit::Millisecond => 1_000_000,
+            DurationUnit::Second => 1_000_000_000,
+            DurationUnit::Minute => 60_000_000_000,
+            DurationUnit::Hour => 3_600_000_000_000,
+            DurationUnit::Day => 86_400_000_000_000,
+            DurationUnit::Week => 604_800_000_000_000,
+        }
+    }
+
+    /// Returns the abbreviation, and the singular and plural names of the unit.
+    fn names(&self) -> (&'static str, &'static str, &'static str) {
+        match *self {
+            DurationUnit::Nanosecond => ("ns", "nanosecond", "nanoseconds"),
+            DurationUnit::Microsecond => ("us", "microsecond", "microseconds"),
+            DurationUnit::Millisecond => ("ms", "millisecond", "milliseconds"),
+            DurationUnit::Second => ("s", "second", "seconds"),
+            DurationUnit::Minute => ("m", "minute", "minutes"),
+            DurationUnit::Hour => ("h", "hour", "hours"),
+            DurationUnit::Day => ("d", "day", "days"),
+            DurationUnit::Week => ("w", "week", "weeks"),
+        }
+    }
+
+    /// Looks up a unit by any of its accepted names, ignoring ASCII case.
+    fn from_name(name: &str) -> Option<DurationUnit> {
+        static NAMES: &[(&str, DurationUnit)] = &[
+            ("ns", DurationUnit::Nanosecond),
+            ("nsec", DurationUnit::Nanosecond),
+            ("nanosecond", DurationUnit::Nanosecond),
+            ("nanoseconds", DurationUnit::Nanosecond),
+            ("us", DurationUnit::Microsecond),
+            ("µs", DurationUnit::Microsecond),
+            ("usec", DurationUnit::Microsecond),
+            ("microsecond", DurationUnit::Microsecond),
+            ("microseconds", DurationUnit::Microsecond),
+            ("ms", DurationUnit::Millisecond),
+            ("msec", DurationUnit::Millisecond),
+            ("millisecond", DurationUnit::Millisecond),
+            ("milliseconds", DurationUnit::Millisecond),
+            ("s", DurationUnit::Second),
+            ("sec", DurationUnit::Second),
+            ("secs", DurationUnit::Second),
+            ("second", DurationUnit::Second),
+            ("seconds", DurationUnit::Second),
+            ("m", DurationUnit::Minute),
+            ("min", DurationUnit::Minute),
+            ("mins", DurationUnit::Minute),
+            ("minute", DurationUnit::Minute),
+            ("minutes", DurationUnit::Minute),
+            ("h", DurationUnit::Hour),
+            ("hr", DurationUnit::Hour),
+            ("hrs", DurationUnit::Hour),
+            ("hour", DurationUnit::Hour),
+            ("hours", DurationUnit::Hour),
+            ("d", DurationUnit::Day),
+            ("day", DurationUnit::Day),
+            ("days", DurationUnit::Day),
+            ("w", DurationUnit::Week),
+            ("wk", DurationUnit::Week),
+            ("wks", DurationUnit::Week),
+            ("week", DurationUnit::Week),
+            ("weeks", DurationUnit::Week),
+        ];
+        NAMES.iter().find(|&&(n, _)| n.eq_ignore_ascii_case(name)).map(|&(_, unit)| unit)
+    }
+}
+
+/// Returns the total number of nanoseconds in a duration.
+pub(crate) fn duration_to_nanos(duration: &OldDuration) -> i128 {
+    let secs = duration.num_seconds();
+    let subsec = (*duration - OldDuration::seconds(secs)).num_nanoseconds().unwrap_or(0);
+    i128::from(secs) * 1_000_000_000 + i128::from(subsec)
+}
+
+/// Makes a duration from a total number of nanoseconds, or returns `None` when out of range.
+pub(crate) fn duration_from_nanos(nanos: i128) -> Option<OldDuration> {
+    if nanos < duration_to_nanos(&OldDuration::min_value())
+        || nanos > duration_to_nanos(&OldDuration::max_value())
+    {
+        return None;
+    }
+    let secs = (nanos / 1_000_000_000) as i64;
+    let subsec = (nanos % 1_000_000_000) as i64;
+    Some(OldDuration::seconds(secs) + OldDuration::nanoseconds(subsec))
+}
+
+/// Parses a human-friendly duration such as `1h30m`, `2d 4h`, `1.5s`, `-10m` or `01:30:00`.
+///
+/// See the [module documentation](./index.html) for the accepted forms.
+/// Fractions finer than a nanosecond are truncated.
+pub fn parse_human(s: &str) -> ParseResult<OldDuration> {
+    let s = s.trim();
+    let (negative, s) = match s.as_bytes().first() {
+        Some(&b'-') => (true, &s[1..]),
+        Some(&b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    if s.is_empty() {
+        return Err(TOO_SHORT);
+    }
+    let nanos = if s.contains(':') { parse_clock(s)? } else { parse_units(s)? };
+    duration_from_nanos(if negative { -nanos } else { nanos }).ok_or(OUT_OF_RANGE)
+}
+
+/// Parses an ISO 8601 duration such as `P1DT2.345S` or `-PT1H30M`,
+/// as written by the `Display` implementation of `Duration`.
+///
+/// Weeks, days, hours, minutes and seconds are accepted in this order, each of them optionally
+/// fractional. Years and months are rejected because their lengths vary.
+///
+/// # Example
+///
+/// ```
+/// use chrono::format::duration::parse_iso8601;
+/// use chrono::Duration;
+///
+/// assert_eq!(parse_iso8601("P1DT2.345S"), Ok(Duration::days(1) + Duration::milliseconds(2345)));
+/// assert_eq!(parse_iso8601("-PT1H30M"), Ok(Duration::minutes(-90)));
+/// assert_eq!(parse_iso8601(&Duration::weeks(-3).to_string()), Ok(Duration::weeks(-3)));
+/// ```
+pub fn parse_iso8601(s: &str) -> ParseResult<OldDuration> {
+    let (negative, mut s) = match s.as_bytes().first() {
+        Some(&b'-') => (true, &s[1..]),
+        Some(&b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    s = match s.as_bytes().first() {
+        Some(&b'P') => &s[1..],
+        Some(_) => return Err(INVALID),
+        None => return Err(TOO_SHORT),
+    };
+
+    // designators in their order, with the time designator `T` in between
+    const DESIGNATORS: [(u8, DurationUnit); 5] = [
+        (b'W', DurationUnit::Week),
+        (b'D', DurationUnit::Day),
+        (b'H', DurationUnit::Hour),
+        (b'M', DurationUnit::Minute),
+        (b'S', DurationUnit::Second),
+    ];
+    let mut next = 0;
+    let mut time = false;
+    let mut total: i128 = 0;
+    while !s.is_empty() {
+        if !time && s.starts_with('T') {
+            time = true;
+            next = 2;
+            s = &s[1..];
+            if s.is_empty() {
+                return Err(TOO_SHORT);
+            }
+        }
+        let (rest, int, frac) = number(s)?;
+        let designator = match rest.as_bytes().first() {
+            Some(&b) => b,
+            None => return Err(TOO_SHORT),
+        };
+        let pos = DESIGNATORS[next..].iter().position(|&(d, _)| d == designator);
+        let index = match pos {
+            Some(pos) if (next + pos >= 2) == time => next + pos,
+            _ => return Err(INVALID),
+        };
+        let nanos = scale(int, frac, DESIGNATORS[index].1.nanos()).ok_or(OUT_OF_RANGE)?;
+        total = total.checked_add(nanos).ok_or(OUT_OF_RANGE)?;
+        next = index + 1;
+        s = &rest[1..];
+    }
+    if next == 0 {
+        return Err(TOO_SHORT);
+    }
+    duration_from_nanos(if negative { -total } else { total }).ok_or(OUT_OF_RANGE)
+}
+
+/// Parses a sequence of numbers with units.
+fn parse_units(mut s: &str) -> ParseResult<i128> {
+    let mut total: i128 = 0;
+    while !s.is_empty() {
+        let (rest, int, frac) = number(s)?;
+        let rest = rest.trim_start_matches(char::is_whitespace);
+        let len = rest.find(|c: char| !c.is_alphabetic()).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(if rest.is_empty() { TOO_SHORT } else { INVALID });
+        }
+        let unit = DurationUnit::from_name(&rest[..len]).ok_or(INVALID)?;
+        let nanos = scale(int, frac, unit.nanos()).ok_or(OUT_OF_RANGE)?;
+        total = total.checked_add(nanos).ok_or(OUT_OF_RANGE)?;
+        s = rest[len..].trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+    Ok(total)
+}
+
+/// Parses `H:MM` or `H:MM:SS[.fraction]`, where the hours are not limited.
+fn parse_clock(s: &str) -> ParseResult<i128> {
+    let mut parts = s.split(':');
+    let hours = integer(parts.next().unwrap_or(""))?;
+    let minutes = integer(parts.next().unwrap_or(""))?;
+    let (seconds, frac) = match parts.next() {
+        Some(part) => match number(part)? {
+            ("", seconds, frac) => (seconds, frac),
+            _ => return Err(INVALID),
+        },
+        None => (0, ""),
+    };
+    if parts.next().is_some() {
+        return Err(INVALID);
+    }
+    if minutes >= 60 || seconds >= 60 {
+        return Err(OUT_OF_RANGE);
+    }
+    let total = hours.checked_mul(60).and_then(|m| m.checked_add(minutes));
+    let total = total.and_then(|m| m.checked_mul(60)).and_then(|s| s.checked_add(seconds));
+    total.and_then(|secs| scale(secs, frac, 1_000_000_000)).ok_or(OUT_OF_RANGE)
+}
+
+/// Parses a number which consists of digits only.
+fn integer(s: &str) -> ParseResult<i128> {
+    match number(s)? {
+        ("", value, "") if !s.starts_with('.') => Ok(value),
+        _ => Err(INVALID),
+    }
+}
+
+/// Parses an unsigned decimal number at the start of `s`,
+/// returning the remaining input, the integral part and the digits of the fractional part.
+fn number(s: &str) -> ParseResult<(&str, i128, &str)> {
+    let int_len = s.bytes().take_while(|b| b.is_ascii_digit()).count();
+    let mut value: i128 = 0;
+    for b in s[..int_len].bytes() {
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add(i128::from(b - b'0')))
+            .ok_or(OUT_OF_RANGE)?;
+    }
+    let rest = &s[int_len..];
+    let (rest, frac) = if rest.starts_with('.') {
+        let rest = &rest[1..];
+        let frac_len = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+        (&rest[frac_len..], &rest[..frac_len])
+    } else {
+        (rest, "")
+    };
+    match (int_len, frac.len()) {
+        (0, 0) if s.is_empty() => Err(TOO_SHORT),
+        (0, 0) => Err(INVALID),
+        _ => Ok((rest, value, frac)),
+    }
+}
+
+/// Returns `int.frac` units in nanoseconds, truncating fractions of a nanosecond.
+fn scale(int: i128, frac: &str, unit: i128) -> Option<i128> {
+    let mut numer: i128 = 0;
+    let mut denom: i128 = 1;
+    // more digits can't change the result with units of at most a week
+    for b in frac.bytes().take(18) {
+        numer = numer * 10 + i128::from(b - b'0');
+        denom *= 10;
+    }
+    int.checked_mul(unit)?.checked_add(numer * unit / denom)
+}
+
+/// A human-friendly format of `Duration`s.
+///
+/// By default, durations are formatted with abbreviated units from days to nanoseconds,
+/// leaving out zero units: `2d 4h`, `1m 30s`, `1s 500ms`, `-10m` or `0s`.
+///
+/// See the [module documentation](./index.html) for an example.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct HumanFormat {
+    largest: DurationUnit,
+    smallest: DurationUnit,
+    rounding: Rounding,
+    decimals: Option<u8>,
+    long_names: bool,
+}
+
+impl HumanFormat {
+    /// Makes a new format with the default settings.
+    pub fn new() -> HumanFormat {
+        HumanFormat::default()
+    }
+
+    /// Sets the largest unit used. Larger amounts are expressed in this unit, e.g. `36h`.
+    pub fn with_largest(mut self, unit: DurationUnit) -> HumanFormat {
+        self.largest = unit;
+        self
+    }
+
+    /// Sets the smallest unit used. The duration is rounded to a multiple of this unit,
+    /// or to the decimals of it with `with_decimals`.
+    pub fn with_smallest(mut self, unit: DurationUnit) -> HumanFormat {
+        self.smallest = unit;
+        self
+    }
+
+    /// Sets how the duration is rounded; the default is to the nearest value.
+    pub fn with_rounding(mut self, rounding: Rounding) -> HumanFormat {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Formats the duration as a single decimal number, with at most given number of
+    /// fractional digits, in the largest unit not exceeding the duration (e.g. `1.5h`).
+    ///
+    /// At most 9 digits are written, as a duration has nanosecond precision.
+    pub fn with_decimals(mut self, decimals: u8) -> HumanFormat {
+        self.decimals = Some(cmp::min(decimals, 9));
+        self
+    }
+
+    /// Sets whether to use full unit names like `1 hour 30 minutes` instead of `1h 30m`.
+    pub fn with_long_names(mut self, long_names: bool) -> HumanFormat {
+        self.long_names = long_names;
+        self
+    }
+
+    /// Returns an object that displays the duration in this format.
+    pub fn format(&self, duration: OldDuration) -> HumanDuration {
+        HumanDuration { duration: duration, format: *self }
+    }
+
+    fn write_unit(&self, f: &mut fmt::Formatter, unit: DurationUnit, one: bool) -> fmt::Result {
+        let (abbr, singular, plural) = unit.names();
+        match (self.long_names, one) {
+            (false, _) => f.write_str(abbr),
+            (true, true) => write!(f, " {}", singular),
+            (true, false) => write!(f, " {}", plural),
+        }
+    }
+}
+
+impl Default for HumanFormat {
+    fn default() -> HumanFormat {
+        HumanFormat {
+            largest: DurationUnit::Day,
+            smallest: DurationUnit::Nanosecond,
+            rounding: Rounding::Nearest,
+            decimals: None,
+            long_names: false,
+        }
+    }
+}
+
+/// A duration displayed in a [`HumanFormat`](./struct.HumanFormat.html).
+#[derive(Copy, Clone, Debug)]
+pub struct HumanDuration {
+    duration: OldDuration,
+    format: HumanFormat,
+}
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let format = &self.format;
+        let nanos = duration_to_nanos(&self.duration);
+        let smallest = format.smallest;
+        let largest = if format.largest < smallest { smallest } else { format.largest };
+        let units = &DurationUnit::ALL[smallest as usize..largest as usize + 1];
+
+        if let Some(decimals) = format.decimals {
+            let unit = units.iter().rev().find(|u| nanos.abs() >= u.nanos()).unwrap_or(&smallest);
+            // `with_decimals` allows at most 9 digits, so this can't overflow
+            let pow = 10i128.pow(u32::from(decimals));
+            let value = format.rounding.div(nanos.abs() * pow, unit.nanos());
+            if nanos < 0 && value != 0 {
+                f.write_str("-")?;
+            }
+            write!(f, "{}", value / pow)?;
+            let mut frac = value % pow;
+            if frac != 0 {
+                let mut digits = decimals as usize;
+                while frac % 10 == 0 {
+                    frac /= 10;
+                    digits -= 1;
+                }
+                write!(f, ".{:0width$}", frac, width = digits)?;
+            }
+            return format.write_unit(f, *unit, value == pow);
+        }
+
+        let mut rest = format.rounding.div(nanos.abs(), smallest.nanos()) * smallest.nanos();
+        if rest == 0 {
+            write!(f, "0")?;
+            return format.write_unit(f, smallest, false);
+        }
+        if nanos < 0 {
+            f.write_str("-")?;
+        }
+        let mut first = true;
+        for unit in units.iter().rev() {
+            let value = rest / unit.nanos();
+            rest %= unit.nanos();
+            if value != 0 {
+                if !first {
+                    f.write_str(" ")?;
+                }
+                first = false;
+                write!(f, "{}", value)?;
+                format.write_unit(f, *unit, value == 1)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A single item of a duration pattern. This is used for both formatting and parsing.
+///
+/// See [`DurationItems`](./struct.DurationItems.html) for the pattern syntax.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DurationItem<'a> {
+    /// A literally printed and parsed text.
+    Literal(&'a str),
+    /// Whitespace. Prints literally but reads zero or more whitespace.
+    Space(&'a str),
+    /// A number of units. This is the remainder after the next larger unit present in the
+    /// same items if any, so hours are 0 to 23 with days, and the total number of units
+    /// otherwise. Padded to two digits when formatting (three below a second).
+    Field(DurationUnit, Pad),
+    /// The fraction of a second, with given number of digits or the minimal number of
+    /// 0, 3, 6 or 9 digits when `None`, optionally preceded by a dot.
+    Fraction {
+        /// The number of digits (at most 9), or `None` for as many as needed.
+        digits: Option<u8>,
+        /// Whether the digits are preceded by a dot.
+        dot: bool,
+    },
+    /// The sign, `+` or `-`. Without it, negative durations are prefixed with `-`.
+    Sign,
+    /// Issues a formatting error. Used to signal an invalid pattern.
+    Error,
+}
+
+/// Parsing iterator for duration patterns.
+///
+/// The following specifiers are available both to formatting and parsing:
+///
+/// | Spec.  | Example | Description                                                    |
+/// |--------|---------|----------------------------------------------------------------|
+/// | `%W`   | `2`     | Weeks.                                                         |
+/// | `%D`   | `3`     | Days, 0 to 6 when weeks are present.                           |
+/// | `%H`   | `04`    | Hours, 00 to 23 when days or weeks are present.                |
+/// | `%M`   | `05`    | Minutes, 00 to 59 when hours or larger units are present.      |
+/// | `%S`   | `06`    | Seconds, 00 to 59 when minutes or larger units are present.    |
+/// | `%f`   | `026490000` | The fraction of a second in nanoseconds.                   |
+/// | `%.f`  | `.026490` | The fraction of a second with 0, 3, 6 or 9 digits, and a dot. |
+/// | `%.3f` | `.026`  | The fraction of a second with a fixed number (1 to 9) of digits and a dot. |
+/// | `%3f`  | `026`   | The same as `%.3f` but without the dot.                       |
+/// | `%+`   | `-`     | The sign, `+` or `-`.                                          |
+/// | `%n`   |         | Newline character.                                             |
+/// | `%t`   |         | Tab character.                                                 |
+/// | `%%`   | `%`     | A literal percent sign.                                        |
+///
+/// The largest unit in a pattern holds the total amount of it, e.g. `%H:%M:%S` shows
+/// `100:00:00` and `%M` shows the total number of minutes, and fractions of smaller units
+/// which are not in the pattern are truncated. `%-H`, `%_H` and `%0H` set the padding of
+/// a field to none, spaces and zeroes respectively, which only matters to formatting.
+///
+/// When parsing, the largest unit reads all following digits while smaller units read at most
+/// as many digits as their limit has. A negative duration is prefixed with `-` unless the
+/// pattern contains `%+`, and parsing accepts such a prefix in the same way.
+///
+/// # Example
+///
+/// ```
+/// use chrono::format::duration::{format_duration, parse_duration, DurationItems};
+/// use chrono::Duration;
+///
+/// let d = Duration::hours(100) + Duration::milliseconds(1500);
+/// assert_eq!(format_duration(d, DurationItems::new("%H:%M:%S%.3f")).to_string(), "100:00:01.500");
+/// assert_eq!(format_duration(d, DurationItems::new("%D days %H:%M")).to_string(), "4 days 04:00");
+/// assert_eq!(format_duration(-d, DurationItems::new("%M min")).to_string(), "-6000 min");
+///
+/// assert_eq!(parse_duration("100:00:01.500", DurationItems::new("%H:%M:%S%.f")), Ok(d));
+/// assert_eq!(parse_duration("+4 days 04:00", DurationItems::new("%+%D days %H:%M")),
+///            Ok(Duration::hours(100)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DurationItems<'a> {
+    remainder: &'a str,
+}
+
+impl<'a> DurationItems<'a> {
+    /// Creates a new parsing iterator from a duration pattern.
+    pub fn new(s: &'a str) -> DurationItems<'a> {
+        DurationItems { remainder: s }
+    }
+}
+
+impl<'a> Iterator for DurationItems<'a> {
+    type Item = DurationItem<'a>;
+
+    fn next(&mut self) -> Option<DurationItem<'a>> {
+        match self.remainder.chars().next() {
+            // we are done
+            None => None,
+
+            // the next item is a specifier
+            Some('%') => {
+                self.remainder = &self.remainder[1..];
+
+                macro_rules! next {
+                    () => {
+                        match self.remainder.chars().next() {
+                            Some(x) => {
+                                self.remainder = &self.remainder[x.len_utf8()..];
+                                x
+                            }
+                            None => return Some(DurationItem::Error), // premature end of string
+                        }
+                    };
+                }
+
+                let spec = next!();
+                let pad_override = match spec {
+                    '-' => Some(Pad::None),
+                    '0' => Some(Pad::Zero),
+                    '_' => Some(Pad::Space),
+                    _ => None,
+                };
+                let spec = if pad_override.is_some() { next!() } else { spec };
+
+                let item = match spec {
+                    'W' => DurationItem::Field(DurationUnit::Week, Pad::None),
+                    'D' => DurationItem::Field(DurationUnit::Day, Pad::None),
+                    'H' => DurationItem::Field(DurationUnit::Hour, Pad::Zero),
+                    'M' => DurationItem::Field(DurationUnit::Minute, Pad::Zero),
+                    'S' => DurationItem::Field(DurationUnit::Second, Pad::Zero),
+                    'f' => DurationItem::Fraction { digits: Some(9), dot: false },
+                    '.' => match next!() {
+                        'f' => DurationItem::Fraction { digits: None, dot: true },
+                        c @ '1'..='9' => match next!() {
+                            'f' => {
+                                DurationItem::Fraction { digits: Some(c as u8 - b'0'), dot: true }
+                            }
+                            _ => DurationItem::Error,
+                        },
+                        _ => DurationItem::Error,
+                    },
+                    c @ '1'..='9' => match next!() {
+                        'f' => DurationItem::Fraction { digits: Some(c as u8 - b'0'), dot: false },
+                        _ => DurationItem::Error,
+                    },
+                    '+' => DurationItem::Sign,
+                    'n' => DurationItem::Space("\n"),
+                    't' => DurationItem::Space("\t"),
+                    '%' => DurationItem::Literal("%"),
+                    _ => DurationItem::Error, // no such specifier
+                };
+
+                // adjust `item` if we have any padding modifier
+                match (item, pad_override) {
+                    (DurationItem::Field(unit, _), Some(pad)) => {
+                        Some(DurationItem::Field(unit, pad))
+                    }
+                    (_, Some(_)) => Some(DurationItem::Error), // non-numeric item
+                    (item, None) => Some(item),
+                }
+            }
+
+            // the next item is space
+            Some(c) if c.is_whitespace() => {
+                let nextspec = self
+                    .remainder
+                    .find(|c: char| !c.is_whitespace())
+                    .unwrap_or(self.remainder.len());
+                let item = DurationItem::Space(&self.remainder[..nextspec]);
+                self.remainder = &self.remainder[nextspec..];
+                Some(item)
+            }
+
+            // the next item is literal
+            _ => {
+                let nextspec = self
+                    .remainder
+                    .find(|c: char| c.is_whitespace() || c == '%')
+                    .unwrap_or(self.remainder.len());
+                let item = DurationItem::Literal(&self.remainder[..nextspec]);
+                self.remainder = &self.remainder[nextspec..];
+                Some(item)
+            }
+        }
+    }
+}
+
+/// Returns a bit set of the units of the fields in `items`, and whether they contain a sign.
+fn scan_items<'a, I, B>(items: I) -> (u8, bool)
+where
+    I: Iterator<Item = B>,
+    B: Borrow<DurationItem<'a>>,
+{
+    let mut units = 0;
+    let mut sign = false;
+    for item in items {
+        match *item.borrow() {
+            DurationItem::Field(unit, _) => units |= 1 << unit as u8,
+            DurationItem::Sign => sign = true,
+            _ => {}
+        }
+    }
+    (units, sign)
+}
+
+/// Returns the smallest unit in `units` which is larger than `unit`.
+fn next_larger(unit: DurationUnit, units: u8) -> Option<DurationUnit> {
+    DurationUnit::ALL[unit as usize + 1..].iter().find(|u| units & (1 << **u as u8) != 0).cloned()
+}
+
+/// Formats a duration with given items, returning an object which can be displayed.
+///
+/// See [`DurationItems`](./struct.DurationItems.html) for an example.
+pub fn format_duration<'a, I, B>(duration: OldDuration, items: I) -> DelayedDurationFormat<I>
+where
+    I: Iterator<Item = B> + Clone,
+    B: Borrow<DurationItem<'a>>,
+{
+    DelayedDurationFormat { duration: duration, items: items }
+}
+
+/// A *temporary* object which can be used as an argument to `format!` or others.
+/// This is constructed via [`format_duration`](./fn.format_duration.html).
+#[derive(Debug)]
+pub struct DelayedDurationFormat<I> {
+    duration: OldDuration,
+    items: I,
+}
+
+impl<'a, I, B> fmt::Display for DelayedDurationFormat<I>
+where
+    I: Iterator<Item = B> + Clone,
+    B: Borrow<DurationItem<'a>>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nanos = duration_to_nanos(&self.duration);
+        let (abs, negative) = (nanos.abs(), nanos < 0);
+        let (units, sign) = scan_items(self.items.clone());
+        if negative && !sign {
+            f.write_str("-")?;
+        }
+        for item in self.items.clone() {
+            match *item.borrow() {
+                DurationItem::Literal(s) | DurationItem::Space(s) => f.write_str(s)?,
+                DurationItem::Field(unit, pad) => {
+                    let value = match next_larger(unit, units) {
+                        Some(larger) => abs % larger.nanos() / unit.nanos(),
+                        None => abs / unit.nanos(),
+                    };
+                    let width = if unit < DurationUnit::Second { 3 } else { 2 };
+                    match pad {
+                        Pad::None => write!(f, "{}", value)?,
+                        Pad::Zero => write!(f, "{:01$}", value, width)?,
+                        Pad::Space => write!(f, "{:1$}", value, width)?,
+                    }
+                }
+                DurationItem::Fraction { digits, dot } => {
+                    let frac = abs % 1_000_000_000;
+                    let digits = match digits {
+                        Some(digits) => cmp::min(digits, 9),
+                        None if frac == 0 => 0,
+                        None if frac % 1_000_000 == 0 => 3,
+                        None if frac % 1_000 == 0 => 6,
+                        None => 9,
+                    };
+                    if digits > 0 {
+                        if dot {
+                            f.write_str(".")?;
+                        }
+                        let value = frac / 10i128.pow(9 - u32::from(digits));
+                        write!(f, "{:01$}", value, digits as usize)?;
+                    }
+                }
+                DurationItem::Sign => f.write_str(if negative { "-" } else { "+" })?,
+                DurationItem::Error => return Err(fmt::Error),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses a duration with given items.
+///
+/// Fields which are not the largest unit in the items must be less than the next larger
+/// unit, and fractions of a second finer than a nanosecond are truncated.
+/// See [`DurationItems`](./struct.DurationItems.html) for an example.
+pub fn parse_duration<'a, I, B>(mut s: &str, items: I) -> ParseResult<OldDuration>
+where
+    I: Iterator<Item = B> + Clone,
+    B: Borrow<DurationItem<'a>>,
+{
+    let (units, sign) = scan_items(items.clone());
+    let mut negative = false;
+    if !sign && s.starts_with('-') {
+        negative = true;
+        s = &s[1..];
+    }
+
+    let mut total: i128 = 0;
+    let mut parsed = false;
+    for item in items {
+        match *item.borrow() {
+            DurationItem::Literal(prefix) => {
+                if s.len() < prefix.len() {
+                    return Err(TOO_SHORT);
+                }
+                if !s.starts_with(prefix) {
+                    return Err(INVALID);
+                }
+                s = &s[prefix.len()..];
+            }
+            DurationItem::Space(_) => s = s.trim_start(),
+            DurationItem::Field(unit, _) => {
+                s = s.trim_start();
+                let larger = next_larger(unit, units).map(|larger| larger.nanos() / unit.nanos());
+                // all digits for the largest unit, otherwise only as many as the limit has
+                let max_len = match larger {
+                    Some(limit) => num_digits(limit - 1),
+                    None => s.len(),
+                };
+                let len = s.bytes().take(max_len).take_while(|b| b.is_ascii_digit()).count();
+                if len == 0 {
+                    return Err(if s.is_empty() { TOO_SHORT } else { INVALID });
+                }
+                let value = integer(&s[..len])?;
+                if let Some(limit) = larger {
+                    if value >= limit {
+                        return Err(OUT_OF_RANGE);
+                    }
+                }
+                let nanos = value.checked_mul(unit.nanos()).ok_or(OUT_OF_RANGE)?;
+                total = total.checked_add(nanos).ok_or(OUT_OF_RANGE)?;
+                s = &s[len..];
+                parsed = true;
+            }
+            DurationItem::Fraction { digits, dot } => {
+                let mut rest = s;
+                if dot {
+                    match (s.starts_with('.'), digits) {
+                        (true, _) => rest = &s[1..],
+                        (false, None) => continue,
+                        (false, Some(_)) => {
+                            return Err(if s.is_empty() { TOO_SHORT } else { INVALID })
+                        }
+                    }
+                }
+                let len = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+                let len = match digits {
+                    Some(digits) if len < digits as usize => {
+                        return Err(if len == rest.len() { TOO_SHORT } else { INVALID })
+                    }
+                    Some(digits) => digits as usize,
+                    None if len == 0 => {
+                        return Err(if rest.is_empty() { TOO_SHORT } else { INVALID })
+                    }
+                    None => len,
+                };
+                total = total
+                    .checked_add(scale(0, &rest[..len], 1_000_000_000).unwrap_or(0))
+                    .ok_or(OUT_OF_RANGE)?;
+                s = &rest[len..];
+                parsed = true;
+            }
+            DurationItem::Sign => {
+                match s.as_bytes().first() {
+                    Some(&b'+') => {}
+                    Some(&b'-') => negative = true,
+                    Some(_) => return Err(INVALID),
+                    None => return Err(TOO_SHORT),
+                }
+                s = &s[1..];
+            }
+            DurationItem::Error => return Err(BAD_FORMAT),
+        }
+    }
+    if !s.is_empty() {
+        return Err(TOO_LONG);
+    }
+    if !parsed {
+        return Err(NOT_ENOUGH);
+    }
+    duration_from_nanos(if negative { -total } else { total }).ok_or(OUT_OF_RANGE)
+}
+
+/// Returns the number of decimal digits of a non-negative number.
+fn num_digits(mut value: i128) -> usize {
+    let mut digits = 1;
+    while value >= 10 {
+        value /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        format_duration, parse_duration, parse_human, parse_iso8601, DurationItem, DurationItems,
+        DurationUnit, HumanFormat, Rounding,
+    };
+    use format::Pad;
+    use format::{BAD_FORMAT, INVALID, NOT_ENOUGH, OUT_OF_RANGE, TOO_LONG, TOO_SHORT};
+    use oldtime::Duration;
+
+    #[test]
+    fn test_parse_human() {
+        assert_eq!(parse_human("1h30m"), Ok(Duration::minutes(90)));
+        assert_eq!(parse_human("2d 4h"), Ok(Duration::hours(52)));
+        assert_eq!(parse_human("500ms"), Ok(Duration::milliseconds(500)));
+        assert_eq!(parse_human("1.5s"), Ok(Duration::milliseconds(1500)));
+        assert_eq!(parse_human("-10m"), Ok(Duration::minutes(-10)));
+        assert_eq!(parse_human("90s"), Ok(Duration::seconds(90)));
+        assert_eq!(parse_human(" +.25 h "), Ok(Duration::minutes(15)));
+        assert_eq!(parse_human("1 Hour, 2 minutes 3 SECONDS"), Ok(Duration::seconds(3723)));
+        assert_eq!(parse_human("1w 1wk 1 week"), Ok(Duration::weeks(3)));
+        assert_eq!(parse_human("7µs 3us 5ns"), Ok(Duration::nanoseconds(10_005)));
+        assert_eq!(parse_human("0.0000000019s"), Ok(Duration::nanoseconds(1)));
+        assert_eq!(parse_human("1m1m"), Ok(Duration::minutes(2)));
+
+        assert_eq!(parse_human("01:30:00"), Ok(Duration::minutes(90)));
+        assert_eq!(parse_human("36:00"), Ok(Duration::hours(36)));
+        assert_eq!(parse_human("-0:00:01.5"), Ok(Duration::milliseconds(-1500)));
+
+        assert_eq!(parse_human(""), Err(TOO_SHORT));
+        assert_eq!(parse_human("-"), Err(TOO_SHORT));
+        assert_eq!(parse_human("10"), Err(TOO_SHORT));
+        assert_eq!(parse_human("10 apples"), Err(INVALID));
+        assert_eq!(parse_human("h"), Err(INVALID));
+        assert_eq!(parse_human("1h-5m"), Err(INVALID));
+        assert_eq!(parse_human("- 5m"), Err(INVALID));
+        assert_eq!(parse_human("1:2:3:4"), Err(INVALID));
+        assert_eq!(parse_human("1:.5"), Err(INVALID));
+        assert_eq!(parse_human("1:30m"), Err(INVALID));
+        assert_eq!(parse_human("1:60"), Err(OUT_OF_RANGE));
+        assert_eq!(parse_human("200000000000000d"), Err(OUT_OF_RANGE));
+        assert_eq!(parse_human("999999999999999999999999999999999999999s"), Err(OUT_OF_RANGE));
+    }
+
+    #[test]
+    fn test_parse_iso8601() {
+        assert_eq!(parse_iso8601("PT0S"), Ok(Duration::zero()));
+        assert_eq!(parse_iso8601("P42D"), Ok(Duration::days(42)));
+        assert_eq!(parse_iso8601("-P1DT1S"), Ok(Duration::seconds(-86401)));
+        assert_eq!(parse_iso8601("PT0.000000001S"), Ok(Duration::nanoseconds(1)));
+        assert_eq!(parse_iso8601("P2W3DT4H5M6.5S"), Ok(Duration::milliseconds(1_483_506_500)));
+        assert_eq!(parse_iso8601("+PT1.5H"), Ok(Duration::minutes(90)));
+        assert_eq!(parse_iso8601("P0.5W"), Ok(Duration::hours(84)));
+
+        assert_eq!(parse_iso8601(""), Err(TOO_SHORT));
+        assert_eq!(parse_iso8601("P"), Err(TOO_SHORT));
+        assert_eq!(parse_iso8601("PT"), Err(TOO_SHORT));
+        assert_eq!(parse_iso8601("P1"), Err(TOO_SHORT));
+        assert_eq!(parse_iso8601("1D"), Err(INVALID));
+        assert_eq!(parse_iso8601("P1Y"), Err(INVALID));
+        assert_eq!(parse_iso8601("P1M"), Err(INVALID));
+        assert_eq!(parse_iso8601("P1H"), Err(INVALID));
+        assert_eq!(parse_iso8601("PT1D"), Err(INVALID));
+        assert_eq!(parse_iso8601("PT1S1M"), Err(INVALID));
+        assert_eq!(parse_iso8601("P1D1D"), Err(INVALID));
+        assert_eq!(parse_iso8601("PT1M T1S"), Err(INVALID));
+        assert_eq!(parse_iso8601("P999999999999999W"), Err(OUT_OF_RANGE));
+
+        let durations = [Duration::nanoseconds(-1), Duration::max_value(), Duration::min_value()];
+        for &d in &durations {
+            assert_eq!(parse_iso8601(&d.to_string()), Ok(d));
+        }
+    }
+
+    #[test]
+    fn test_human_format() {
+        let fmt = |format: HumanFormat, d: Duration| format.format(d).to_string();
+        let default = HumanFormat::new();
+        assert_eq!(fmt(default, Duration::zero()), "0ns");
+        assert_eq!(fmt(default, Duration::hours(52)), "2d 4h");
+        assert_eq!(fmt(default, Duration::milliseconds(-1500)), "-1s 500ms");
+        assert_eq!(fmt(default, Duration::nanoseconds(61_000_000_001)), "1m 1s 1ns");
+        assert_eq!(fmt(default, Duration::weeks(3)), "21d");
+        assert_eq!(fmt(default.with_largest(DurationUnit::Week), Duration::weeks(3)), "3w");
+        assert_eq!(fmt(default.with_largest(DurationUnit::Hour), Duration::hours(52)), "52h");
+
+        let minutes = default.with_smallest(DurationUnit::Minute);
+        assert_eq!(fmt(minutes, Duration::seconds(5429)), "1h 30m");
+        assert_eq!(fmt(minutes, Duration::seconds(5430)), "1h 31m");
+        assert_eq!(fmt(minutes, Duration::seconds(-5430)), "-1h 31m");
+        assert_eq!(
+            fmt(minutes.with_rounding(Rounding::TowardZero), Duration::seconds(5459)),
+            "1h 30m"
+        );
+        assert_eq!(
+            fmt(minutes.with_rounding(Rounding::AwayFromZero), Duration::seconds(5401)),
+            "1h 31m"
+        );
+        assert_eq!(fmt(minutes, Duration::seconds(29)), "0m");
+        assert_eq!(fmt(minutes.with_long_names(true), Duration::seconds(29)), "0 minutes");
+        assert_eq!(
+            fmt(default.with_long_names(true), Duration::seconds(3661)),
+            "1 hour 1 minute 1 second"
+        );
+        // the largest unit is never smaller than the smallest
+        assert_eq!(fmt(minutes.with_largest(DurationUnit::Second), Duration::seconds(5400)), "90m");
+
+        let decimal = default.with_decimals(2);
+        assert_eq!(fmt(decimal, Duration::seconds(5400)), "1.5h");
+        assert_eq!(fmt(decimal, Duration::seconds(-5412)), "-1.5h");
+        assert_eq!(fmt(decimal, Duration::milliseconds(1234)), "1.23s");
+        assert_eq!(fmt(decimal, Duration::nanoseconds(999)), "999ns");
+        assert_eq!(fmt(decimal, Duration::zero()), "0ns");
+        assert_eq!(fmt(decimal.with_smallest(DurationUnit::Hour), Duration::minutes(45)), "0.75h");
+        assert_eq!(fmt(default.with_decimals(0), Duration::minutes(-90)), "-2h");
+        let long = decimal.with_long_names(true);
+        assert_eq!(fmt(long, Duration::seconds(5400)), "1.5 hours");
+        assert_eq!(fmt(long, Duration::days(1)), "1 day");
+        assert_eq!(fmt(long, Duration::milliseconds(59_999)), "60 seconds");
+
+        // more decimals than the nanosecond precision are ignored
+        let seconds = default.with_largest(DurationUnit::Second);
+        assert_eq!(
+            fmt(seconds.with_decimals(40), Duration::nanoseconds(1_000_000_001)),
+            "1.000000001s"
+        );
+        assert_eq!(
+            fmt(default.with_decimals(15), Duration::max_value()),
+            "106751991167.300645914d"
+        );
+        assert_eq!(
+            fmt(default.with_decimals(255), Duration::min_value()),
+            "-106751991167.300645926d"
+        );
+    }
+
+    #[test]
+    fn test_human_round_trip() {
+        let durations = [
+            Duration::zero(),
+            Duration::nanoseconds(-1),
+            Duration::seconds(5412),
+            Duration::weeks(-7) + Duration::microseconds(3),
+            Duration::max_value(),
+            Duration::min_value(),
+        ];
+        for &d in &durations {
+            for &long in &[false, true] {
+                let format =
+                    HumanFormat::new().with_largest(DurationUnit::Week).with_long_names(long);
+                assert_eq!(parse_human(&format.format(d).to_string()), Ok(d));
+            }
+        }
+    }
+
+    #[test]
+    fn test_duration_items() {
+        fn parse_and_collect<'a>(s: &'a str) -> Vec<DurationItem<'a>> {
+            // map any error into `[DurationItem::Error]`. useful for easy testing.
+            let items = DurationItems::new(s);
+            let items =
+                items.map(|item| if item == DurationItem::Error { None } else { Some(item) });
+            items.collect::<Option<Vec<_>>>().unwrap_or(vec![DurationItem::Error])
+        }
+
+        assert_eq!(
+            parse_and_collect("%D days %-H:%_M"),
+            [
+                DurationItem::Field(DurationUnit::Day, Pad::None),
+                DurationItem::Space(" "),
+                DurationItem::Literal("days"),
+                DurationItem::Space(" "),
+                DurationItem::Field(DurationUnit::Hour, Pad::None),
+                DurationItem::Literal(":"),
+                DurationItem::Field(DurationUnit::Minute, Pad::Space),
+            ]
+        );
+        assert_eq!(
+            parse_and_collect("%+%0W%S%f%.f%.3f%6f%%"),
+            [
+                DurationItem::Sign,
+                DurationItem::Field(DurationUnit::Week, Pad::Zero),
+                DurationItem::Field(DurationUnit::Second, Pad::Zero),
+                DurationItem::Fraction { digits: Some(9), dot: false },
+                DurationItem::Fraction { digits: None, dot: true },
+                DurationItem::Fraction { digits: Some(3), dot: true },
+                DurationItem::Fraction { digits: Some(6), dot: false },
+                DurationItem::Literal("%"),
+            ]
+        );
+        assert_eq!(parse_and_collect("%"), [DurationItem::Error]);
+        assert_eq!(parse_and_collect("%Y"), [DurationItem::Error]);
+        assert_eq!(parse_and_collect("%-+"), [DurationItem::Error]);
+        assert_eq!(parse_and_collect("%.0f"), [DurationItem::Error]);
+        assert_eq!(parse_and_collect("%3S"), [DurationItem::Error]);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        let fmt =
+            |d: Duration, pattern| format_duration(d, DurationItems::new(pattern)).to_string();
+        let d = Duration::seconds(3 * 86400 + 4 * 3600 + 5 * 60 + 6) + Duration::milliseconds(78);
+        assert_eq!(fmt(d, "%H:%M:%S"), "76:05:06");
+        assert_eq!(fmt(d, "%D days %H:%M"), "3 days 04:05");
+        assert_eq!(fmt(d, "%W weeks %D days"), "0 weeks 3 days");
+        assert_eq!(fmt(d, "%M"), "4565");
+        assert_eq!(fmt(d, "%S%.3f"), "273906.078");
+        assert_eq!(fmt(d, "%S%.f|%f|%3f|%.1f"), "273906.078|078000000|078|.0");
+        assert_eq!(fmt(d, "%-M:%_S"), "4565: 6");
+        assert_eq!(fmt(-d, "%H:%M"), "-76:05");
+        assert_eq!(fmt(-d, "(%+) %Hh"), "(-) 76h");
+        assert_eq!(fmt(d, "%+%Hh"), "+76h");
+        assert_eq!(fmt(Duration::zero(), "%+%H:%M:%S%.f"), "+00:00:00");
+        assert_eq!(fmt(Duration::nanoseconds(-1500), "%S%.f"), "-00.000001500");
+        let nanos = DurationItem::Field(DurationUnit::Nanosecond, Pad::Zero);
+        let items = [
+            DurationItem::Field(DurationUnit::Second, Pad::None),
+            DurationItem::Literal("s"),
+            nanos,
+        ];
+        assert_eq!(format_duration(d, items.iter()).to_string(), "273906s78000000");
+
+        let mut s = String::new();
+        assert!(::core::fmt::write(
+            &mut s,
+            format_args!("{}", format_duration(d, DurationItems::new("%Q")))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        let parse = |s, pattern| parse_duration(s, DurationItems::new(pattern));
+        let d = Duration::seconds(3 * 86400 + 4 * 3600 + 5 * 60 + 6);
+        assert_eq!(parse("76:05:06", "%H:%M:%S"), Ok(d));
+        assert_eq!(parse("3 days  4:05:06", "%D days %H:%M:%S"), Ok(d));
+        assert_eq!(parse("-3 days 04:05:06", "%D days %H:%M:%S"), Ok(-d));
+        assert_eq!(parse("+273906", "%+%S"), Ok(d));
+        assert_eq!(parse("-273906", "%+%S"), Ok(-d));
+        assert_eq!(parse("3 0405", "%D %H%M"), Ok(d - Duration::seconds(6)));
+        assert_eq!(parse("4565", "%M"), Ok(Duration::minutes(4565)));
+        assert_eq!(parse("1:2:3.5", "%H:%M:%S%.f"), Ok(Duration::milliseconds(3_723_500)));
+        assert_eq!(parse("1:2:3", "%H:%M:%S%.f"), Ok(Duration::seconds(3723)));
+        assert_eq!(parse("3.123456789123", "%S%.f"), Ok(Duration::nanoseconds(3_123_456_789)));
+        assert_eq!(parse("3.12", "%S%.2f"), Ok(Duration::milliseconds(3120)));
+        assert_eq!(parse("3,120", "%S,%3f"), Ok(Duration::milliseconds(3120)));
+
+        assert_eq!(parse("", "%H"), Err(TOO_SHORT));
+        assert_eq!(parse("1", "%H:%M"), Err(TOO_SHORT));
+        assert_eq!(parse("1:", "%H:%M"), Err(TOO_SHORT));
+        assert_eq!(parse("1:x", "%H:%M"), Err(INVALID));
+        assert_eq!(parse("1-2", "%H:%M"), Err(INVALID));
+        assert_eq!(parse("1:2:3", "%H:%M"), Err(TOO_LONG));
+        assert_eq!(parse("1:60", "%H:%M"), Err(OUT_OF_RANGE));
+        assert_eq!(parse("1:123", "%H:%M"), Err(TOO_LONG));
+        assert_eq!(parse("3 24", "%D %H"), Err(OUT_OF_RANGE));
+        assert_eq!(parse("3", "%+%S"), Err(INVALID));
+        assert_eq!(parse("3.1", "%S%.2f"), Err(TOO_SHORT));
+        assert_eq!(parse("3", "%S%.3f"), Err(TOO_SHORT));
+        assert_eq!(parse("3.", "%S%.f"), Err(TOO_SHORT));
+        assert_eq!(parse("x", "x"), Err(NOT_ENOUGH));
+        assert_eq!(parse("1", "%H%Q"), Err(BAD_FORMAT));
+        assert_eq!(parse("99999999999999999999", "%W"), Err(OUT_OF_RANGE));
+    }
+
+    #[test]
+    fn test_duration_pattern_round_trip() {
+        let durations = [
+            Duration::zero(),
+            Duration::nanoseconds(-1),
+            Duration::seconds(5412) + Duration::microseconds(7),
+            Duration::weeks(-7) + Duration::milliseconds(3),
+            Duration::max_value(),
+            Duration::min_value(),
+        ];
+        for &d in &durations {
+            for pattern in &["%H:%M:%S%.f", "%+%W %D %H %M %S %f", "%D days %-H:%M:%S%.9f", "%S%.f"]
+            {
+                let s = format_duration(d, DurationItems::new(pattern)).to_string();
+                assert_eq!(
+                    parse_duration(&s, DurationItems::new(pattern)),
+                    Ok(d),
+                    "{} {}",
+                    pattern,
+                    s
+                );
+            }
+        }
+    }
+}
@@ -39,25 +39,30 @@
 use alloc::boxed::Box;
 #[cfg(feature = "alloc")]
 use alloc::string::{String, ToString};
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use core::borrow::Borrow;
-use core::fmt;
-use core::str::FromStr;
+use core::fmt::{self, Write};
+use core::str::{self, FromStr};
 #[cfg(any(feature = "std", test))]
 use std::error::Error;
 
-#[cfg(any(feature = "alloc", feature = "std", test))]
+use fiscal::FiscalCalendar;
 use naive::{NaiveDate, NaiveTime};
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use offset::{FixedOffset, Offset};
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use {Datelike, Timelike};
 use {Month, ParseMonthError, ParseWeekdayError, Weekday};
 
 #[cfg(feature = "unstable-locales")]
 pub(crate) mod locales;
 
+#[cfg(feature = "format-description")]
+#[doc(hidden)]
+pub use self::description::{
+    description_items as __description_items, description_len as __description_len,
+};
+#[cfg(any(feature = "alloc", feature = "std", test))]
+pub use self::owned::{FormatError, OwnedFormat};
 pub use self::parse::parse;
+pub(crate) use self::parse::parse_interval;
 pub use self::parsed::Parsed;
 pub use self::strftime::StrftimeItems;
 /// L10n locales.
@@ -65,13 +70,9 @@ pub use self::strftime::StrftimeItems;
 pub use pure_rust_locales::Locale;
 
 #[cfg(not(feature = "unstable-locales"))]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 struct Locale;
 
-/// An uninhabited type used for `InternalNumeric` and `InternalFixed` below.
-#[derive(Clone, PartialEq, Eq)]
-enum Void {}
-
 /// Padding characters for numeric items.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Pad {
@@ -152,28 +153,21 @@ pub enum Numeric {
 }
 
 /// An opaque type representing numeric item types for internal uses only.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InternalNumeric {
-    _dummy: Void,
+    val: InternalNumericInternal,
 }
 
-impl Clone for InternalNumeric {
-    fn clone(&self) -> Self {
-        match self._dummy {}
-    }
-}
-
-impl PartialEq for InternalNumeric {
-    fn eq(&self, _other: &InternalNumeric) -> bool {
-        match self._dummy {}
-    }
-}
-
-impl Eq for InternalNumeric {}
-
-impl fmt::Debug for InternalNumeric {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "<InternalNumeric>")
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum InternalNumericInternal {
+    /// The quarter of the year (FW=PW=1), from 1 to 4.
+    Quarter,
+    /// The fiscal year starting in given month, named by the year in which it ends (FW=4).
+    /// Parsing this is a `BadFormat` error.
+    FiscalYear(u8),
+    /// The quarter of the fiscal year starting in given month (FW=1), from 1 to 4.
+    /// Parsing this is a `BadFormat` error.
+    FiscalQuarter(u8),
 }
 
 /// Fixed-format item types.
@@ -298,6 +292,32 @@ pub enum Item<'a> {
     Error,
 }
 
+#[cfg(any(feature = "alloc", feature = "std", test))]
+impl<'a> Item<'a> {
+    /// Converts the item into one which does not borrow from the format string,
+    /// copying any borrowed text into an owned string.
+    ///
+    /// ```
+    /// use chrono::format::{Item, StrftimeItems};
+    ///
+    /// let fmt = String::from("%Y-%m-%d");
+    /// let items: Vec<Item<'static>> = StrftimeItems::new(&fmt).map(Item::into_owned).collect();
+    /// drop(fmt);
+    /// assert_eq!(items[1], Item::OwnedLiteral("-".into()));
+    /// ```
+    pub fn into_owned(self) -> Item<'static> {
+        match self {
+            Item::Literal(s) => Item::OwnedLiteral(s.into()),
+            Item::OwnedLiteral(s) => Item::OwnedLiteral(s),
+            Item::Space(s) => Item::OwnedSpace(s.into()),
+            Item::OwnedSpace(s) => Item::OwnedSpace(s),
+            Item::Numeric(spec, pad) => Item::Numeric(spec, pad),
+            Item::Fixed(spec) => Item::Fixed(spec),
+            Item::Error => Item::Error,
+        }
+    }
+}
+
 macro_rules! lit {
     ($x:expr) => {
         Item::Literal($x)
@@ -328,6 +348,16 @@ macro_rules! fix {
         Item::Fixed(Fixed::$x)
     };
 }
+macro_rules! internal_num {
+    ($x:expr) => {
+        Item::Numeric(Numeric::Internal(InternalNumeric { val: $x }), Pad::None)
+    };
+}
+macro_rules! internal_num0 {
+    ($x:expr) => {
+        Item::Numeric(Numeric::Internal(InternalNumeric { val: $x }), Pad::Zero)
+    };
+}
 macro_rules! internal_fix {
     ($x:ident) => {
         Item::Fixed(Fixed::Internal(InternalFixed { val: InternalInternal::$x }))
@@ -395,11 +425,11 @@ impl Error for ParseError {
     }
 }
 
-// to be used in this module and submodules
-const OUT_OF_RANGE: ParseError = ParseError(ParseErrorKind::OutOfRange);
-const IMPOSSIBLE: ParseError = ParseError(ParseErrorKind::Impossible);
-const NOT_ENOUGH: ParseError = ParseError(ParseErrorKind::NotEnough);
-const INVALID: ParseError = ParseError(ParseErrorKind::Invalid);
+// to be used in this module and submodules, and by other parsers in this crate
+pub(crate) const OUT_OF_RANGE: ParseError = ParseError(ParseErrorKind::OutOfRange);
+pub(crate) const IMPOSSIBLE: ParseError = ParseError(ParseErrorKind::Impossible);
+pub(crate) const NOT_ENOUGH: ParseError = ParseError(ParseErrorKind::NotEnough);
+pub(crate) const INVALID: ParseError = ParseError(ParseErrorKind::Invalid);
 const TOO_SHORT: ParseError = ParseError(ParseErrorKind::TooShort);
 const TOO_LONG: ParseError = ParseError(ParseErrorKind::TooLong);
 const BAD_FORMAT: ParseError = ParseError(ParseErrorKind::BadFormat);
@@ -413,17 +443,20 @@ pub fn format_item<'a>(
     off: Option<&(String, FixedOffset)>,
     item: &Item<'a>,
 ) -> fmt::Result {
+    let off = off.map(|&(ref name, off)| (Some(&name[..]), off));
     let mut result = String::new();
     format_inner(&mut result, date, time, off, item, None)?;
     w.pad(&result)
 }
 
-#[cfg(any(feature = "alloc", feature = "std", test))]
-fn format_inner<'a>(
-    result: &mut String,
+/// Writes a single formatting item into `result` without allocating.
+///
+/// The offset name is `None` if it could not be stored (see `OffsetName`).
+fn format_inner<'a, W: Write>(
+    result: &mut W,
     date: Option<&NaiveDate>,
     time: Option<&NaiveTime>,
-    off: Option<&(String, FixedOffset)>,
+    off: Option<(Option<&str>, FixedOffset)>,
     item: &Item<'a>,
     _locale: Option<Locale>,
 ) -> fmt::Result {
@@ -465,13 +498,12 @@ fn format_inner<'a>(
         )
     };
 
-    use core::fmt::Write;
     use div::{div_floor, mod_floor};
 
     match *item {
-        Item::Literal(s) | Item::Space(s) => result.push_str(s),
+        Item::Literal(s) | Item::Space(s) => result.write_str(s)?,
         #[cfg(any(feature = "alloc", feature = "std", test))]
-        Item::OwnedLiteral(ref s) | Item::OwnedSpace(ref s) => result.push_str(s),
+        Item::OwnedLiteral(ref s) | Item::OwnedSpace(ref s) => result.write_str(s)?,
 
         Item::Numeric(ref spec, ref pad) => {
             use self::Numeric::*;
@@ -507,19 +539,35 @@ fn format_inner<'a>(
                     1,
                     match (date, time, off) {
                         (Some(d), Some(t), None) => Some(d.and_time(*t).timestamp()),
-                        (Some(d), Some(t), Some(&(_, off))) => {
+                        (Some(d), Some(t), Some((_, off))) => {
                             Some((d.and_time(*t) - off).timestamp())
                         }
                         (_, _, _) => None,
                     },
                 ),
 
-                // for the future expansion
-                Internal(ref int) => match int._dummy {},
+                Internal(InternalNumeric { val: InternalNumericInternal::Quarter }) => {
+                    (1, date.map(|d| i64::from(d.quarter())))
+                }
+                Internal(InternalNumeric { val: InternalNumericInternal::FiscalYear(month) }) => {
+                    let fiscal = FiscalCalendar::new(u32::from(month));
+                    (4, date.map(|d| i64::from(fiscal.year(*d))))
+                }
+                Internal(InternalNumeric {
+                    val: InternalNumericInternal::FiscalQuarter(month),
+                }) => {
+                    let fiscal = FiscalCalendar::new(u32::from(month));
+                    (1, date.map(|d| i64::from(fiscal.quarter(*d))))
+                }
             };
 
+            let is_year = match *spec {
+                Year | IsoYear => true,
+                Internal(InternalNumeric { val: InternalNumericInternal::FiscalYear(_) }) => true,
+                _ => false,
+            };
             if let Some(v) = v {
-                if (spec == &Year || spec == &IsoYear) && !(0 <= v && v < 10_000) {
+                if is_year && !(0 <= v && v < 10_000) {
                     // non-four-digit years require an explicit sign as per ISO 8601
                     match *pad {
                         Pad::None => write!(result, "{:+}", v),
@@ -543,8 +591,8 @@ fn format_inner<'a>(
 
             /// Prints an offset from UTC in the format of `+HHMM` or `+HH:MM`.
             /// `Z` instead of `+00[:]00` is allowed when `allow_zulu` is true.
-            fn write_local_minus_utc(
-                result: &mut String,
+            fn write_local_minus_utc<W: Write>(
+                result: &mut W,
                 off: FixedOffset,
                 allow_zulu: bool,
                 use_colon: bool,
@@ -558,137 +606,124 @@ fn format_inner<'a>(
                         write!(result, "{}{:02}{:02}", sign, off / 3600, off / 60 % 60)
                     }
                 } else {
-                    result.push('Z');
-                    Ok(())
+                    result.write_char('Z')
                 }
             }
 
-            let ret =
-                match *spec {
-                    ShortMonthName => date.map(|d| {
-                        result.push_str(short_months[d.month0() as usize]);
-                        Ok(())
-                    }),
-                    LongMonthName => date.map(|d| {
-                        result.push_str(long_months[d.month0() as usize]);
-                        Ok(())
-                    }),
-                    ShortWeekdayName => date.map(|d| {
-                        result
-                            .push_str(short_weekdays[d.weekday().num_days_from_sunday() as usize]);
-                        Ok(())
-                    }),
-                    LongWeekdayName => date.map(|d| {
-                        result.push_str(long_weekdays[d.weekday().num_days_from_sunday() as usize]);
-                        Ok(())
-                    }),
-                    LowerAmPm => time.map(|t| {
-                        #[cfg_attr(feature = "cargo-clippy", allow(useless_asref))]
-                        {
-                            result.push_str(if t.hour12().0 {
-                                am_pm_lowercase[1].as_ref()
-                            } else {
-                                am_pm_lowercase[0].as_ref()
-                            });
-                        }
-                        Ok(())
-                    }),
-                    UpperAmPm => time.map(|t| {
-                        result.push_str(if t.hour12().0 { am_pm[1] } else { am_pm[0] });
-                        Ok(())
-                    }),
-                    Nanosecond => time.map(|t| {
-                        let nano = t.nanosecond() % 1_000_000_000;
-                        if nano == 0 {
-                            Ok(())
-                        } else if nano % 1_000_000 == 0 {
-                            write!(result, ".{:03}", nano / 1_000_000)
-                        } else if nano % 1_000 == 0 {
-                            write!(result, ".{:06}", nano / 1_000)
+            let ret = match *spec {
+                ShortMonthName => date.map(|d| result.write_str(short_months[d.month0() as usize])),
+                LongMonthName => date.map(|d| result.write_str(long_months[d.month0() as usize])),
+                ShortWeekdayName => date.map(|d| {
+                    result.write_str(short_weekdays[d.weekday().num_days_from_sunday() as usize])
+                }),
+                LongWeekdayName => date.map(|d| {
+                    result.write_str(long_weekdays[d.weekday().num_days_from_sunday() as usize])
+                }),
+                LowerAmPm => time.map(|t| {
+                    #[cfg_attr(feature = "cargo-clippy", allow(useless_asref))]
+                    {
+                        result.write_str(if t.hour12().0 {
+                            am_pm_lowercase[1].as_ref()
                         } else {
-                            write!(result, ".{:09}", nano)
-                        }
-                    }),
-                    Nanosecond3 => time.map(|t| {
-                        let nano = t.nanosecond() % 1_000_000_000;
+                            am_pm_lowercase[0].as_ref()
+                        })
+                    }
+                }),
+                UpperAmPm => {
+                    time.map(|t| result.write_str(if t.hour12().0 { am_pm[1] } else { am_pm[0] }))
+                }
+                Nanosecond => time.map(|t| {
+                    let nano = t.nanosecond() % 1_000_000_000;
+                    if nano == 0 {
+                        Ok(())
+                    } else if nano % 1_000_000 == 0 {
                         write!(result, ".{:03}", nano / 1_000_000)
-                    }),
-                    Nanosecond6 => time.map(|t| {
-                        let nano = t.nanosecond() % 1_000_000_000;
+                    } else if nano % 1_000 == 0 {
                         write!(result, ".{:06}", nano / 1_000)
-                    }),
-                    Nanosecond9 => time.map(|t| {
-                        let nano = t.nanosecond() % 1_000_000_000;
+                    } else {
                         write!(result, ".{:09}", nano)
-                    }),
-                    Internal(InternalFixed { val: InternalInternal::Nanosecond3NoDot }) => time
-                        .map(|t| {
-                            let nano = t.nanosecond() % 1_000_000_000;
-                            write!(result, "{:03}", nano / 1_000_000)
-                        }),
-                    Internal(InternalFixed { val: InternalInternal::Nanosecond6NoDot }) => time
-                        .map(|t| {
-                            let nano = t.nanosecond() % 1_000_000_000;
-                            write!(result, "{:06}", nano / 1_000)
-                        }),
-                    Internal(InternalFixed { val: InternalInternal::Nanosecond9NoDot }) => time
-                        .map(|t| {
-                            let nano = t.nanosecond() % 1_000_000_000;
-                            write!(result, "{:09}", nano)
-                        }),
-                    TimezoneName => off.map(|&(ref name, _)| {
-                        result.push_str(name);
-                        Ok(())
-                    }),
-                    TimezoneOffsetColon => {
-                        off.map(|&(_, off)| write_local_minus_utc(result, off, false, true))
                     }
-                    TimezoneOffsetColonZ => {
-                        off.map(|&(_, off)| write_local_minus_utc(result, off, true, true))
-                    }
-                    TimezoneOffset => {
-                        off.map(|&(_, off)| write_local_minus_utc(result, off, false, false))
-                    }
-                    TimezoneOffsetZ => {
-                        off.map(|&(_, off)| write_local_minus_utc(result, off, true, false))
-                    }
-                    Internal(InternalFixed { val: InternalInternal::TimezoneOffsetPermissive }) => {
-                        panic!("Do not try to write %#z it is undefined")
-                    }
-                    RFC2822 =>
-                    // same as `%a, %d %b %Y %H:%M:%S %z`
-                    {
-                        if let (Some(d), Some(t), Some(&(_, off))) = (date, time, off) {
-                            let sec = t.second() + t.nanosecond() / 1_000_000_000;
-                            write!(
-                                result,
-                                "{}, {:02} {} {:04} {:02}:{:02}:{:02} ",
-                                short_weekdays[d.weekday().num_days_from_sunday() as usize],
-                                d.day(),
-                                short_months[d.month0() as usize],
-                                d.year(),
-                                t.hour(),
-                                t.minute(),
-                                sec
-                            )?;
-                            Some(write_local_minus_utc(result, off, false, false))
-                        } else {
-                            None
-                        }
+                }),
+                Nanosecond3 => time.map(|t| {
+                    let nano = t.nanosecond() % 1_000_000_000;
+                    write!(result, ".{:03}", nano / 1_000_000)
+                }),
+                Nanosecond6 => time.map(|t| {
+                    let nano = t.nanosecond() % 1_000_000_000;
+                    write!(result, ".{:06}", nano / 1_000)
+                }),
+                Nanosecond9 => time.map(|t| {
+                    let nano = t.nanosecond() % 1_000_000_000;
+                    write!(result, ".{:09}", nano)
+                }),
+                Internal(InternalFixed { val: InternalInternal::Nanosecond3NoDot }) => {
+                    time.map(|t| {
+                        let nano = t.nanosecond() % 1_000_000_000;
+                        write!(result, "{:03}", nano / 1_000_000)
+                    })
+                }
+                Internal(InternalFixed { val: InternalInternal::Nanosecond6NoDot }) => {
+                    time.map(|t| {
+                        let nano = t.nanosecond() % 1_000_000_000;
+                        write!(result, "{:06}", nano / 1_000)
+                    })
+                }
+                Internal(InternalFixed { val: InternalInternal::Nanosecond9NoDot }) => {
+                    time.map(|t| {
+                        let nano = t.nanosecond() % 1_000_000_000;
+                        write!(result, "{:09}", nano)
+                    })
+                }
+                TimezoneName => off.and_then(|(name, _)| name).map(|name| result.write_str(name)),
+                TimezoneOffsetColon => {
+                    off.map(|(_, off)| write_local_minus_utc(result, off, false, true))
+                }
+                TimezoneOffsetColonZ => {
+                    off.map(|(_, off)| write_local_minus_utc(result, off, true, true))
+                }
+                TimezoneOffset => {
+                    off.map(|(_, off)| write_local_minus_utc(result, off, false, false))
+                }
+                TimezoneOffsetZ => {
+                    off.map(|(_, off)| write_local_minus_utc(result, off, true, false))
+                }
+                Internal(InternalFixed { val: InternalInternal::TimezoneOffsetPermissive }) => {
+                    panic!("Do not try to write %#z it is undefined")
+                }
+                RFC2822 =>
+                // same as `%a, %d %b %Y %H:%M:%S %z`
+                {
+                    if let (Some(d), Some(t), Some((_, off))) = (date, time, off) {
+                        let sec = t.second() + t.nanosecond() / 1_000_000_000;
+                        write!(
+                            result,
+                            "{}, {:02} {} {:04} {:02}:{:02}:{:02} ",
+                            short_weekdays[d.weekday().num_days_from_sunday() as usize],
+                            d.day(),
+                            short_months[d.month0() as usize],
+                            d.year(),
+                            t.hour(),
+                            t.minute(),
+                            sec
+                        )?;
+                        Some(write_local_minus_utc(result, off, false, false))
+                    } else {
+                        None
                     }
-                    RFC3339 =>
-                    // same as `%Y-%m-%dT%H:%M:%S%.f%:z`
-                    {
-                        if let (Some(d), Some(t), Some(&(_, off))) = (date, time, off) {
-                            // reuse `Debug` impls which already print ISO 8601 format.
-                            // this is faster in this way.
-                            write!(result, "{:?}T{:?}", d, t)?;
-                            Some(write_local_minus_utc(result, off, false, true))
-                        } else {
-                            None
-                        }
+                }
+                RFC3339 =>
+                // same as `%Y-%m-%dT%H:%M:%S%.f%:z`
+                {
+                    if let (Some(d), Some(t), Some((_, off))) = (date, time, off) {
+                        // reuse `Debug` impls which already print ISO 8601 format.
+                        // this is faster in this way.
+                        write!(result, "{:?}T{:?}", d, t)?;
+                        Some(write_local_minus_utc(result, off, false, true))
+                    } else {
+                        None
                     }
-                };
+                }
+            };
 
             match ret {
                 Some(ret) => ret?,
@@ -715,6 +750,7 @@ where
     I: Iterator<Item = B> + Clone,
     B: Borrow<Item<'a>>,
 {
+    let off = off.map(|&(ref name, off)| (Some(&name[..]), off));
     let mut result = String::new();
     for item in items {
         format_inner(&mut result, date, time, off, item.borrow(), None)?;
@@ -722,17 +758,30 @@ where
     w.pad(&result)
 }
 
+#[cfg(feature = "format-description")]
+mod description;
 mod parsed;
 
 // due to the size of parsing routines, they are in separate modules.
 mod parse;
 mod scan;
 
+pub mod duration;
+#[cfg(any(feature = "alloc", feature = "std", test))]
+mod owned;
 pub mod strftime;
 
 /// A *temporary* object which can be used as an argument to `format!` or others.
 /// This is normally constructed via `format` methods of each date and time type.
-#[cfg(any(feature = "alloc", feature = "std", test))]
+///
+/// Besides `Display`, it can be written into any `fmt::Write` or a byte buffer without
+/// allocating with the [`write_to`](#method.write_to) and [`format_into`](#method.format_into)
+/// methods. Without the `alloc` feature, `Display` ignores any width and precision.
+///
+/// `Display` streams the output into the formatter unless a width or precision is given.
+/// So if an item can't be formatted (e.g. `%Z` for a `NaiveDateTime`), it fails after
+/// the output of the preceding items has been written, like [`write_to`](#method.write_to).
+/// Only the output buffered for a width or precision is written all or nothing.
 #[derive(Debug)]
 pub struct DelayedFormat<I> {
     /// The date view, if any.
@@ -740,14 +789,13 @@ pub struct DelayedFormat<I> {
     /// The time view, if any.
     time: Option<NaiveTime>,
     /// The name and local-to-UTC difference for the offset (timezone), if any.
-    off: Option<(String, FixedOffset)>,
+    off: Option<(OffsetName, FixedOffset)>,
     /// An iterator returning formatting items.
     items: I,
     /// Locale used for text.
     locale: Option<Locale>,
 }
 
-#[cfg(any(feature = "alloc", feature = "std", test))]
 impl<'a, I: Iterator<Item = B> + Clone, B: Borrow<Item<'a>>> DelayedFormat<I> {
     /// Makes a new `DelayedFormat` value out of local date and time.
     pub fn new(date: Option<NaiveDate>, time: Option<NaiveTime>, items: I) -> DelayedFormat<I> {
@@ -764,7 +812,7 @@ impl<'a, I: Iterator<Item = B> + Clone, B: Borrow<Item<'a>>> DelayedFormat<I> {
     where
         Off: Offset + fmt::Display,
     {
-        let name_and_diff = (offset.to_string(), offset.fix());
+        let name_and_diff = (OffsetName::new(offset), offset.fix());
         DelayedFormat {
             date: date,
             time: time,
@@ -797,7 +845,7 @@ impl<'a, I: Iterator<Item = B> + Clone, B: Borrow<Item<'a>>> DelayedFormat<I> {
     where
         Off: Offset + fmt::Display,
     {
-        let name_and_diff = (offset.to_string(), offset.fix());
+        let name_and_diff = (OffsetName::new(offset), offset.fix());
         DelayedFormat {
             date: date,
             time: time,
@@ -806,26 +854,140 @@ impl<'a, I: Iterator<Item = B> + Clone, B: Borrow<Item<'a>>> DelayedFormat<I> {
             locale: Some(locale),
         }
     }
+
+    /// Writes the formatted date and time into `w` without allocating.
+    ///
+    /// Fails if an item is invalid or needs a missing field (e.g. an offset for a
+    /// `NaiveDateTime`), in which case `w` may already contain a part of the output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use std::fmt::Write;
+    ///
+    /// let dt = Utc.ymd(2020, 1, 31).and_hms(13, 45, 0);
+    /// let mut s = String::from("at ");
+    /// dt.format("%Y-%m-%d %H:%M %Z").write_to(&mut s).unwrap();
+    /// assert_eq!(s, "at 2020-01-31 13:45 UTC");
+    /// assert!(dt.naive_utc().format("%Z").write_to(&mut s).is_err());
+    /// ```
+    pub fn write_to<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let off = self.off.as_ref().map(|&(ref name, off)| (name.as_str(), off));
+        for item in self.items.clone() {
+            let (date, time) = (self.date.as_ref(), self.time.as_ref());
+            format_inner(w, date, time, off, item.borrow(), self.locale)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the formatted date and time into `buf` without allocating,
+    /// and returns the written part of `buf`.
+    ///
+    /// Fails if `buf` is too small, or for the same reasons as [`write_to`](#method.write_to).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    ///
+    /// let dt = NaiveDate::from_ymd(2020, 1, 31).and_hms_milli(13, 45, 0, 120);
+    /// let mut buf = [0; 32];
+    /// assert_eq!(dt.format("%Y-%m-%dT%H:%M:%S%.3f").format_into(&mut buf),
+    ///            Ok("2020-01-31T13:45:00.120"));
+    /// assert!(dt.format("%Y-%m-%dT%H:%M:%S%.3f").format_into(&mut buf[..8]).is_err());
+    /// ```
+    pub fn format_into<'b>(&self, buf: &'b mut [u8]) -> Result<&'b str, fmt::Error> {
+        let len = {
+            let mut w = BufWriter { buf: &mut *buf, len: 0 };
+            self.write_to(&mut w)?;
+            w.len
+        };
+        // only whole strings have been written, so this never fails
+        str::from_utf8(&buf[..len]).map_err(|_| fmt::Error)
+    }
 }
 
-#[cfg(any(feature = "alloc", feature = "std", test))]
 impl<'a, I: Iterator<Item = B> + Clone, B: Borrow<Item<'a>>> fmt::Display for DelayedFormat<I> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        #[cfg(feature = "unstable-locales")]
+        // padding needs the whole output, so it is only buffered when padding is requested
+        #[cfg(any(feature = "alloc", feature = "std", test))]
         {
-            if let Some(locale) = self.locale {
-                return format_localized(
-                    f,
-                    self.date.as_ref(),
-                    self.time.as_ref(),
-                    self.off.as_ref(),
-                    self.items.clone(),
-                    locale,
-                );
+            if f.width().is_some() || f.precision().is_some() {
+                let mut result = String::new();
+                self.write_to(&mut result)?;
+                return f.pad(&result);
+            }
+        }
+
+        self.write_to(f)
+    }
+}
+
+/// The name of an offset, as printed by `%Z`.
+#[cfg(any(feature = "alloc", feature = "std", test))]
+#[derive(Debug)]
+struct OffsetName(String);
+
+#[cfg(any(feature = "alloc", feature = "std", test))]
+impl OffsetName {
+    fn new<Off: fmt::Display>(offset: &Off) -> OffsetName {
+        OffsetName(offset.to_string())
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        Some(&self.0)
+    }
+}
+
+/// The name of an offset, as printed by `%Z`, stored inline as there is no allocator.
+///
+/// Names are usually abbreviations like `UTC` or `+09:00`.
+/// Longer names are not stored, so formatting `%Z` fails for them.
+#[cfg(not(any(feature = "alloc", feature = "std", test)))]
+#[derive(Debug)]
+struct OffsetName {
+    buf: [u8; 32],
+    len: Option<usize>,
+}
+
+#[cfg(not(any(feature = "alloc", feature = "std", test)))]
+impl OffsetName {
+    fn new<Off: fmt::Display>(offset: &Off) -> OffsetName {
+        let mut buf = [0; 32];
+        let len = {
+            let mut w = BufWriter { buf: &mut buf, len: 0 };
+            match write!(w, "{}", offset) {
+                Ok(()) => Some(w.len),
+                Err(_) => None,
             }
+        };
+        OffsetName { buf: buf, len: len }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self.len {
+            Some(len) => str::from_utf8(&self.buf[..len]).ok(),
+            None => None,
         }
+    }
+}
+
+/// A `fmt::Write` into a byte buffer, which fails when the buffer is full.
+struct BufWriter<'b> {
+    buf: &'b mut [u8],
+    len: usize,
+}
 
-        format(f, self.date.as_ref(), self.time.as_ref(), self.off.as_ref(), self.items.clone())
+impl<'b> Write for BufWriter<'b> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
     }
 }
 
@@ -877,6 +1039,7 @@ pub fn format_item_localized<'a>(
     item: &Item<'a>,
     locale: Locale,
 ) -> fmt::Result {
+    let off = off.map(|&(ref name, off)| (Some(&name[..]), off));
     let mut result = String::new();
     format_inner(&mut result, date, time, off, item, Some(locale))?;
     w.pad(&result)
@@ -897,6 +1060,7 @@ where
     I: Iterator<Item = B> + Clone,
     B: Borrow<Item<'a>>,
 {
+    let off = off.map(|&(ref name, off)| (Some(&name[..]), off));
     let mut result = String::new();
     for item in items {
         format_inner(&mut result, date, time, off, item.borrow(), Some(locale))?;
@@ -0,0 +1,211 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Pre-compiled and validated format strings.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::slice;
+use core::str::FromStr;
+#[cfg(any(feature = "std", test))]
+use std::error::Error;
+
+use super::{parse, Item, ParseResult, Parsed, StrftimeItems};
+
+/// A `strftime`-like format string which has been parsed and validated once,
+/// so that it can be reused for formatting and parsing without being parsed again.
+///
+/// Unlike [`StrftimeItems`](./strftime/struct.StrftimeItems.html), which only signals
+/// an invalid specifier with [`Item::Error`](./enum.Item.html#variant.Error) when the items
+/// are used, an `OwnedFormat` can never contain `Item::Error`.
+/// The items own their literal text and do not borrow from the original format string.
+///
+/// # Example
+///
+/// ```
+/// use chrono::format::OwnedFormat;
+/// use chrono::NaiveDate;
+///
+/// let fmt = OwnedFormat::new("%Y-%m-%d %H:%M").unwrap();
+///
+/// let dt = NaiveDate::from_ymd(2020, 1, 31).and_hms(13, 45, 0);
+/// assert_eq!(dt.format_with_items(fmt.items()).to_string(), "2020-01-31 13:45");
+/// assert_eq!(fmt.parse("2020-01-31 13:45").and_then(|p| p.to_naive_datetime_with_offset(0)),
+///            Ok(dt));
+///
+/// let err = OwnedFormat::new("%Y-%Q").unwrap_err();
+/// assert_eq!(err.position(), 3);
+/// assert_eq!(err.specifier(), "%Q");
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OwnedFormat {
+    items: Vec<Item<'static>>,
+}
+
+impl OwnedFormat {
+    /// Parses and validates the `strftime`-like format string `fmt`.
+    ///
+    /// Returns a [`FormatError`](./struct.FormatError.html) pointing to the first
+    /// invalid specifier, if any.
+    pub fn new(fmt: &str) -> Result<OwnedFormat, FormatError> {
+        let mut iter = StrftimeItems::new(fmt);
+        let mut items = Vec::new();
+        loop {
+            // composite specifiers never fail, so this is where a failing specifier starts
+            let start = fmt.len() - iter.remainder().len();
+            match iter.next() {
+                Some(Item::Error) => {
+                    let end = fmt.len() - iter.remainder().len();
+                    return Err(FormatError { position: start, specifier: fmt[start..end].into() });
+                }
+                Some(item) => items.push(item.into_owned()),
+                None => return Ok(OwnedFormat { items: items }),
+            }
+        }
+    }
+
+    /// Returns an iterator over the formatting items,
+    /// which can be passed to `format_with_items` methods or [`parse`](./fn.parse.html).
+    pub fn items(&self) -> slice::Iter<'_, Item<'static>> {
+        self.items.iter()
+    }
+
+    /// Returns the formatting items as a slice.
+    pub fn as_slice(&self) -> &[Item<'static>] {
+        &self.items
+    }
+
+    /// Parses `s` with this format into a fresh [`Parsed`](./struct.Parsed.html).
+    pub fn parse(&self, s: &str) -> ParseResult<Parsed> {
+        let mut parsed = Parsed::new();
+        parse(&mut parsed, s, self.items())?;
+        Ok(parsed)
+    }
+}
+
+impl FromStr for OwnedFormat {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<OwnedFormat, FormatError> {
+        OwnedFormat::new(s)
+    }
+}
+
+impl<'a> IntoIterator for &'a OwnedFormat {
+    type Item = &'a Item<'static>;
+    type IntoIter = slice::Iter<'a, Item<'static>>;
+
+    fn into_iter(self) -> slice::Iter<'a, Item<'static>> {
+        self.items.iter()
+    }
+}
+
+/// An error from [`OwnedFormat::new`](./struct.OwnedFormat.html#method.new),
+/// pointing to the first invalid specifier of the format string.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FormatError {
+    position: usize,
+    specifier: Box<str>,
+}
+
+impl FormatError {
+    /// Returns the byte offset of the invalid specifier in the format string.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the invalid specifier, as far as it has been read.
+    ///
+    /// This starts with `%`, and is only `%` itself if the format string ended prematurely.
+    pub fn specifier(&self) -> &str {
+        &self.specifier
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid format specifier `{}` at byte {}", self.specifier, self.position)
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl Error for FormatError {
+    #[allow(deprecated)]
+    fn description(&self) -> &str {
+        "invalid format specifier"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OwnedFormat;
+    use format::{Fixed, Item, Numeric, Pad, StrftimeItems};
+    use naive::NaiveDate;
+    use offset::{FixedOffset, TimeZone};
+
+    #[test]
+    fn test_owned_format() {
+        let fmt = OwnedFormat::new("%F %T%.3f %:z").unwrap();
+        assert_eq!(fmt.as_slice()[0], Item::Numeric(Numeric::Year, Pad::Zero));
+        assert_eq!(fmt.as_slice()[1], Item::OwnedLiteral("-".into()));
+        assert_eq!(fmt.as_slice()[11], Item::Fixed(Fixed::Nanosecond3));
+        assert_eq!(fmt.as_slice()[12], Item::OwnedSpace(" ".into()));
+        assert_eq!("%F %T%.3f %:z".parse(), Ok(fmt.clone()));
+
+        let dt = FixedOffset::east(3600).ymd(2020, 1, 31).and_hms_milli(13, 45, 0, 120);
+        assert_eq!(dt.format_with_items(fmt.items()).to_string(), "2020-01-31 13:45:00.120 +01:00");
+        assert_eq!(
+            dt.format_with_items(fmt.items()).to_string(),
+            dt.format("%F %T%.3f %:z").to_string()
+        );
+        let parsed = fmt.parse("2020-01-31 13:45:00.120 +01:00").unwrap();
+        assert_eq!(parsed.to_datetime(), Ok(dt));
+        assert!(fmt.parse("2020-01-31 13:45:00.120").is_err());
+
+        let fmt = OwnedFormat::new("").unwrap();
+        assert_eq!(fmt.items().count(), 0);
+        assert_eq!(NaiveDate::from_ymd(2020, 1, 31).format_with_items(fmt.items()).to_string(), "");
+    }
+
+    #[test]
+    fn test_owned_format_error() {
+        fn check(fmt: &str, position: usize, specifier: &str) {
+            let err = OwnedFormat::new(fmt).unwrap_err();
+            assert_eq!((err.position(), err.specifier()), (position, specifier), "for {:?}", fmt);
+            // `StrftimeItems` agrees that the format is invalid
+            assert!(StrftimeItems::new(fmt).any(|item| item == Item::Error));
+        }
+
+        check("%Q", 0, "%Q");
+        check("%Y-%m-%Q", 6, "%Q");
+        check("%Y-%", 3, "%");
+        check("%Y %:y", 3, "%:y");
+        check("%.4f", 0, "%.4");
+        check("%-D", 0, "%-D");
+        check("%#Y", 0, "%#Y");
+        check("année %é", 7, "%é");
+
+        let err = "%H:%M:%Q".parse::<OwnedFormat>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid format specifier `%Q` at byte 6");
+    }
+
+    #[test]
+    fn test_item_into_owned() {
+        let fmt = String::from("%Y at %H");
+        let items: Vec<Item<'static>> = StrftimeItems::new(&fmt).map(Item::into_owned).collect();
+        drop(fmt);
+        assert_eq!(
+            items,
+            [
+                Item::Numeric(Numeric::Year, Pad::Zero),
+                Item::OwnedSpace(" ".into()),
+                Item::OwnedLiteral("at".into()),
+                Item::OwnedSpace(" ".into()),
+                Item::Numeric(Numeric::Hour, Pad::Zero),
+            ]
+        );
+    }
+}
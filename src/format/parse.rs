@@ -12,9 +12,12 @@ use core::usize;
 
 use super::scan;
 use super::{Fixed, InternalFixed, InternalInternal, Item, Numeric, Pad, Parsed};
+use super::{InternalNumeric, InternalNumericInternal};
 use super::{ParseError, ParseErrorKind, ParseResult};
-use super::{BAD_FORMAT, INVALID, NOT_ENOUGH, OUT_OF_RANGE, TOO_LONG, TOO_SHORT};
-use {DateTime, FixedOffset, Weekday};
+use super::{BAD_FORMAT, IMPOSSIBLE, INVALID, NOT_ENOUGH, OUT_OF_RANGE, TOO_LONG, TOO_SHORT};
+use num_traits::ToPrimitive;
+use oldtime::Duration as OldDuration;
+use {DateTime, FixedOffset, Period, Weekday};
 
 fn set_weekday_with_num_days_from_sunday(p: &mut Parsed, v: i64) -> ParseResult<()> {
     p.set_weekday(match v {
@@ -318,8 +321,15 @@ where
                     Nanosecond => (9, false, Parsed::set_nanosecond),
                     Timestamp => (usize::MAX, false, Parsed::set_timestamp),
 
-                    // for the future expansion
-                    Internal(ref int) => match int._dummy {},
+                    Internal(InternalNumeric { val: InternalNumericInternal::Quarter }) => {
+                        (1, false, Parsed::set_quarter)
+                    }
+                    Internal(InternalNumeric { val: InternalNumericInternal::FiscalYear(_) })
+                    | Internal(InternalNumeric {
+                        val: InternalNumericInternal::FiscalQuarter(_),
+                    }) => {
+                        return Err((s, BAD_FORMAT));
+                    }
                 };
 
                 s = s.trim_left();
@@ -509,6 +519,149 @@ impl str::FromStr for DateTime<FixedOffset> {
     }
 }
 
+/// Parses an ISO 8601 duration such as `P1Y2M10DT2H30M` or `P3W` into a `Period`.
+///
+/// The date components (`Y`, `M`, `W`, `D`) and the time components (`H`, `M`, `S`)
+/// after the `T` designator can be omitted but should appear in this order.
+/// Weeks are converted to days and only the seconds can have a fractional part.
+/// A leading minus sign negates the whole period,
+/// and individual components can be negative as well (e.g. `P1Y-1M`).
+///
+/// # Example
+///
+/// ```
+/// # extern crate chrono; fn main() {
+/// use chrono::{Duration, Period};
+///
+/// assert_eq!("P1Y2M10D".parse::<Period>(), Ok(Period::new(1, 2, 10)));
+/// assert_eq!("P3W".parse::<Period>(), Ok(Period::new(0, 0, 21)));
+/// assert_eq!("-P1M".parse::<Period>(), Ok(Period::new(0, -1, 0)));
+/// assert_eq!("PT1H30M0.5S".parse::<Period>(),
+///            Ok(Period::with_time(0, 0, 0, Duration::milliseconds(5_400_500))));
+/// assert!("P".parse::<Period>().is_err());
+/// assert!("P1D2Y".parse::<Period>().is_err());
+/// # }
+/// ```
+impl str::FromStr for Period {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> ParseResult<Period> {
+        // optionally signed number of up to `max` digits
+        fn signed_number(s: &str, max: usize) -> ParseResult<(&str, i64)> {
+            match s.as_bytes().first() {
+                Some(&b'-') => scan::number(&s[1..], 1, max).map(|(s, v)| (s, -v)),
+                _ => scan::number(s, 1, max),
+            }
+        }
+
+        let (negative, s) = match s.as_bytes().first() {
+            Some(&b'-') => (true, &s[1..]),
+            Some(&b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let mut s = scan::char(s, b'P')?;
+
+        // years, months, weeks and days in this order
+        let mut date = [0i64; 4];
+        let mut seen = 0;
+        while !s.is_empty() && !s.starts_with('T') {
+            let (s_, v) = signed_number(s, 9)?;
+            let idx = match s_.as_bytes().first() {
+                Some(&b'Y') => 1,
+                Some(&b'M') => 2,
+                Some(&b'W') => 3,
+                Some(&b'D') => 4,
+                Some(_) => return Err(INVALID),
+                None => return Err(TOO_SHORT),
+            };
+            if idx <= seen {
+                return Err(INVALID);
+            }
+            date[idx - 1] = v;
+            seen = idx;
+            s = &s_[1..];
+        }
+
+        // hours, minutes and seconds in this order
+        let mut time = OldDuration::zero();
+        if s.starts_with('T') {
+            s = &s[1..];
+            if s.is_empty() {
+                return Err(TOO_SHORT);
+            }
+            let mut seen = 0;
+            while !s.is_empty() {
+                let (s_, v) = signed_number(s, 12)?;
+                let (s_, nanos) = match s_.as_bytes().first() {
+                    Some(&b'.') | Some(&b',') => scan::nanosecond(&s_[1..])?,
+                    _ => (s_, 0),
+                };
+                let (idx, v) = match s_.as_bytes().first() {
+                    Some(&b'H') if nanos == 0 => (1, OldDuration::hours(v)),
+                    Some(&b'M') if nanos == 0 => (2, OldDuration::minutes(v)),
+                    Some(&b'S') => {
+                        let nanos = if s.starts_with('-') { -nanos } else { nanos };
+                        (3, OldDuration::seconds(v) + OldDuration::nanoseconds(nanos))
+                    }
+                    Some(_) => return Err(INVALID),
+                    None => return Err(TOO_SHORT),
+                };
+                if idx <= seen {
+                    return Err(INVALID);
+                }
+                time = time.checked_add(&v).ok_or(OUT_OF_RANGE)?;
+                seen = idx;
+                s = &s_[1..];
+            }
+        } else if seen == 0 {
+            return Err(TOO_SHORT);
+        }
+
+        let days = (date[2] * 7 + date[3]).to_i32().ok_or(OUT_OF_RANGE)?;
+        let period = Period::with_time(
+            date[0].to_i32().ok_or(OUT_OF_RANGE)?,
+            date[1].to_i32().ok_or(OUT_OF_RANGE)?,
+            days,
+            time,
+        );
+        Ok(if negative { -period } else { period })
+    }
+}
+
+/// Parses an ISO 8601 time interval in the `<start>/<end>`, `<start>/<duration>`
+/// or `<duration>/<end>` form, where the duration is parsed as a `Period`.
+///
+/// `parse` parses the start and end values
+/// and `add` adds a (possibly negative) period to a parsed value,
+/// failing when the period can't be applied to that kind of value.
+pub(crate) fn parse_interval<T, F, G>(s: &str, parse: F, add: G) -> ParseResult<(T, T)>
+where
+    T: PartialOrd,
+    F: Fn(&str) -> ParseResult<T>,
+    G: Fn(&T, Period) -> ParseResult<T>,
+{
+    let slash = s.find('/').ok_or(INVALID)?;
+    let (start, end) = (&s[..slash], &s[slash + 1..]);
+    let (start, end) = match (start.starts_with('P'), end.starts_with('P')) {
+        (false, false) => (parse(start)?, parse(end)?),
+        (false, true) => {
+            let start = parse(start)?;
+            let end = add(&start, end.parse::<Period>()?)?;
+            (start, end)
+        }
+        (true, false) => {
+            let end = parse(end)?;
+            let start = add(&end, -start.parse::<Period>()?)?;
+            (start, end)
+        }
+        (true, true) => return Err(NOT_ENOUGH),
+    };
+    if start > end {
+        return Err(IMPOSSIBLE);
+    }
+    Ok((start, end))
+}
+
 #[cfg(test)]
 #[test]
 fn test_parse() {
@@ -765,6 +918,11 @@ fn test_parse() {
     check!("z",         [internal_fix!(TimezoneOffsetPermissive)]; offset: 0);
     check!("+12:00",    [internal_fix!(TimezoneOffsetPermissive)]; offset: 12 * 60 * 60);
     check!("+12",       [internal_fix!(TimezoneOffsetPermissive)]; offset: 12 * 60 * 60);
+
+    // quarter and fiscal year
+    check!("3",    [internal_num!(InternalNumericInternal::Quarter)]; quarter: 3);
+    check!("2022", [internal_num0!(InternalNumericInternal::FiscalYear(10))]; BAD_FORMAT);
+    check!("1",    [internal_num!(InternalNumericInternal::FiscalQuarter(10))]; BAD_FORMAT);
     check!("CEST 5",    [fix!(TimezoneName), lit!(" "), num!(Day)]; day: 5);
 
     // some practical examples
@@ -943,3 +1101,47 @@ fn test_rfc3339() {
         }
     }
 }
+
+#[cfg(test)]
+#[test]
+fn test_period_from_str() {
+    let p = |s: &str| s.parse::<Period>();
+    let time = |y, m, d, secs, nanos| {
+        Period::with_time(y, m, d, OldDuration::seconds(secs) + OldDuration::nanoseconds(nanos))
+    };
+
+    assert_eq!(p("P0D"), Ok(Period::zero()));
+    assert_eq!(p("PT0S"), Ok(Period::zero()));
+    assert_eq!(p("P1Y"), Ok(Period::new(1, 0, 0)));
+    assert_eq!(p("P1Y1W"), Ok(Period::new(1, 0, 7)));
+    assert_eq!(p("P2W3D"), Ok(Period::new(0, 0, 17)));
+    assert_eq!(p("+P12M"), Ok(Period::new(0, 12, 0)));
+    assert_eq!(p("P1Y-1M"), Ok(Period::new(1, -1, 0)));
+    assert_eq!(p("-P1Y2M3DT4H5M6S"), Ok(-time(1, 2, 3, 4 * 3600 + 5 * 60 + 6, 0)));
+    assert_eq!(p("PT1M"), Ok(time(0, 0, 0, 60, 0)));
+    assert_eq!(p("PT0,25S"), Ok(time(0, 0, 0, 0, 250_000_000)));
+    assert_eq!(p("PT-1.5S"), Ok(time(0, 0, 0, -1, -500_000_000)));
+    assert_eq!(p("P"), Err(TOO_SHORT));
+    assert_eq!(p("P1"), Err(TOO_SHORT));
+    assert_eq!(p("PT"), Err(TOO_SHORT));
+    assert_eq!(p("1D"), Err(INVALID));
+    assert_eq!(p("p1d"), Err(INVALID));
+    assert_eq!(p("P1D1Y"), Err(INVALID));
+    assert_eq!(p("P1D1D"), Err(INVALID));
+    assert_eq!(p("P1H"), Err(INVALID));
+    assert_eq!(p("PT1D"), Err(INVALID));
+    assert_eq!(p("PT1.5H"), Err(INVALID));
+    assert_eq!(p("P1DT"), Err(TOO_SHORT));
+    assert_eq!(p("P999999999W"), Err(OUT_OF_RANGE));
+
+    // round trip through the `Display` output
+    for period in &[
+        Period::new(1, 2, 3),
+        Period::new(0, -1, -300),
+        Period::new(2, -1, 0),
+        time(0, 0, 1, 3723, 500),
+        -time(0, 0, 1, 3723, 500),
+    ] {
+        assert_eq!(p(&period.to_string()), Ok(*period));
+    }
+}
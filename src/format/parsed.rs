@@ -9,7 +9,7 @@ use oldtime::Duration as OldDuration;
 
 use super::{ParseResult, IMPOSSIBLE, NOT_ENOUGH, OUT_OF_RANGE};
 use div::div_rem;
-use naive::{NaiveDate, NaiveDateTime, NaiveTime};
+use naive::{NaiveDate, NaiveDateTime, NaiveTime, WeekRule};
 use offset::{FixedOffset, LocalResult, Offset, TimeZone};
 use DateTime;
 use Weekday;
@@ -62,6 +62,9 @@ pub struct Parsed {
     /// Month (1--12).
     pub month: Option<u32>,
 
+    /// Quarter of the year (1--4). Only used to verify the date.
+    pub quarter: Option<u32>,
+
     /// Week number, where the week 1 starts at the first Sunday of January
     /// (0--53, 1--53 or 1--52 depending on the year).
     pub week_from_sun: Option<u32>,
@@ -74,6 +77,15 @@ pub struct Parsed {
     /// (1--52 or 1--53 depending on the year).
     pub isoweek: Option<u32>,
 
+    /// Year in the week date of a [`WeekRule`](../naive/struct.WeekRule.html).
+    /// Only used by [`to_naive_date_with_week_rule`](#method.to_naive_date_with_week_rule).
+    pub rule_weekyear: Option<i32>,
+
+    /// Week number in the week date of a [`WeekRule`](../naive/struct.WeekRule.html)
+    /// (1--52 or 1--53 depending on the year).
+    /// Only used by [`to_naive_date_with_week_rule`](#method.to_naive_date_with_week_rule).
+    pub rule_week: Option<u32>,
+
     /// Day of the week.
     pub weekday: Option<Weekday>,
 
@@ -136,9 +148,12 @@ impl Default for Parsed {
             isoyear_div_100: None,
             isoyear_mod_100: None,
             month: None,
+            quarter: None,
             week_from_sun: None,
             week_from_mon: None,
             isoweek: None,
+            rule_weekyear: None,
+            rule_week: None,
             weekday: None,
             ordinal: None,
             day: None,
@@ -214,6 +229,12 @@ impl Parsed {
         set_if_consistent(&mut self.month, value.to_u32().ok_or(OUT_OF_RANGE)?)
     }
 
+    /// Tries to set the [`quarter`](#structfield.quarter) field from given value.
+    #[inline]
+    pub fn set_quarter(&mut self, value: i64) -> ParseResult<()> {
+        set_if_consistent(&mut self.quarter, value.to_u32().ok_or(OUT_OF_RANGE)?)
+    }
+
     /// Tries to set the [`week_from_sun`](#structfield.week_from_sun) field from given value.
     #[inline]
     pub fn set_week_from_sun(&mut self, value: i64) -> ParseResult<()> {
@@ -232,6 +253,18 @@ impl Parsed {
         set_if_consistent(&mut self.isoweek, value.to_u32().ok_or(OUT_OF_RANGE)?)
     }
 
+    /// Tries to set the [`rule_weekyear`](#structfield.rule_weekyear) field from given value.
+    #[inline]
+    pub fn set_rule_weekyear(&mut self, value: i64) -> ParseResult<()> {
+        set_if_consistent(&mut self.rule_weekyear, value.to_i32().ok_or(OUT_OF_RANGE)?)
+    }
+
+    /// Tries to set the [`rule_week`](#structfield.rule_week) field from given value.
+    #[inline]
+    pub fn set_rule_week(&mut self, value: i64) -> ParseResult<()> {
+        set_if_consistent(&mut self.rule_week, value.to_u32().ok_or(OUT_OF_RANGE)?)
+    }
+
     /// Tries to set the [`weekday`](#structfield.weekday) field from given value.
     #[inline]
     pub fn set_weekday(&mut self, value: Weekday) -> ParseResult<()> {
@@ -378,11 +411,13 @@ impl Parsed {
                 (None, None) // they should be empty to be consistent
             };
             let month = date.month();
+            let quarter = date.quarter();
             let day = date.day();
             self.year.unwrap_or(year) == year
                 && self.year_div_100.or(year_div_100) == year_div_100
                 && self.year_mod_100.or(year_mod_100) == year_mod_100
                 && self.month.unwrap_or(month) == month
+                && self.quarter.unwrap_or(quarter) == quarter
                 && self.day.unwrap_or(day) == day
         };
 
@@ -423,7 +458,8 @@ impl Parsed {
             (Some(year), _, &Parsed { month: Some(month), day: Some(day), .. }) => {
                 // year, month, day
                 let date = NaiveDate::from_ymd_opt(year, month, day).ok_or(OUT_OF_RANGE)?;
-                (verify_isoweekdate(date) && verify_ordinal(date), date)
+                let verify_quarter = self.quarter.unwrap_or(date.quarter()) == date.quarter();
+                (verify_quarter && verify_isoweekdate(date) && verify_ordinal(date), date)
             }
 
             (Some(year), _, &Parsed { ordinal: Some(ordinal), .. }) => {
@@ -517,6 +553,52 @@ impl Parsed {
         }
     }
 
+    /// Returns a parsed naive date out of given fields, also using the week date
+    /// ([`rule_weekyear`](#structfield.rule_weekyear), [`rule_week`](#structfield.rule_week)
+    /// and [`weekday`](#structfield.weekday)) of given week rule.
+    ///
+    /// The week date is used when the other fields are not enough to determine the date
+    /// as in [`to_naive_date`](#method.to_naive_date), and is verified otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::format::Parsed;
+    /// use chrono::{NaiveDate, WeekRule, Weekday};
+    ///
+    /// let mut parsed = Parsed::new();
+    /// parsed.set_rule_weekyear(2022).unwrap();
+    /// parsed.set_rule_week(1).unwrap();
+    /// parsed.set_weekday(Weekday::Mon).unwrap();
+    /// let rule = WeekRule::new(Weekday::Sun, 1);
+    /// assert_eq!(parsed.to_naive_date_with_week_rule(&rule), Ok(NaiveDate::from_ymd(2021, 12, 27)));
+    /// ```
+    pub fn to_naive_date_with_week_rule(&self, rule: &WeekRule) -> ParseResult<NaiveDate> {
+        let date = match self.to_naive_date() {
+            Err(e) if e == NOT_ENOUGH => match (self.rule_weekyear, self.rule_week, self.weekday) {
+                (Some(year), Some(week), Some(weekday)) => {
+                    let date = rule.from_week_date_opt(year, week, weekday).ok_or(OUT_OF_RANGE)?;
+                    // the other fields were not enough, but they may still be inconsistent
+                    let consistent = self.year.unwrap_or(date.year()) == date.year()
+                        && self.month.unwrap_or(date.month()) == date.month()
+                        && self.quarter.unwrap_or(date.quarter()) == date.quarter()
+                        && self.day.unwrap_or(date.day()) == date.day()
+                        && self.ordinal.unwrap_or(date.ordinal()) == date.ordinal();
+                    return if consistent { Ok(date) } else { Err(IMPOSSIBLE) };
+                }
+                _ => return Err(NOT_ENOUGH),
+            },
+            result => result?,
+        };
+        let year = rule.week_based_year(date);
+        let week = rule.week(date);
+        if self.rule_weekyear.unwrap_or(year) == year && self.rule_week.unwrap_or(week) == week {
+            Ok(date)
+        } else {
+            Err(IMPOSSIBLE)
+        }
+    }
+
     /// Returns a parsed naive time out of given fields.
     ///
     /// This method is able to determine the time from given subset of fields:
@@ -721,10 +803,10 @@ impl Parsed {
 mod tests {
     use super::super::{IMPOSSIBLE, NOT_ENOUGH, OUT_OF_RANGE};
     use super::Parsed;
-    use naive::{NaiveDate, NaiveTime, MAX_DATE, MIN_DATE};
+    use naive::{NaiveDate, NaiveTime, WeekRule, MAX_DATE, MIN_DATE};
     use offset::{FixedOffset, TimeZone, Utc};
     use Datelike;
-    use Weekday::*;
+    use Weekday::{self, *};
 
     #[test]
     fn test_parsed_set_fields() {
@@ -812,6 +894,45 @@ mod tests {
         assert_eq!(parse!(year: 1984), Err(NOT_ENOUGH));
         assert_eq!(parse!(year: 1984, month: 1), Err(NOT_ENOUGH));
         assert_eq!(parse!(year: 1984, month: 1, day: 2), ymd(1984, 1, 2));
+        assert_eq!(parse!(year: 1984, month: 1, day: 2, quarter: 1), ymd(1984, 1, 2));
+        assert_eq!(parse!(year: 1984, month: 1, day: 2, quarter: 2), Err(IMPOSSIBLE));
+        assert_eq!(parse!(year: 1984, quarter: 1), Err(NOT_ENOUGH));
+
+        // week date of a week rule
+        let rule = WeekRule::new(Weekday::Sun, 1);
+        let parse_rule = |parsed: Parsed| parsed.to_naive_date_with_week_rule(&rule);
+        assert_eq!(
+            parse_rule(Parsed { rule_weekyear: Some(2022), rule_week: Some(1), ..Parsed::new() }),
+            Err(NOT_ENOUGH)
+        );
+        let week_date = Parsed {
+            rule_weekyear: Some(2022),
+            rule_week: Some(1),
+            weekday: Some(Weekday::Mon),
+            ..Parsed::new()
+        };
+        assert_eq!(parse_rule(week_date.clone()), ymd(2021, 12, 27));
+        assert_eq!(parse_rule(Parsed { month: Some(12), ..week_date.clone() }), ymd(2021, 12, 27));
+        assert_eq!(parse_rule(Parsed { month: Some(1), ..week_date.clone() }), Err(IMPOSSIBLE));
+        assert_eq!(
+            parse_rule(Parsed { rule_week: Some(54), ..week_date.clone() }),
+            Err(OUT_OF_RANGE)
+        );
+        assert_eq!(
+            parse_rule(Parsed { year: Some(2021), ordinal: Some(361), ..week_date.clone() }),
+            ymd(2021, 12, 27)
+        );
+        assert_eq!(
+            parse_rule(Parsed { year: Some(2021), ordinal: Some(362), ..week_date.clone() }),
+            Err(IMPOSSIBLE)
+        );
+        let ymd_parsed =
+            Parsed { year: Some(2021), month: Some(12), day: Some(27), ..Parsed::new() };
+        assert_eq!(
+            parse_rule(Parsed { rule_week: Some(1), ..ymd_parsed.clone() }),
+            ymd(2021, 12, 27)
+        );
+        assert_eq!(parse_rule(Parsed { rule_week: Some(52), ..ymd_parsed }), Err(IMPOSSIBLE));
         assert_eq!(parse!(year: 1984, day: 2), Err(NOT_ENOUGH));
         assert_eq!(parse!(year_div_100: 19), Err(NOT_ENOUGH));
         assert_eq!(parse!(year_div_100: 19, year_mod_100: 84), Err(NOT_ENOUGH));
@@ -19,6 +19,7 @@ The following specifiers are available both to formatting and parsing.
 | `%b`  | `Jul`    | Abbreviated month name. Always 3 letters.                                  |
 | `%B`  | `July`   | Full month name. Also accepts corresponding abbreviation in parsing.       |
 | `%h`  | `Jul`    | Same as `%b`.                                                              |
+| `%q`  | `3`      | Quarter of the year (1--4).                                                |
 |       |          |                                                                            |
 | `%d`  | `08`     | Day number (01--31), zero-padded to 2 digits.                              |
 | `%e`  | ` 8`     | Same as `%d` but space-padded. Same as `%_d`.                              |
@@ -37,6 +38,9 @@ The following specifiers are available both to formatting and parsing.
 |       |          |                                                                            |
 | `%j`  | `189`    | Day of the year (001--366), zero-padded to 3 digits.                       |
 |       |          |                                                                            |
+| `%10Y`| `2001`   | The fiscal year starting in given month (1--12), named by its last year. [^10] |
+| `%10q`| `4`      | Quarter of the fiscal year starting in given month (1--4). [^10]           |
+|       |          |                                                                            |
 | `%D`  | `07/08/01`    | Month-day-year format. Same as `%m/%d/%y`.                            |
 | `%x`  | `07/08/01`    | Locale's date representation (e.g., 12/31/99).                        |
 | `%F`  | `2001-07-08`  | Year-month-day format (ISO 8601). Same as `%Y-%m-%d`.                 |
@@ -166,11 +170,17 @@ Notes:
    It is not possible to reliably convert from an abbreviation to an offset,
    for example CDT can mean either Central Daylight Time (North America) or
    China Daylight Time.
+
+[^10]: `%10Y`, `%10q`:
+   The number before `Y` or `q` is the month in which fiscal years start,
+   e.g. `%4Y` for fiscal years from April to March. These are only supported in formatting.
+   See [`FiscalCalendar`](../../fiscal/struct.FiscalCalendar.html) for other fiscal calendars.
 */
 
 #[cfg(feature = "unstable-locales")]
 use super::{locales, Locale};
 use super::{Fixed, InternalFixed, InternalInternal, Item, Numeric, Pad};
+use super::{InternalNumeric, InternalNumericInternal};
 
 #[cfg(feature = "unstable-locales")]
 type Fmt<'a> = Vec<Item<'a>>;
@@ -259,6 +269,12 @@ impl<'a> StrftimeItems<'a> {
             t_fmt: T_FMT.to_vec(),
         }
     }
+
+    /// Returns the portion of the format string which has not been parsed yet.
+    #[cfg(any(feature = "alloc", feature = "std", test))]
+    pub(super) fn remainder(&self) -> &'a str {
+        self.remainder
+    }
 }
 
 const HAVE_ALTERNATES: &'static str = "z";
@@ -381,6 +397,7 @@ impl<'a> Iterator for StrftimeItems<'a> {
                     'm' => num0!(Month),
                     'n' => sp!("\n"),
                     'p' => fix!(UpperAmPm),
+                    'q' => internal_num!(InternalNumericInternal::Quarter),
                     'r' => recons![
                         num0!(Hour12),
                         lit!(":"),
@@ -427,18 +444,25 @@ impl<'a> Iterator for StrftimeItems<'a> {
                         'f' => fix!(Nanosecond),
                         _ => Item::Error,
                     },
-                    '3' => match next!() {
-                        'f' => internal_fix!(Nanosecond3NoDot),
-                        _ => Item::Error,
-                    },
-                    '6' => match next!() {
-                        'f' => internal_fix!(Nanosecond6NoDot),
-                        _ => Item::Error,
-                    },
-                    '9' => match next!() {
-                        'f' => internal_fix!(Nanosecond9NoDot),
-                        _ => Item::Error,
-                    },
+                    '1'..='9' => {
+                        // `%3f`, `%6f` and `%9f`, or a fiscal year starting in given month
+                        let mut month = spec as u8 - b'0';
+                        let mut spec = next!();
+                        if month == 1 && '0' <= spec && spec <= '2' {
+                            month = 10 + (spec as u8 - b'0');
+                            spec = next!();
+                        }
+                        match (month, spec) {
+                            (3, 'f') => internal_fix!(Nanosecond3NoDot),
+                            (6, 'f') => internal_fix!(Nanosecond6NoDot),
+                            (9, 'f') => internal_fix!(Nanosecond9NoDot),
+                            (_, 'Y') => internal_num0!(InternalNumericInternal::FiscalYear(month)),
+                            (_, 'q') => {
+                                internal_num!(InternalNumericInternal::FiscalQuarter(month))
+                            }
+                            _ => Item::Error,
+                        }
+                    }
                     '%' => lit!("%"),
                     _ => Item::Error, // no such specifier
                 };
@@ -518,6 +542,21 @@ fn test_strftime_items() {
     assert_eq!(parse_and_collect("bar%42"), [Item::Error]);
     assert_eq!(parse_and_collect("quux% +"), [Item::Error]);
     assert_eq!(parse_and_collect("%.Z"), [Item::Error]);
+    assert_eq!(parse_and_collect("%q"), [internal_num!(InternalNumericInternal::Quarter)]);
+    assert_eq!(parse_and_collect("%7Y"), [internal_num0!(InternalNumericInternal::FiscalYear(7))]);
+    assert_eq!(
+        parse_and_collect("%-12Y"),
+        [Item::Numeric(
+            Numeric::Internal(InternalNumeric { val: InternalNumericInternal::FiscalYear(12) }),
+            Pad::None
+        )]
+    );
+    assert_eq!(
+        parse_and_collect("%3q"),
+        [internal_num!(InternalNumericInternal::FiscalQuarter(3))]
+    );
+    assert_eq!(parse_and_collect("%13Y"), [Item::Error]);
+    assert_eq!(parse_and_collect("%4f"), [Item::Error]);
     assert_eq!(parse_and_collect("%:Z"), [Item::Error]);
     assert_eq!(parse_and_collect("%-Z"), [Item::Error]);
     assert_eq!(parse_and_collect("%0Z"), [Item::Error]);
@@ -552,6 +591,7 @@ fn test_strftime_docs() {
     assert_eq!(dt.format("%b").to_string(), "Jul");
     assert_eq!(dt.format("%B").to_string(), "July");
     assert_eq!(dt.format("%h").to_string(), "Jul");
+    assert_eq!(dt.format("%q").to_string(), "3");
     assert_eq!(dt.format("%d").to_string(), "08");
     assert_eq!(dt.format("%e").to_string(), " 8");
     assert_eq!(dt.format("%e").to_string(), dt.format("%_d").to_string());
@@ -565,6 +605,8 @@ fn test_strftime_docs() {
     assert_eq!(dt.format("%g").to_string(), "01");
     assert_eq!(dt.format("%V").to_string(), "27");
     assert_eq!(dt.format("%j").to_string(), "189");
+    assert_eq!(dt.format("%10Y").to_string(), "2001");
+    assert_eq!(dt.format("%10q").to_string(), "4");
     assert_eq!(dt.format("%D").to_string(), "07/08/01");
     assert_eq!(dt.format("%x").to_string(), "07/08/01");
     assert_eq!(dt.format("%F").to_string(), "2001-07-08");
@@ -0,0 +1,607 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Humanized relative time, such as "in 5 minutes", "yesterday" or "3 weeks ago".
+//!
+//! A [`Humanizer`](./struct.Humanizer.html) picks the unit for a signed `Duration` by
+//! configurable thresholds and rounds the duration to it, giving a
+//! [`RelativeTime`](./struct.RelativeTime.html) which displays as English text.
+//! With the `unstable-locales` feature, it can be formatted with the CLDR relative time
+//! patterns and plural rules of several languages as well.
+//!
+//! Months and years are based on their average length in the Gregorian calendar,
+//! and days on 24 hours of elapsed time (so "yesterday" is 24 hours ago, give or take
+//! the rounding, instead of the previous calendar day).
+//!
+//! # Example
+//!
+//! ```
+//! use chrono::humanize::{Humanizer, TimeUnit};
+//! use chrono::{Duration, TimeZone, Utc};
+//!
+//! let humanizer = Humanizer::new();
+//! assert_eq!(humanizer.relative(Duration::minutes(5)).to_string(), "in 5 minutes");
+//! assert_eq!(humanizer.relative(Duration::hours(-26)).to_string(), "yesterday");
+//! assert_eq!(humanizer.relative(Duration::days(-20)).to_string(), "3 weeks ago");
+//!
+//! let now = Utc.ymd(2021, 6, 1).and_hms(12, 0, 0);
+//! let posted = Utc.ymd(2021, 6, 1).and_hms(9, 10, 0);
+//! assert_eq!(humanizer.between(&posted, &now).to_string(), "3 hours ago");
+//!
+//! let exact = Humanizer::new().with_numeric(true).with_granularity(TimeUnit::Minute);
+//! assert_eq!(exact.relative(Duration::hours(-26)).to_string(), "1 day ago");
+//! assert_eq!(exact.relative(Duration::seconds(20)).to_string(), "in 0 minutes");
+//! ```
+
+use core::fmt;
+use oldtime::Duration as OldDuration;
+
+#[cfg(feature = "unstable-locales")]
+use alloc::string::String;
+#[cfg(feature = "unstable-locales")]
+use format::Locale;
+use offset::TimeZone;
+use DateTime;
+
+/// A unit of relative time.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum TimeUnit {
+    /// Seconds.
+    Second,
+    /// Minutes.
+    Minute,
+    /// Hours.
+    Hour,
+    /// Days of 24 hours.
+    Day,
+    /// Weeks of 7 days.
+    Week,
+    /// Months of 30.436875 days, the average length of a month.
+    Month,
+    /// Years of 365.2425 days, the average length of a year.
+    Year,
+}
+
+impl TimeUnit {
+    const ALL: [TimeUnit; 7] = [
+        TimeUnit::Second,
+        TimeUnit::Minute,
+        TimeUnit::Hour,
+        TimeUnit::Day,
+        TimeUnit::Week,
+        TimeUnit::Month,
+        TimeUnit::Year,
+    ];
+
+    /// Returns the number of seconds in the unit.
+    fn seconds(&self) -> i64 {
+        match *self {
+            TimeUnit::Second => 1,
+            TimeUnit::Minute => 60,
+            TimeUnit::Hour => 3600,
+            TimeUnit::Day => 86_400,
+            TimeUnit::Week => 604_800,
+            TimeUnit::Month => 2_629_746,
+            TimeUnit::Year => 31_556_952,
+        }
+    }
+}
+
+/// Converts a `Duration` into a [`RelativeTime`](./struct.RelativeTime.html).
+///
+/// The duration is expressed in the smallest unit, starting from the granularity,
+/// whose rounded value is less than the threshold of that unit.
+/// The default thresholds are 45 seconds, 45 minutes, 22 hours, 7 days, 4 weeks and 11 months,
+/// so e.g. 50 minutes are "1 hour" and 10 days are "1 week".
+///
+/// See the [module documentation](./index.html) for an example.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Humanizer {
+    thresholds: [i64; 6],
+    granularity: TimeUnit,
+    numeric: bool,
+}
+
+impl Humanizer {
+    /// Makes a new humanizer with the default thresholds and a granularity of seconds.
+    pub fn new() -> Humanizer {
+        Humanizer::default()
+    }
+
+    /// Sets the threshold of given unit, i.e. the smallest value which is instead expressed
+    /// in the next larger unit. The threshold of years is ignored.
+    pub fn with_threshold(mut self, unit: TimeUnit, threshold: i64) -> Humanizer {
+        if unit != TimeUnit::Year {
+            self.thresholds[unit as usize] = threshold;
+        }
+        self
+    }
+
+    /// Sets the smallest unit used. Durations rounding to zero of this unit are "now",
+    /// "this minute", "today" etc.
+    pub fn with_granularity(mut self, unit: TimeUnit) -> Humanizer {
+        self.granularity = unit;
+        self
+    }
+
+    /// Sets whether to always use numbers ("1 day ago")
+    /// instead of words like "yesterday" when available.
+    pub fn with_numeric(mut self, numeric: bool) -> Humanizer {
+        self.numeric = numeric;
+        self
+    }
+
+    /// Returns the relative time of a duration, which is in the future when positive.
+    pub fn relative(&self, duration: OldDuration) -> RelativeTime {
+        let negative = duration < OldDuration::zero();
+        let (secs, nanos) = match duration.num_nanoseconds() {
+            Some(nanos) => (nanos / 1_000_000_000, nanos % 1_000_000_000),
+            None => (duration.num_seconds(), 0),
+        };
+        let (secs, nanos) = (secs.abs(), nanos.abs());
+        let mut unit = self.granularity;
+        let mut value = 0;
+        for &u in &TimeUnit::ALL[self.granularity as usize..] {
+            unit = u;
+            // rounded half up
+            let unit_secs = u.seconds();
+            value = secs / unit_secs;
+            let rem = (secs % unit_secs) * 2 + if nanos >= 500_000_000 { 1 } else { 0 };
+            if rem >= unit_secs {
+                value += 1;
+            }
+            if u == TimeUnit::Year || value < self.thresholds[u as usize] {
+                break;
+            }
+        }
+        RelativeTime {
+            value: if negative { -value } else { value },
+            unit: unit,
+            numeric: self.numeric,
+        }
+    }
+
+    /// Returns the relative time of `dt` as seen from `base` (usually the current time).
+    pub fn between<Tz: TimeZone, Tz2: TimeZone>(
+        &self,
+        dt: &DateTime<Tz>,
+        base: &DateTime<Tz2>,
+    ) -> RelativeTime {
+        self.relative(dt.clone().signed_duration_since(base.clone()))
+    }
+}
+
+impl Default for Humanizer {
+    fn default() -> Humanizer {
+        Humanizer {
+            thresholds: [45, 45, 22, 7, 4, 11],
+            granularity: TimeUnit::Second,
+            numeric: false,
+        }
+    }
+}
+
+/// A relative time, i.e. a whole number of units in the past or the future.
+///
+/// Displays as English text; see also `format_localized` with the `unstable-locales` feature.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct RelativeTime {
+    value: i64,
+    unit: TimeUnit,
+    numeric: bool,
+}
+
+impl RelativeTime {
+    /// Makes a new relative time of given number of units, in the past if negative.
+    pub fn new(value: i64, unit: TimeUnit) -> RelativeTime {
+        RelativeTime { value: value, unit: unit, numeric: false }
+    }
+
+    /// Returns the number of units, which is negative in the past.
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    /// Returns the unit.
+    pub fn unit(&self) -> TimeUnit {
+        self.unit
+    }
+
+    /// Returns the relative time as text in the language of given locale.
+    ///
+    /// The supported languages are German (`de_*` locales), Spanish (`es_*`), French (`fr_*`),
+    /// Japanese (`ja_JP`) and Russian (`ru_*`).
+    /// Every other locale falls back to English.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::humanize::{RelativeTime, TimeUnit};
+    /// use chrono::Locale;
+    ///
+    /// assert_eq!(RelativeTime::new(-1, TimeUnit::Day).format_localized(Locale::de_DE), "gestern");
+    /// assert_eq!(RelativeTime::new(3, TimeUnit::Week).format_localized(Locale::fr_FR),
+    ///            "dans 3 semaines");
+    /// assert_eq!(RelativeTime::new(-5, TimeUnit::Hour).format_localized(Locale::ru_RU),
+    ///            "5 часов назад");
+    /// ```
+    #[cfg(feature = "unstable-locales")]
+    pub fn format_localized(&self, locale: Locale) -> String {
+        let mut result = String::new();
+        let _ = self.write(&mut result, Language::of(locale));
+        result
+    }
+
+    fn write<W: fmt::Write>(&self, w: &mut W, lang: &Language) -> fmt::Result {
+        let unit = self.unit as usize;
+        if !self.numeric && self.value >= -2 && self.value <= 2 {
+            let word = lang.relative[unit][(self.value + 2) as usize];
+            if !word.is_empty() {
+                return w.write_str(word);
+            }
+        }
+        let n = if self.value < 0 { (self.value as u64).wrapping_neg() } else { self.value as u64 };
+        let patterns = if self.value < 0 { &lang.past[unit] } else { &lang.future[unit] };
+        let pattern = patterns[(lang.plural)(n) as usize];
+        let mut parts = pattern.splitn(2, "{0}");
+        w.write_str(parts.next().unwrap_or(""))?;
+        write!(w, "{}", n)?;
+        w.write_str(parts.next().unwrap_or(""))
+    }
+}
+
+impl fmt::Display for RelativeTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, &ENGLISH)
+    }
+}
+
+/// CLDR plural categories, as indices into the patterns.
+///
+/// `Few` and `Many` are only used by the languages of `unstable-locales`.
+#[cfg_attr(not(feature = "unstable-locales"), allow(dead_code))]
+#[derive(Copy, Clone)]
+enum Plural {
+    One = 0,
+    Few = 1,
+    Many = 2,
+    Other = 3,
+}
+
+/// The relative time patterns of a language, indexed by `TimeUnit`.
+struct Language {
+    plural: fn(u64) -> Plural,
+    /// patterns by plural category, with `{0}` for the number
+    future: [[&'static str; 4]; 7],
+    past: [[&'static str; 4]; 7],
+    /// words for the values from -2 to 2, empty if none
+    relative: [[&'static str; 5]; 7],
+}
+
+#[cfg(feature = "unstable-locales")]
+impl Language {
+    fn of(locale: Locale) -> &'static Language {
+        use self::Locale::*;
+        match locale {
+            de_AT | de_AT_euro | de_BE | de_BE_euro | de_CH | de_DE | de_DE_euro | de_IT
+            | de_LI | de_LU | de_LU_euro => &GERMAN,
+            es_AR | es_BO | es_CL | es_CO | es_CR | es_CU | es_DO | es_EC | es_ES | es_ES_euro
+            | es_GT | es_HN | es_MX | es_NI | es_PA | es_PE | es_PR | es_PY | es_SV | es_US
+            | es_UY | es_VE => &SPANISH,
+            fr_BE | fr_BE_euro | fr_CA | fr_CH | fr_FR | fr_FR_euro | fr_LU | fr_LU_euro => &FRENCH,
+            ja_JP => &JAPANESE,
+            ru_RU | ru_UA => &RUSSIAN,
+            _ => &ENGLISH,
+        }
+    }
+}
+
+fn plural_one_other(n: u64) -> Plural {
+    if n == 1 {
+        Plural::One
+    } else {
+        Plural::Other
+    }
+}
+
+macro_rules! one_other {
+    ($one:expr, $other:expr) => {
+        [$one, $other, $other, $other]
+    };
+}
+
+static ENGLISH: Language = Language {
+    plural: plural_one_other,
+    future: [
+        one_other!("in {0} second", "in {0} seconds"),
+        one_other!("in {0} minute", "in {0} minutes"),
+        one_other!("in {0} hour", "in {0} hours"),
+        one_other!("in {0} day", "in {0} days"),
+        one_other!("in {0} week", "in {0} weeks"),
+        one_other!("in {0} month", "in {0} months"),
+        one_other!("in {0} year", "in {0} years"),
+    ],
+    past: [
+        one_other!("{0} second ago", "{0} seconds ago"),
+        one_other!("{0} minute ago", "{0} minutes ago"),
+        one_other!("{0} hour ago", "{0} hours ago"),
+        one_other!("{0} day ago", "{0} days ago"),
+        one_other!("{0} week ago", "{0} weeks ago"),
+        one_other!("{0} month ago", "{0} months ago"),
+        one_other!("{0} year ago", "{0} years ago"),
+    ],
+    relative: [
+        ["", "", "now", "", ""],
+        ["", "", "this minute", "", ""],
+        ["", "", "this hour", "", ""],
+        ["", "yesterday", "today", "tomorrow", ""],
+        ["", "last week", "this week", "next week", ""],
+        ["", "last month", "this month", "next month", ""],
+        ["", "last year", "this year", "next year", ""],
+    ],
+};
+
+#[cfg(feature = "unstable-locales")]
+static GERMAN: Language = Language {
+    plural: plural_one_other,
+    future: [
+        one_other!("in {0} Sekunde", "in {0} Sekunden"),
+        one_other!("in {0} Minute", "in {0} Minuten"),
+        one_other!("in {0} Stunde", "in {0} Stunden"),
+        one_other!("in {0} Tag", "in {0} Tagen"),
+        one_other!("in {0} Woche", "in {0} Wochen"),
+        one_other!("in {0} Monat", "in {0} Monaten"),
+        one_other!("in {0} Jahr", "in {0} Jahren"),
+    ],
+    past: [
+        one_other!("vor {0} Sekunde", "vor {0} Sekunden"),
+        one_other!("vor {0} Minute", "vor {0} Minuten"),
+        one_other!("vor {0} Stunde", "vor {0} Stunden"),
+        one_other!("vor {0} Tag", "vor {0} Tagen"),
+        one_other!("vor {0} Woche", "vor {0} Wochen"),
+        one_other!("vor {0} Monat", "vor {0} Monaten"),
+        one_other!("vor {0} Jahr", "vor {0} Jahren"),
+    ],
+    relative: [
+        ["", "", "jetzt", "", ""],
+        ["", "", "in dieser Minute", "", ""],
+        ["", "", "in dieser Stunde", "", ""],
+        ["vorgestern", "gestern", "heute", "morgen", "übermorgen"],
+        ["", "letzte Woche", "diese Woche", "nächste Woche", ""],
+        ["", "letzten Monat", "diesen Monat", "nächsten Monat", ""],
+        ["", "letztes Jahr", "dieses Jahr", "nächstes Jahr", ""],
+    ],
+};
+
+#[cfg(feature = "unstable-locales")]
+static SPANISH: Language = Language {
+    plural: plural_one_other,
+    future: [
+        one_other!("dentro de {0} segundo", "dentro de {0} segundos"),
+        one_other!("dentro de {0} minuto", "dentro de {0} minutos"),
+        one_other!("dentro de {0} hora", "dentro de {0} horas"),
+        one_other!("dentro de {0} día", "dentro de {0} días"),
+        one_other!("dentro de {0} semana", "dentro de {0} semanas"),
+        one_other!("dentro de {0} mes", "dentro de {0} meses"),
+        one_other!("dentro de {0} año", "dentro de {0} años"),
+    ],
+    past: [
+        one_other!("hace {0} segundo", "hace {0} segundos"),
+        one_other!("hace {0} minuto", "hace {0} minutos"),
+        one_other!("hace {0} hora", "hace {0} horas"),
+        one_other!("hace {0} día", "hace {0} días"),
+        one_other!("hace {0} semana", "hace {0} semanas"),
+        one_other!("hace {0} mes", "hace {0} meses"),
+        one_other!("hace {0} año", "hace {0} años"),
+    ],
+    relative: [
+        ["", "", "ahora", "", ""],
+        ["", "", "este minuto", "", ""],
+        ["", "", "esta hora", "", ""],
+        ["anteayer", "ayer", "hoy", "mañana", "pasado mañana"],
+        ["", "la semana pasada", "esta semana", "la próxima semana", ""],
+        ["", "el mes pasado", "este mes", "el próximo mes", ""],
+        ["", "el año pasado", "este año", "el próximo año", ""],
+    ],
+};
+
+/// French uses the singular for 0 and 1.
+#[cfg(feature = "unstable-locales")]
+fn plural_french(n: u64) -> Plural {
+    if n <= 1 {
+        Plural::One
+    } else {
+        Plural::Other
+    }
+}
+
+#[cfg(feature = "unstable-locales")]
+static FRENCH: Language = Language {
+    plural: plural_french,
+    future: [
+        one_other!("dans {0} seconde", "dans {0} secondes"),
+        one_other!("dans {0} minute", "dans {0} minutes"),
+        one_other!("dans {0} heure", "dans {0} heures"),
+        one_other!("dans {0} jour", "dans {0} jours"),
+        one_other!("dans {0} semaine", "dans {0} semaines"),
+        one_other!("dans {0} mois", "dans {0} mois"),
+        one_other!("dans {0} an", "dans {0} ans"),
+    ],
+    past: [
+        one_other!("il y a {0} seconde", "il y a {0} secondes"),
+        one_other!("il y a {0} minute", "il y a {0} minutes"),
+        one_other!("il y a {0} heure", "il y a {0} heures"),
+        one_other!("il y a {0} jour", "il y a {0} jours"),
+        one_other!("il y a {0} semaine", "il y a {0} semaines"),
+        one_other!("il y a {0} mois", "il y a {0} mois"),
+        one_other!("il y a {0} an", "il y a {0} ans"),
+    ],
+    relative: [
+        ["", "", "maintenant", "", ""],
+        ["", "", "cette minute-ci", "", ""],
+        ["", "", "cette heure-ci", "", ""],
+        ["avant-hier", "hier", "aujourd’hui", "demain", "après-demain"],
+        ["", "la semaine dernière", "cette semaine", "la semaine prochaine", ""],
+        ["", "le mois dernier", "ce mois-ci", "le mois prochain", ""],
+        ["", "l’année dernière", "cette année", "l’année prochaine", ""],
+    ],
+};
+
+#[cfg(feature = "unstable-locales")]
+fn plural_other(_: u64) -> Plural {
+    Plural::Other
+}
+
+#[cfg(feature = "unstable-locales")]
+static JAPANESE: Language = Language {
+    plural: plural_other,
+    future: [
+        one_other!("{0} 秒後", "{0} 秒後"),
+        one_other!("{0} 分後", "{0} 分後"),
+        one_other!("{0} 時間後", "{0} 時間後"),
+        one_other!("{0} 日後", "{0} 日後"),
+        one_other!("{0} 週間後", "{0} 週間後"),
+        one_other!("{0} か月後", "{0} か月後"),
+        one_other!("{0} 年後", "{0} 年後"),
+    ],
+    past: [
+        one_other!("{0} 秒前", "{0} 秒前"),
+        one_other!("{0} 分前", "{0} 分前"),
+        one_other!("{0} 時間前", "{0} 時間前"),
+        one_other!("{0} 日前", "{0} 日前"),
+        one_other!("{0} 週間前", "{0} 週間前"),
+        one_other!("{0} か月前", "{0} か月前"),
+        one_other!("{0} 年前", "{0} 年前"),
+    ],
+    relative: [
+        ["", "", "今", "", ""],
+        ["", "", "1 分以内", "", ""],
+        ["", "", "1 時間以内", "", ""],
+        ["一昨日", "昨日", "今日", "明日", "明後日"],
+        ["", "先週", "今週", "来週", ""],
+        ["", "先月", "今月", "来月", ""],
+        ["", "昨年", "今年", "来年", ""],
+    ],
+};
+
+/// Russian distinguishes one (1, 21, ...), few (2-4, 22-24, ...) and many (0, 5-20, 25-30, ...)
+/// for integers.
+#[cfg(feature = "unstable-locales")]
+fn plural_russian(n: u64) -> Plural {
+    match (n % 10, n % 100) {
+        (1, r) if r != 11 => Plural::One,
+        (2..=4, r) if !(12..=14).contains(&r) => Plural::Few,
+        _ => Plural::Many,
+    }
+}
+
+#[cfg(feature = "unstable-locales")]
+static RUSSIAN: Language = Language {
+    plural: plural_russian,
+    future: [
+        ["через {0} секунду", "через {0} секунды", "через {0} секунд", "через {0} секунды"],
+        ["через {0} минуту", "через {0} минуты", "через {0} минут", "через {0} минуты"],
+        ["через {0} час", "через {0} часа", "через {0} часов", "через {0} часа"],
+        ["через {0} день", "через {0} дня", "через {0} дней", "через {0} дня"],
+        ["через {0} неделю", "через {0} недели", "через {0} недель", "через {0} недели"],
+        ["через {0} месяц", "через {0} месяца", "через {0} месяцев", "через {0} месяца"],
+        ["через {0} год", "через {0} года", "через {0} лет", "через {0} года"],
+    ],
+    past: [
+        ["{0} секунду назад", "{0} секунды назад", "{0} секунд назад", "{0} секунды назад"],
+        ["{0} минуту назад", "{0} минуты назад", "{0} минут назад", "{0} минуты назад"],
+        ["{0} час назад", "{0} часа назад", "{0} часов назад", "{0} часа назад"],
+        ["{0} день назад", "{0} дня назад", "{0} дней назад", "{0} дня назад"],
+        ["{0} неделю назад", "{0} недели назад", "{0} недель назад", "{0} недели назад"],
+        ["{0} месяц назад", "{0} месяца назад", "{0} месяцев назад", "{0} месяца назад"],
+        ["{0} год назад", "{0} года назад", "{0} лет назад", "{0} года назад"],
+    ],
+    relative: [
+        ["", "", "сейчас", "", ""],
+        ["", "", "в эту минуту", "", ""],
+        ["", "", "в этот час", "", ""],
+        ["позавчера", "вчера", "сегодня", "завтра", "послезавтра"],
+        ["", "на прошлой неделе", "на этой неделе", "на следующей неделе", ""],
+        ["", "в прошлом месяце", "в этом месяце", "в следующем месяце", ""],
+        ["", "в прошлом году", "в этом году", "в следующем году", ""],
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::{Humanizer, RelativeTime, TimeUnit};
+    use oldtime::Duration;
+
+    #[test]
+    fn test_humanizer_thresholds() {
+        let h = Humanizer::new();
+        let rel = |d: Duration| h.relative(d);
+        assert_eq!(rel(Duration::zero()), RelativeTime::new(0, TimeUnit::Second));
+        assert_eq!(rel(Duration::seconds(44)), RelativeTime::new(44, TimeUnit::Second));
+        assert_eq!(rel(Duration::seconds(45)), RelativeTime::new(1, TimeUnit::Minute));
+        assert_eq!(rel(Duration::milliseconds(-44_600)), RelativeTime::new(-1, TimeUnit::Minute));
+        assert_eq!(rel(Duration::minutes(-89)), RelativeTime::new(-1, TimeUnit::Hour));
+        assert_eq!(rel(Duration::minutes(90)), RelativeTime::new(2, TimeUnit::Hour));
+        assert_eq!(rel(Duration::hours(21)), RelativeTime::new(21, TimeUnit::Hour));
+        assert_eq!(rel(Duration::hours(22)), RelativeTime::new(1, TimeUnit::Day));
+        assert_eq!(rel(Duration::days(6)), RelativeTime::new(6, TimeUnit::Day));
+        assert_eq!(rel(Duration::days(-10)), RelativeTime::new(-1, TimeUnit::Week));
+        assert_eq!(rel(Duration::days(27)), RelativeTime::new(1, TimeUnit::Month));
+        assert_eq!(rel(Duration::days(320)), RelativeTime::new(1, TimeUnit::Year));
+        assert_eq!(rel(Duration::days(-3650)), RelativeTime::new(-10, TimeUnit::Year));
+        assert_eq!(rel(Duration::max_value()).unit(), TimeUnit::Year);
+
+        let h = Humanizer::new()
+            .with_granularity(TimeUnit::Day)
+            .with_threshold(TimeUnit::Day, 30)
+            .with_threshold(TimeUnit::Year, 1);
+        assert_eq!(h.relative(Duration::hours(11)).to_string(), "today");
+        assert_eq!(h.relative(Duration::hours(12)).to_string(), "tomorrow");
+        assert_eq!(h.relative(Duration::days(-29)).to_string(), "29 days ago");
+        assert_eq!(h.relative(Duration::days(-30)).to_string(), "last month");
+    }
+
+    #[test]
+    fn test_relative_time_display() {
+        let text = |value, unit| RelativeTime::new(value, unit).to_string();
+        assert_eq!(text(0, TimeUnit::Second), "now");
+        assert_eq!(text(1, TimeUnit::Second), "in 1 second");
+        assert_eq!(text(-30, TimeUnit::Second), "30 seconds ago");
+        assert_eq!(text(-1, TimeUnit::Hour), "1 hour ago");
+        assert_eq!(text(-2, TimeUnit::Day), "2 days ago");
+        assert_eq!(text(1, TimeUnit::Week), "next week");
+        assert_eq!(text(-1, TimeUnit::Year), "last year");
+        assert_eq!(text(5, TimeUnit::Year), "in 5 years");
+
+        let numeric = Humanizer::new().with_numeric(true).with_granularity(TimeUnit::Week);
+        assert_eq!(numeric.relative(Duration::weeks(-1)).to_string(), "1 week ago");
+        assert_eq!(numeric.relative(Duration::days(3)).to_string(), "in 0 weeks");
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-locales")]
+    fn test_relative_time_localized() {
+        use format::Locale;
+
+        let text = |value, unit, locale| RelativeTime::new(value, unit).format_localized(locale);
+        assert_eq!(text(2, TimeUnit::Day, Locale::de_AT), "übermorgen");
+        assert_eq!(text(1, TimeUnit::Year, Locale::de_DE), "nächstes Jahr");
+        assert_eq!(text(-3, TimeUnit::Month, Locale::de_DE), "vor 3 Monaten");
+        assert_eq!(text(-1, TimeUnit::Minute, Locale::es_ES), "hace 1 minuto");
+        assert_eq!(text(0, TimeUnit::Second, Locale::fr_BE), "maintenant");
+        assert_eq!(text(-1, TimeUnit::Hour, Locale::fr_FR), "il y a 1 heure");
+        assert_eq!(text(10, TimeUnit::Month, Locale::fr_FR), "dans 10 mois");
+        assert_eq!(text(-2, TimeUnit::Day, Locale::ja_JP), "一昨日");
+        assert_eq!(text(3, TimeUnit::Week, Locale::ja_JP), "3 週間後");
+        assert_eq!(text(21, TimeUnit::Day, Locale::ru_RU), "через 21 день");
+        assert_eq!(text(-3, TimeUnit::Year, Locale::ru_RU), "3 года назад");
+        assert_eq!(text(-12, TimeUnit::Year, Locale::ru_RU), "12 лет назад");
+        assert_eq!(text(-1, TimeUnit::Week, Locale::ru_RU), "на прошлой неделе");
+        assert_eq!(text(-3, TimeUnit::Week, Locale::nl_NL), "3 weeks ago");
+        // regional variants and locales with modifiers
+        assert_eq!(text(-1, TimeUnit::Minute, Locale::es_MX), "hace 1 minuto");
+        assert_eq!(text(0, TimeUnit::Second, Locale::fr_FR_euro), "maintenant");
+        assert_eq!(text(-12, TimeUnit::Year, Locale::ru_UA), "12 лет назад");
+        assert_eq!(text(-1, TimeUnit::Minute, Locale::en_US), "1 minute ago");
+    }
+}
@@ -0,0 +1,951 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Time intervals between two dates or times.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(any(feature = "alloc", feature = "std", test))]
+use core::iter::FromIterator;
+#[cfg(any(feature = "alloc", feature = "std", test))]
+use core::ops::Add;
+use core::ops::Sub;
+use core::{fmt, str};
+use oldtime::Duration as OldDuration;
+use period::Period;
+
+use format::{parse_interval, ParseError, ParseResult, INVALID, OUT_OF_RANGE};
+use naive::{NaiveDate, NaiveDateDaysIterator, NaiveDateTime};
+#[cfg(feature = "clock")]
+use offset::Local;
+use offset::{FixedOffset, TimeZone, Utc};
+use DateTime;
+
+/// A half-open interval `[start, end)`: it includes `start` but excludes `end`.
+///
+/// The interval can be built from any ordered type,
+/// but it is most useful with [`NaiveDate`](./naive/struct.NaiveDate.html),
+/// [`NaiveDateTime`](./naive/struct.NaiveDateTime.html) and [`DateTime`](./struct.DateTime.html).
+/// Half-open intervals can be empty (`start == end`) and two intervals sharing an end point
+/// are adjacent without overlapping, which makes them suitable for partitioning time.
+///
+/// The `Display` and `FromStr` implementations use the ISO 8601 interval notation:
+/// `<start>/<end>` is always printed,
+/// while `<start>/<duration>` and `<duration>/<end>` are also accepted by the parser.
+/// The duration is parsed as a [`Period`](./struct.Period.html),
+/// so calendar units are added in the calendar sense.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, Interval, NaiveDate};
+///
+/// let jan = Interval::new(NaiveDate::from_ymd(2020, 1, 1), NaiveDate::from_ymd(2020, 2, 1));
+/// assert!(jan.contains(&NaiveDate::from_ymd(2020, 1, 31)));
+/// assert!(!jan.contains(&NaiveDate::from_ymd(2020, 2, 1)));
+/// assert_eq!(jan.span(), Duration::days(31));
+///
+/// assert_eq!(jan.to_string(), "2020-01-01/2020-02-01");
+/// assert_eq!("2020-01-01/P1M".parse::<Interval<NaiveDate>>(), Ok(jan));
+/// assert_eq!("P31D/2020-02-01".parse::<Interval<NaiveDate>>(), Ok(jan));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+/// A closed interval `[start, end]`: it includes both `start` and `end`.
+///
+/// Closed intervals are never empty and two intervals sharing an end point overlap.
+/// They are most natural for ranges of dates like "from March 1 to March 31".
+/// [`ClosedInterval<NaiveDate>`](#method.to_half_open) can be converted to and from
+/// an equivalent half-open [`Interval`](./struct.Interval.html).
+///
+/// The `Display` and `FromStr` implementations use the ISO 8601 interval notation
+/// as in [`Interval`](./struct.Interval.html).
+///
+/// # Example
+///
+/// ```
+/// use chrono::{ClosedInterval, Interval, NaiveDate};
+///
+/// let from_ymd = NaiveDate::from_ymd;
+///
+/// let march = ClosedInterval::new(from_ymd(2020, 3, 1), from_ymd(2020, 3, 31));
+/// assert!(march.contains(&from_ymd(2020, 3, 31)));
+/// assert_eq!(march.to_half_open(), Some(Interval::new(from_ymd(2020, 3, 1), from_ymd(2020, 4, 1))));
+/// assert_eq!(march.to_string(), "2020-03-01/2020-03-31");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ClosedInterval<T> {
+    start: T,
+    end: T,
+}
+
+#[inline]
+fn min_of<T: PartialOrd + Clone>(a: &T, b: &T) -> T {
+    if b < a {
+        b.clone()
+    } else {
+        a.clone()
+    }
+}
+
+#[inline]
+fn max_of<T: PartialOrd + Clone>(a: &T, b: &T) -> T {
+    if b > a {
+        b.clone()
+    } else {
+        a.clone()
+    }
+}
+
+impl<T: PartialOrd + Clone> Interval<T> {
+    /// Makes a new `Interval` from `start` (inclusive) to `end` (exclusive).
+    ///
+    /// Panics if `start` is later than `end`.
+    #[inline]
+    pub fn new(start: T, end: T) -> Interval<T> {
+        Interval::new_opt(start, end).expect("interval start is later than its end")
+    }
+
+    /// Makes a new `Interval` from `start` (inclusive) to `end` (exclusive).
+    ///
+    /// Returns `None` if `start` is later than `end`.
+    #[inline]
+    pub fn new_opt(start: T, end: T) -> Option<Interval<T>> {
+        if start <= end {
+            Some(Interval { start: start, end: end })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the (inclusive) start of the interval.
+    #[inline]
+    pub fn start(&self) -> &T {
+        &self.start
+    }
+
+    /// Returns the (exclusive) end of the interval.
+    #[inline]
+    pub fn end(&self) -> &T {
+        &self.end
+    }
+
+    /// Returns `true` if the interval does not contain any value, i.e. `start == end`.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns `true` if the interval contains given value.
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool {
+        self.start <= *value && *value < self.end
+    }
+
+    /// Returns `true` if the interval contains every value of the other interval.
+    ///
+    /// An empty interval is contained in any interval which contains or ends at its position.
+    #[inline]
+    pub fn contains_interval(&self, other: &Interval<T>) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Returns `true` if both intervals have at least one value in common.
+    ///
+    /// Adjacent intervals, where one ends when the other starts, do *not* overlap,
+    /// and an empty interval never overlaps with anything.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Interval, NaiveDate};
+    ///
+    /// let d = |day| NaiveDate::from_ymd(2020, 1, day);
+    /// assert!(Interval::new(d(1), d(10)).overlaps(&Interval::new(d(9), d(20))));
+    /// assert!(!Interval::new(d(1), d(10)).overlaps(&Interval::new(d(10), d(20))));
+    /// ```
+    #[inline]
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.start < other.end && other.start < self.end && !self.is_empty() && !other.is_empty()
+    }
+
+    /// Returns `true` if one interval ends exactly when the other starts.
+    #[inline]
+    pub fn is_adjacent(&self, other: &Interval<T>) -> bool {
+        self.end == other.start || other.end == self.start
+    }
+
+    /// Returns the interval of values contained in both intervals,
+    /// or `None` if the intervals do not overlap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Interval, NaiveDate};
+    ///
+    /// let d = |day| NaiveDate::from_ymd(2020, 1, day);
+    /// assert_eq!(Interval::new(d(1), d(10)).intersection(&Interval::new(d(5), d(20))),
+    ///            Some(Interval::new(d(5), d(10))));
+    /// assert_eq!(Interval::new(d(1), d(10)).intersection(&Interval::new(d(10), d(20))), None);
+    /// ```
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.overlaps(other) {
+            Some(Interval {
+                start: max_of(&self.start, &other.start),
+                end: min_of(&self.end, &other.end),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the interval of values contained in either interval,
+    /// or `None` if the intervals neither overlap nor are adjacent
+    /// (as the union would not be a single interval).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Interval, NaiveDate};
+    ///
+    /// let d = |day| NaiveDate::from_ymd(2020, 1, day);
+    /// assert_eq!(Interval::new(d(1), d(10)).union(&Interval::new(d(10), d(20))),
+    ///            Some(Interval::new(d(1), d(20))));
+    /// assert_eq!(Interval::new(d(1), d(10)).union(&Interval::new(d(11), d(20))), None);
+    /// ```
+    pub fn union(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.start <= other.end && other.start <= self.end {
+            Some(Interval {
+                start: min_of(&self.start, &other.start),
+                end: max_of(&self.end, &other.end),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Splits the interval into `[start, at)` and `[at, end)`.
+    ///
+    /// Returns `None` if `at` is not strictly within the interval,
+    /// i.e. when one of the resulting intervals would be empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Interval, NaiveDate};
+    ///
+    /// let d = |day| NaiveDate::from_ymd(2020, 1, day);
+    /// assert_eq!(Interval::new(d(1), d(10)).split_at(d(4)),
+    ///            Some((Interval::new(d(1), d(4)), Interval::new(d(4), d(10)))));
+    /// assert_eq!(Interval::new(d(1), d(10)).split_at(d(1)), None);
+    /// ```
+    pub fn split_at(&self, at: T) -> Option<(Interval<T>, Interval<T>)> {
+        if self.start < at && at < self.end {
+            Some((
+                Interval { start: self.start.clone(), end: at.clone() },
+                Interval { start: at, end: self.end.clone() },
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the length of the interval, i.e. `end - start`.
+    #[inline]
+    pub fn span(&self) -> OldDuration
+    where
+        T: Sub<T, Output = OldDuration>,
+    {
+        self.end.clone() - self.start.clone()
+    }
+}
+
+impl<T: PartialOrd + Clone> ClosedInterval<T> {
+    /// Makes a new `ClosedInterval` from `start` to `end`, both inclusive.
+    ///
+    /// Panics if `start` is later than `end`.
+    #[inline]
+    pub fn new(start: T, end: T) -> ClosedInterval<T> {
+        ClosedInterval::new_opt(start, end).expect("interval start is later than its end")
+    }
+
+    /// Makes a new `ClosedInterval` from `start` to `end`, both inclusive.
+    ///
+    /// Returns `None` if `start` is later than `end`.
+    #[inline]
+    pub fn new_opt(start: T, end: T) -> Option<ClosedInterval<T>> {
+        if start <= end {
+            Some(ClosedInterval { start: start, end: end })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the (inclusive) start of the interval.
+    #[inline]
+    pub fn start(&self) -> &T {
+        &self.start
+    }
+
+    /// Returns the (inclusive) end of the interval.
+    #[inline]
+    pub fn end(&self) -> &T {
+        &self.end
+    }
+
+    /// Returns `true` if the interval contains given value.
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool {
+        self.start <= *value && *value <= self.end
+    }
+
+    /// Returns `true` if the interval contains every value of the other interval.
+    #[inline]
+    pub fn contains_interval(&self, other: &ClosedInterval<T>) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Returns `true` if both intervals have at least one value in common.
+    ///
+    /// Intervals sharing only an end point do overlap.
+    #[inline]
+    pub fn overlaps(&self, other: &ClosedInterval<T>) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Returns the interval of values contained in both intervals,
+    /// or `None` if the intervals do not overlap.
+    pub fn intersection(&self, other: &ClosedInterval<T>) -> Option<ClosedInterval<T>> {
+        if self.overlaps(other) {
+            Some(ClosedInterval {
+                start: max_of(&self.start, &other.start),
+                end: min_of(&self.end, &other.end),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the interval of values contained in either interval,
+    /// or `None` if the intervals do not overlap.
+    ///
+    /// For dates, intervals with consecutive days like `[1, 3]` and `[4, 6]`
+    /// can be joined by converting them to half-open intervals first.
+    pub fn union(&self, other: &ClosedInterval<T>) -> Option<ClosedInterval<T>> {
+        if self.overlaps(other) {
+            Some(ClosedInterval {
+                start: min_of(&self.start, &other.start),
+                end: max_of(&self.end, &other.end),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Splits the interval into `[start, at]` and `[at, end]`.
+    /// Both resulting intervals contain `at`.
+    ///
+    /// Returns `None` if `at` is not within the interval.
+    pub fn split_at(&self, at: T) -> Option<(ClosedInterval<T>, ClosedInterval<T>)> {
+        if self.contains(&at) {
+            Some((
+                ClosedInterval { start: self.start.clone(), end: at.clone() },
+                ClosedInterval { start: at, end: self.end.clone() },
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the length of the interval, i.e. `end - start`.
+    ///
+    /// Note that for dates this is one day less than the number of days in the interval.
+    #[inline]
+    pub fn span(&self) -> OldDuration
+    where
+        T: Sub<T, Output = OldDuration>,
+    {
+        self.end.clone() - self.start.clone()
+    }
+}
+
+impl Interval<NaiveDate> {
+    /// Converts the interval to a `ClosedInterval` containing the same days.
+    ///
+    /// Returns `None` if the interval is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{ClosedInterval, Interval, NaiveDate};
+    ///
+    /// let d = |day| NaiveDate::from_ymd(2020, 1, day);
+    /// assert_eq!(Interval::new(d(1), d(10)).to_closed(), Some(ClosedInterval::new(d(1), d(9))));
+    /// assert_eq!(Interval::new(d(1), d(1)).to_closed(), None);
+    /// ```
+    pub fn to_closed(&self) -> Option<ClosedInterval<NaiveDate>> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(ClosedInterval { start: self.start, end: self.end.pred() })
+    }
+
+    /// Returns an iterator over the days in the interval, like `start..end`.
+    ///
+    /// The iterator is double-ended and knows its exact length.
+    /// Iterating over the interval itself does the same.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Interval, NaiveDate};
+    ///
+    /// let d = |day| NaiveDate::from_ymd(2020, 1, day);
+    /// let interval = Interval::new(d(1), d(4));
+    /// assert_eq!(interval.iter_days().collect::<Vec<_>>(), [d(1), d(2), d(3)]);
+    /// assert_eq!(interval.iter_days().rev().collect::<Vec<_>>(), [d(3), d(2), d(1)]);
+    /// assert_eq!(interval.iter_days().step_by(2).collect::<Vec<_>>(), [d(1), d(3)]);
+    /// for day in interval {
+    ///     assert!(interval.contains(&day));
+    /// }
+    /// ```
+    pub fn iter_days(&self) -> NaiveDateDaysIterator {
+        NaiveDateDaysIterator::new(self.start, (self.end - self.start).num_days())
+    }
+}
+
+impl IntoIterator for Interval<NaiveDate> {
+    type Item = NaiveDate;
+    type IntoIter = NaiveDateDaysIterator;
+
+    fn into_iter(self) -> NaiveDateDaysIterator {
+        self.iter_days()
+    }
+}
+
+impl ClosedInterval<NaiveDate> {
+    /// Converts the interval to a half-open `Interval` containing the same days.
+    ///
+    /// Returns `None` if the interval ends on the last representable date.
+    pub fn to_half_open(&self) -> Option<Interval<NaiveDate>> {
+        Some(Interval { start: self.start, end: try_opt!(self.end.succ_opt()) })
+    }
+
+    /// Returns an iterator over the days in the interval, like `start..=end`.
+    ///
+    /// The iterator is double-ended and knows its exact length.
+    /// Iterating over the interval itself does the same.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{ClosedInterval, NaiveDate};
+    ///
+    /// let d = |day| NaiveDate::from_ymd(2020, 1, day);
+    /// let interval = ClosedInterval::new(d(1), d(3));
+    /// assert_eq!(interval.iter_days().len(), 3);
+    /// assert_eq!(interval.into_iter().collect::<Vec<_>>(), [d(1), d(2), d(3)]);
+    /// ```
+    pub fn iter_days(&self) -> NaiveDateDaysIterator {
+        NaiveDateDaysIterator::new(self.start, (self.end - self.start).num_days() + 1)
+    }
+}
+
+impl IntoIterator for ClosedInterval<NaiveDate> {
+    type Item = NaiveDate;
+    type IntoIter = NaiveDateDaysIterator;
+
+    fn into_iter(self) -> NaiveDateDaysIterator {
+        self.iter_days()
+    }
+}
+
+/// A set of values represented as a sorted list of disjoint half-open intervals.
+///
+/// The set is always kept normalized:
+/// intervals are sorted, non-empty and neither overlap nor touch each other,
+/// so two sets containing the same values compare equal.
+/// This is convenient for computing availability from many busy blocks.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, Interval, IntervalSet, TimeZone, Utc};
+///
+/// let at = |h, m| Utc.ymd(2020, 6, 1).and_hms(h, m, 0);
+/// let busy: IntervalSet<_> = vec![
+///     Interval::new(at(9, 0), at(10, 0)),  // alice
+///     Interval::new(at(9, 30), at(11, 0)), // bob
+///     Interval::new(at(11, 30), at(12, 0)), // alice
+/// ].into_iter().collect();
+/// assert_eq!(busy.intervals(), &[Interval::new(at(9, 0), at(11, 0)),
+///                                Interval::new(at(11, 30), at(12, 0))]);
+///
+/// let workday = Interval::new(at(9, 0), at(17, 0));
+/// assert_eq!(busy.first_free_slot(&workday, Duration::minutes(30)),
+///            Some(Interval::new(at(11, 0), at(11, 30))));
+/// assert_eq!(busy.first_free_slot(&workday, Duration::hours(1)),
+///            Some(Interval::new(at(12, 0), at(13, 0))));
+/// ```
+#[cfg(any(feature = "alloc", feature = "std", test))]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+#[cfg(any(feature = "alloc", feature = "std", test))]
+impl<T: PartialOrd + Clone> IntervalSet<T> {
+    /// Makes a new empty `IntervalSet`.
+    #[inline]
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { intervals: Vec::new() }
+    }
+
+    /// Returns the disjoint intervals of the set in ascending order.
+    #[inline]
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    /// Returns `true` if the set does not contain any value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns `true` if the set contains given value.
+    pub fn contains(&self, value: &T) -> bool {
+        self.intervals.iter().any(|i| i.contains(value))
+    }
+
+    /// Returns `true` if the set contains every value of given interval.
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        interval.is_empty() || self.intervals.iter().any(|i| i.contains_interval(interval))
+    }
+
+    /// Returns `true` if the set has at least one value in common with given interval.
+    pub fn overlaps(&self, interval: &Interval<T>) -> bool {
+        self.intervals.iter().any(|i| i.overlaps(interval))
+    }
+
+    /// Adds every value of given interval to the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Interval, IntervalSet, NaiveDate};
+    ///
+    /// let d = |day| NaiveDate::from_ymd(2020, 1, day);
+    /// let mut set = IntervalSet::new();
+    /// set.insert(Interval::new(d(1), d(3)));
+    /// set.insert(Interval::new(d(5), d(7)));
+    /// set.insert(Interval::new(d(3), d(5)));
+    /// assert_eq!(set.intervals(), &[Interval::new(d(1), d(7))]);
+    /// ```
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        // the first interval ending at or after the new one starts
+        let first = self.intervals.iter().position(|i| interval.start <= i.end);
+        let first = match first {
+            Some(first) => first,
+            None => return self.intervals.push(interval),
+        };
+        // the first interval starting after the new one ends, i.e. not merged with it
+        let last = self.intervals[first..]
+            .iter()
+            .position(|i| interval.end < i.start)
+            .map_or(self.intervals.len(), |n| first + n);
+        let merged = if first < last {
+            Interval {
+                start: min_of(&self.intervals[first].start, &interval.start),
+                end: max_of(&self.intervals[last - 1].end, &interval.end),
+            }
+        } else {
+            interval
+        };
+        self.intervals.drain(first..last);
+        self.intervals.insert(first, merged);
+    }
+
+    /// Removes every value of given interval from the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Interval, IntervalSet, NaiveDate};
+    ///
+    /// let d = |day| NaiveDate::from_ymd(2020, 1, day);
+    /// let mut set = IntervalSet::new();
+    /// set.insert(Interval::new(d(1), d(10)));
+    /// set.remove(Interval::new(d(3), d(5)));
+    /// assert_eq!(set.intervals(), &[Interval::new(d(1), d(3)), Interval::new(d(5), d(10))]);
+    /// ```
+    pub fn remove(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+        for i in self.intervals.drain(..) {
+            if !i.overlaps(&interval) {
+                intervals.push(i);
+                continue;
+            }
+            if i.start < interval.start {
+                intervals.push(Interval { start: i.start.clone(), end: interval.start.clone() });
+            }
+            if interval.end < i.end {
+                intervals.push(Interval { start: interval.end.clone(), end: i.end });
+            }
+        }
+        self.intervals = intervals;
+    }
+
+    /// Returns a set containing the values in either set.
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut set = self.clone();
+        for i in &other.intervals {
+            set.insert(i.clone());
+        }
+        set
+    }
+
+    /// Returns a set containing the values in both sets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Interval, IntervalSet, NaiveDate};
+    ///
+    /// let d = |day| NaiveDate::from_ymd(2020, 1, day);
+    /// let a: IntervalSet<_> =
+    ///     vec![Interval::new(d(1), d(5)), Interval::new(d(8), d(12))].into_iter().collect();
+    /// let b: IntervalSet<_> = vec![Interval::new(d(4), d(10))].into_iter().collect();
+    /// assert_eq!(a.intersection(&b).intervals(),
+    ///            &[Interval::new(d(4), d(5)), Interval::new(d(8), d(10))]);
+    /// ```
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = Vec::new();
+        let (mut a, mut b) = (self.intervals.iter().peekable(), other.intervals.iter().peekable());
+        loop {
+            let a_ends_first = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) => {
+                    if let Some(i) = x.intersection(y) {
+                        intervals.push(i);
+                    }
+                    x.end < y.end
+                }
+                _ => break,
+            };
+            // advance the interval ending first, it cannot overlap anything else
+            if a_ends_first {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        IntervalSet { intervals: intervals }
+    }
+
+    /// Returns a set containing the values in `self` but not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut set = self.clone();
+        for i in &other.intervals {
+            set.remove(i.clone());
+        }
+        set
+    }
+
+    /// Returns a set containing the values within given bounds which are not in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Interval, IntervalSet, NaiveDate};
+    ///
+    /// let d = |day| NaiveDate::from_ymd(2020, 1, day);
+    /// let set: IntervalSet<_> = vec![Interval::new(d(3), d(5))].into_iter().collect();
+    /// assert_eq!(set.complement(&Interval::new(d(1), d(10))).intervals(),
+    ///            &[Interval::new(d(1), d(3)), Interval::new(d(5), d(10))]);
+    /// ```
+    pub fn complement(&self, bounds: &Interval<T>) -> IntervalSet<T> {
+        let mut set = IntervalSet::new();
+        set.insert(bounds.clone());
+        set.difference(self)
+    }
+
+    /// Returns the earliest interval of given length within `bounds`
+    /// which does not overlap with the set, or `None` if there is no such interval.
+    ///
+    /// A zero or negative `length` finds the earliest value within `bounds` not in the set.
+    pub fn first_free_slot(&self, bounds: &Interval<T>, length: OldDuration) -> Option<Interval<T>>
+    where
+        T: Add<OldDuration, Output = T> + Sub<T, Output = OldDuration>,
+    {
+        let length = if length < OldDuration::zero() { OldDuration::zero() } else { length };
+        let free = self.complement(bounds);
+        free.intervals
+            .iter()
+            .find(|i| i.span() >= length)
+            .map(|i| Interval { start: i.start.clone(), end: i.start.clone() + length })
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std", test))]
+impl<T: PartialOrd + Clone> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std", test))]
+impl<T: PartialOrd + Clone> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std", test))]
+impl<T: PartialOrd + Clone> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> IntervalSet<T> {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+// the `Debug` output of the following types is their ISO 8601 representation
+macro_rules! impl_interval_fmt {
+    ($($ty:ident),*) => ($(
+        impl fmt::Display for $ty<NaiveDate> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{:?}/{:?}", self.start, self.end)
+            }
+        }
+
+        impl fmt::Display for $ty<NaiveDateTime> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{:?}/{:?}", self.start, self.end)
+            }
+        }
+
+        impl<Tz: TimeZone> fmt::Display for $ty<DateTime<Tz>> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{:?}/{:?}", self.start, self.end)
+            }
+        }
+    )*)
+}
+
+impl_interval_fmt!(Interval, ClosedInterval);
+
+/// Adds a period from an interval string to a date.
+///
+/// Unlike `NaiveDate::checked_add_period`, this rejects periods with a time part
+/// which is not a whole number of days, as a date-only interval can't represent it.
+fn add_date_period(date: &NaiveDate, period: Period) -> ParseResult<NaiveDate> {
+    let time = period.time();
+    if time != OldDuration::days(time.num_days()) {
+        return Err(INVALID);
+    }
+    date.checked_add_period(period).ok_or(OUT_OF_RANGE)
+}
+
+macro_rules! impl_interval_from_str {
+    ($ty:ident<$inner:ty>) => {
+        impl_interval_from_str!($ty<$inner>, |v: &$inner, p| {
+            v.checked_add_period(p).ok_or(OUT_OF_RANGE)
+        });
+    };
+    ($ty:ident<$inner:ty>, $add:expr) => {
+        impl str::FromStr for $ty<$inner> {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> ParseResult<$ty<$inner>> {
+                let (start, end) = parse_interval(s, str::FromStr::from_str, $add)?;
+                Ok($ty { start: start, end: end })
+            }
+        }
+    };
+}
+
+impl_interval_from_str!(Interval<NaiveDate>, add_date_period);
+impl_interval_from_str!(Interval<NaiveDateTime>);
+impl_interval_from_str!(Interval<DateTime<Utc>>);
+impl_interval_from_str!(Interval<DateTime<FixedOffset>>);
+#[cfg(feature = "clock")]
+impl_interval_from_str!(Interval<DateTime<Local>>);
+impl_interval_from_str!(ClosedInterval<NaiveDate>, add_date_period);
+impl_interval_from_str!(ClosedInterval<NaiveDateTime>);
+impl_interval_from_str!(ClosedInterval<DateTime<Utc>>);
+impl_interval_from_str!(ClosedInterval<DateTime<FixedOffset>>);
+#[cfg(feature = "clock")]
+impl_interval_from_str!(ClosedInterval<DateTime<Local>>);
+
+#[cfg(test)]
+mod tests {
+    use super::{ClosedInterval, Interval};
+    use format::INVALID;
+    use naive::{NaiveDate, MAX_DATE};
+    use offset::{FixedOffset, TimeZone, Utc};
+    use oldtime::Duration;
+    use DateTime;
+
+    #[test]
+    fn test_interval_new() {
+        let d = |day| NaiveDate::from_ymd(2020, 1, day);
+        assert_eq!(Interval::new_opt(d(2), d(1)), None);
+        assert_eq!(ClosedInterval::new_opt(d(2), d(1)), None);
+        assert!(Interval::new(d(1), d(1)).is_empty());
+        assert!(!Interval::new(d(1), d(1)).contains(&d(1)));
+        assert!(ClosedInterval::new(d(1), d(1)).contains(&d(1)));
+    }
+
+    #[test]
+    fn test_interval_algebra() {
+        let d = |day| NaiveDate::from_ymd(2020, 1, day);
+        let i = |a, b| Interval::new(d(a), d(b));
+        assert!(i(1, 10).contains_interval(&i(1, 10)));
+        assert!(i(1, 10).contains_interval(&i(10, 10)));
+        assert!(!i(1, 10).contains_interval(&i(5, 11)));
+        assert!(i(1, 10).is_adjacent(&i(10, 12)));
+        assert!(i(10, 12).is_adjacent(&i(1, 10)));
+        assert!(!i(1, 10).overlaps(&i(5, 5)));
+        assert_eq!(i(1, 10).intersection(&i(3, 4)), Some(i(3, 4)));
+        assert_eq!(i(3, 4).intersection(&i(1, 10)), Some(i(3, 4)));
+        assert_eq!(i(1, 10).union(&i(3, 4)), Some(i(1, 10)));
+        assert_eq!(i(5, 10).union(&i(1, 5)), Some(i(1, 10)));
+        assert_eq!(i(1, 10).split_at(d(10)), None);
+
+        let c = |a, b| ClosedInterval::new(d(a), d(b));
+        assert!(c(1, 10).overlaps(&c(10, 12)));
+        assert_eq!(c(1, 10).intersection(&c(10, 12)), Some(c(10, 10)));
+        assert_eq!(c(1, 10).union(&c(10, 12)), Some(c(1, 12)));
+        assert_eq!(c(1, 3).union(&c(4, 6)), None);
+        let joined = c(1, 3).to_half_open().unwrap().union(&c(4, 6).to_half_open().unwrap());
+        assert_eq!(joined.and_then(|i| i.to_closed()), Some(c(1, 6)));
+        assert_eq!(c(1, 10).split_at(d(10)), Some((c(1, 10), c(10, 10))));
+        assert_eq!(c(1, 10).split_at(d(11)), None);
+        assert_eq!(c(1, 10).span(), Duration::days(9));
+        assert_eq!(ClosedInterval::new(d(1), MAX_DATE).to_half_open(), None);
+    }
+
+    #[test]
+    fn test_interval_set_against_bitmap() {
+        use super::IntervalSet;
+
+        // every set of values within 0..16 is checked against a bitmap of its values
+        fn to_set(bits: u16) -> IntervalSet<i32> {
+            (0..16).filter(|&v| bits & (1 << v) != 0).map(|v| Interval::new(v, v + 1)).collect()
+        }
+        fn to_bits(set: &IntervalSet<i32>) -> u16 {
+            (0..16).filter(|v| set.contains(v)).fold(0, |bits, v| bits | (1 << v))
+        }
+        fn is_normalized(set: &IntervalSet<i32>) -> bool {
+            let intervals = set.intervals();
+            intervals.iter().all(|i| !i.is_empty())
+                && intervals.windows(2).all(|w| w[0].end() < w[1].start())
+        }
+
+        let samples = [0u16, 1, 0x8000, 0xffff, 0x0ff0, 0xf00f, 0x5555, 0x3c3c, 0x1248, 0x7e01];
+        for &a in &samples {
+            let set_a = to_set(a);
+            assert!(is_normalized(&set_a));
+            assert_eq!(to_bits(&set_a), a);
+            assert_eq!(to_bits(&set_a.complement(&Interval::new(0, 16))), !a);
+            assert_eq!(to_bits(&set_a.complement(&Interval::new(4, 12))), !a & 0x0ff0);
+            for &b in &samples {
+                let set_b = to_set(b);
+                for set in
+                    &[set_a.union(&set_b), set_a.intersection(&set_b), set_a.difference(&set_b)]
+                {
+                    assert!(is_normalized(set));
+                }
+                assert_eq!(set_a.union(&set_b), to_set(a | b));
+                assert_eq!(set_a.intersection(&set_b), to_set(a & b));
+                assert_eq!(set_a.difference(&set_b), to_set(a & !b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_interval_set_free_slot() {
+        use super::IntervalSet;
+
+        let at = |h| Utc.ymd(2020, 1, 1).and_hms(h, 0, 0);
+        let i = |a, b| Interval::new(at(a), at(b));
+        let busy: IntervalSet<_> =
+            vec![i(9, 10), i(12, 13), i(11, 12), i(15, 16)].into_iter().collect();
+        assert_eq!(busy.intervals(), &[i(9, 10), i(11, 13), i(15, 16)]);
+        let find = |a, b, h| busy.first_free_slot(&i(a, b), Duration::hours(h));
+        assert_eq!(find(9, 17, 1), Some(i(10, 11)));
+        assert_eq!(find(9, 17, 2), Some(i(13, 15)));
+        assert_eq!(find(9, 17, 3), None);
+        assert_eq!(find(0, 23, 3), Some(i(0, 3)));
+        assert_eq!(find(11, 13, 0), None);
+        assert_eq!(find(11, 14, 0), Some(i(13, 13)));
+        assert_eq!(find(14, 14, 0), None);
+        assert!(busy.contains_interval(&i(11, 13)));
+        assert!(!busy.contains_interval(&i(10, 12)));
+        assert!(busy.overlaps(&i(10, 12)));
+        assert!(!busy.overlaps(&i(13, 15)));
+
+        let mut set = busy.clone();
+        set.remove(i(0, 23));
+        assert!(set.is_empty());
+        set.insert(i(5, 5));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_interval_datetime() {
+        let dt = |h| Utc.ymd(2020, 1, 1).and_hms(h, 0, 0);
+        let i = Interval::new(dt(9), dt(17));
+        assert!(i.contains(&dt(9)));
+        assert!(!i.contains(&dt(17)));
+        assert_eq!(i.span(), Duration::hours(8));
+        assert_eq!(i.to_string(), "2020-01-01T09:00:00Z/2020-01-01T17:00:00Z");
+        assert_eq!(i.to_string().parse::<Interval<DateTime<Utc>>>(), Ok(i));
+    }
+
+    #[test]
+    fn test_interval_from_str() {
+        let d = |m, day| NaiveDate::from_ymd(2020, m, day);
+        let parse = |s: &str| s.parse::<Interval<NaiveDate>>();
+        assert_eq!(parse("2020-01-31/2020-03-01"), Ok(Interval::new(d(1, 31), d(3, 1))));
+        assert_eq!(parse("2020-01-31/P1M"), Ok(Interval::new(d(1, 31), d(2, 29))));
+        assert_eq!(parse("P1M/2020-03-31"), Ok(Interval::new(d(2, 29), d(3, 31))));
+        assert_eq!(parse("P1W/2020-03-31"), Ok(Interval::new(d(3, 24), d(3, 31))));
+        assert!(parse("2020-03-01/2020-01-31").is_err());
+        assert!(parse("P1M/P1D").is_err());
+        assert!(parse("2020-01-31").is_err());
+        assert!(parse("2020-01-31/").is_err());
+        assert!(parse("2020-01-31/-P1D").is_err());
+        assert_eq!(parse("2020-01-01/PT48H"), Ok(Interval::new(d(1, 1), d(1, 3))));
+        assert_eq!(parse("2020-01-01/PT12H"), Err(INVALID));
+        assert_eq!(parse("P1DT1S/2020-01-03"), Err(INVALID));
+
+        let parse = |s: &str| s.parse::<ClosedInterval<NaiveDate>>();
+        assert_eq!(parse("2020-01-01/2020-01-01"), Ok(ClosedInterval::new(d(1, 1), d(1, 1))));
+        assert_eq!(parse("2020-01-01/PT1M"), Err(INVALID));
+
+        let kst = FixedOffset::east(9 * 3600);
+        let i = "2020-03-08T01:30:00+09:00/PT1H30M".parse::<Interval<DateTime<FixedOffset>>>();
+        let expected = Interval::new(
+            kst.ymd(2020, 3, 8).and_hms(1, 30, 0),
+            kst.ymd(2020, 3, 8).and_hms(3, 0, 0),
+        );
+        assert_eq!(i, Ok(expected));
+        assert_eq!(expected.to_string(), "2020-03-08T01:30:00+09:00/2020-03-08T03:00:00+09:00");
+        let naive = "2020-03-08T01:30:00/P1DT1H".parse::<Interval<::NaiveDateTime>>().unwrap();
+        assert_eq!(naive.span(), Duration::hours(25));
+        assert_eq!(naive.to_string(), "2020-03-08T01:30:00/2020-03-09T02:30:00");
+    }
+}
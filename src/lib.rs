@@ -38,7 +38,8 @@
 //!
 //! Default features:
 //!
-//! - `alloc`: Enable features that depend on allocation (primarily string formatting)
+//! - `alloc`: Enable features that depend on allocation (primarily string formatting;
+//!   `DelayedFormat::format_into` formats into a byte buffer without it)
 //! - `std`: Enables functionality that depends on the standard library. This
 //!   is a superset of `alloc` and adds interoperation with standard library types
 //!   and traits.
@@ -52,6 +53,8 @@
 //! - `unstable-locales`: Enable localization. This adds various methods with a
 //!   `_localized` suffix. The implementation and API may change or even be
 //!   removed in a patch release. Feedback welcome.
+//! - `format-description`: Enable the `format_description!` macro, which parses a
+//!   format string at compile time. This needs Rust 1.83 or later.
 //!
 //! [`serde`]: https://github.com/serde-rs/serde
 //! [wasm-bindgen]: https://github.com/rustwasm/wasm-bindgen
@@ -423,7 +426,7 @@
 #![deny(missing_docs)]
 #![deny(missing_debug_implementations)]
 #![deny(dead_code)]
-// lints are added all the time, we test on 1.13
+// lints are added all the time, we test on 1.31
 #![allow(unknown_lints)]
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(feature = "cargo-clippy", allow(
@@ -501,18 +504,24 @@ pub use oldtime::Duration;
 pub use date::{Date, MAX_DATE, MIN_DATE};
 #[cfg(feature = "rustc-serialize")]
 pub use datetime::rustc_serialize::TsSeconds;
-pub use datetime::{DateTime, SecondsFormat, MAX_DATETIME, MIN_DATETIME};
+pub use datetime::{
+    DateTime, DateTimeIterator, DateTimeStep, SecondsFormat, MAX_DATETIME, MIN_DATETIME,
+};
 /// L10n locales.
 #[cfg(feature = "unstable-locales")]
 pub use format::Locale;
 pub use format::{ParseError, ParseResult};
+#[cfg(any(feature = "alloc", feature = "std", test))]
+pub use interval::IntervalSet;
+pub use interval::{ClosedInterval, Interval};
 #[doc(no_inline)]
-pub use naive::{IsoWeek, NaiveDate, NaiveDateTime, NaiveTime};
+pub use naive::{IsoWeek, NaiveDate, NaiveDateTime, NaiveTime, WeekRule};
 #[cfg(feature = "clock")]
 #[doc(no_inline)]
 pub use offset::Local;
 #[doc(no_inline)]
 pub use offset::{FixedOffset, LocalResult, Offset, TimeZone, Utc};
+pub use period::Period;
 pub use round::{DurationRound, RoundingError, SubsecRound};
 
 /// A convenience module appropriate for glob imports (`use chrono::prelude::*;`).
@@ -563,14 +572,19 @@ pub mod naive {
     mod internals;
     mod isoweek;
     mod time;
+    mod week;
 
-    pub use self::date::{NaiveDate, MAX_DATE, MIN_DATE};
+    pub use self::date::{
+        MonthEndPolicy, NaiveDate, NaiveDateDaysIterator, NaiveDateMonthsIterator,
+        NaiveDateWeeksIterator, MAX_DATE, MIN_DATE,
+    };
     #[cfg(feature = "rustc-serialize")]
     #[allow(deprecated)]
     pub use self::datetime::rustc_serialize::TsSeconds;
     pub use self::datetime::{NaiveDateTime, MAX_DATETIME, MIN_DATETIME};
     pub use self::isoweek::IsoWeek;
     pub use self::time::NaiveTime;
+    pub use self::week::WeekRule;
 
     #[cfg(feature = "__internal_bench")]
     #[doc(hidden)]
@@ -589,10 +603,27 @@ pub mod naive {
         pub use super::datetime::serde::*;
     }
 }
+#[cfg(any(feature = "alloc", feature = "std", test))]
+pub mod business;
+#[cfg(feature = "serde")]
+mod compact_serde;
+pub mod cron;
 mod date;
 mod datetime;
+#[cfg(feature = "serde")]
+mod duration_serde;
+pub mod easter;
+pub mod fiscal;
 pub mod format;
+pub mod humanize;
+mod interval;
+mod period;
 mod round;
+#[cfg(any(feature = "alloc", feature = "std", test))]
+pub mod rrule;
+#[cfg(all(feature = "serde", any(feature = "alloc", feature = "std")))]
+#[macro_use]
+mod strftime_serde;
 
 #[cfg(feature = "__internal_bench")]
 #[doc(hidden)]
@@ -608,7 +639,23 @@ pub use naive::__BenchYearFlags;
 /// [2]: https://tools.ietf.org/html/rfc3339
 #[cfg(feature = "serde")]
 pub mod serde {
+    pub use super::compact_serde::compact;
     pub use super::datetime::serde::*;
+    pub use super::duration_serde::{
+        duration_iso8601, duration_iso8601_option, duration_milliseconds,
+        duration_milliseconds_option, duration_nanoseconds, duration_nanoseconds_option,
+        duration_seconds, duration_seconds_option,
+    };
+    #[cfg(any(feature = "alloc", feature = "std"))]
+    pub use super::strftime_serde::{
+        deserialize_with_format, serialize_with_format, StrftimeSerde,
+    };
+
+    // used by `serde_strftime!`
+    #[doc(hidden)]
+    pub use core::result::Result as __Result;
+    #[doc(hidden)]
+    pub use serdelib::{Deserializer as __Deserializer, Serializer as __Serializer};
 }
 
 // Until rust 1.18 there  is no "pub(crate)" so to share this we need it in the root
@@ -625,6 +672,24 @@ fn ne_timestamp<T: fmt::Display>(ts: T) -> SerdeError<T, u8> {
     SerdeError::NonExistent::<T, u8> { timestamp: ts }
 }
 
+/// Convert a timestamp in fractional seconds, rounded to the nearest nanosecond
+#[cfg(feature = "serde")]
+fn from_timestamp_f64<E: serdelib::de::Error>(value: f64) -> Result<NaiveDateTime, E> {
+    // this also rejects NaN
+    if !(value >= core::i64::MIN as f64 && value < core::i64::MAX as f64) {
+        return Err(E::custom(ne_timestamp(value)));
+    }
+    // `f64::floor` and `f64::round` are not available without `std`
+    let mut secs = value as i64;
+    if secs as f64 > value {
+        secs -= 1;
+    }
+    let nsecs = ((value - secs as f64) * 1e9 + 0.5) as u32;
+    let (secs, nsecs) =
+        if nsecs >= 1_000_000_000 { (secs + 1, nsecs - 1_000_000_000) } else { (secs, nsecs) };
+    NaiveDateTime::from_timestamp_opt(secs, nsecs).ok_or_else(|| E::custom(ne_timestamp(value)))
+}
+
 #[cfg(feature = "serde")]
 impl<V: fmt::Display, D: fmt::Display> fmt::Debug for SerdeError<V, D> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -1281,6 +1346,31 @@ pub trait Datelike: Sized {
     /// The return value ranges from 0 to 11.
     fn month0(&self) -> u32;
 
+    /// Returns the quarter number starting from 1.
+    ///
+    /// The return value ranges from 1 to 4.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Datelike, NaiveDate};
+    ///
+    /// assert_eq!(NaiveDate::from_ymd(2021, 3, 31).quarter(), 1);
+    /// assert_eq!(NaiveDate::from_ymd(2021, 11, 1).quarter(), 4);
+    /// ```
+    #[inline]
+    fn quarter(&self) -> u32 {
+        self.month0() / 3 + 1
+    }
+
+    /// Returns the quarter number starting from 0.
+    ///
+    /// The return value ranges from 0 to 3.
+    #[inline]
+    fn quarter0(&self) -> u32 {
+        self.month0() / 3
+    }
+
     /// Returns the day of month starting from 1.
     ///
     /// The return value ranges from 1 to 31. (The last day of month differs by months.)
@@ -3,20 +3,19 @@
 
 //! ISO 8601 calendar date without timezone.
 
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use core::borrow::Borrow;
+use core::iter::FusedIterator;
 use core::ops::{Add, AddAssign, Sub, SubAssign};
 use core::{fmt, str};
 use num_traits::ToPrimitive;
 use oldtime::Duration as OldDuration;
 
 use div::div_mod_floor;
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use format::DelayedFormat;
 use format::{parse, ParseError, ParseResult, Parsed, StrftimeItems};
 use format::{Item, Numeric, Pad};
 use naive::{IsoWeek, NaiveDateTime, NaiveTime};
-use {Datelike, Weekday};
+use {Datelike, Period, Weekday};
 
 use super::internals::{self, DateImpl, Mdf, Of, YearFlags};
 use super::isoweek;
@@ -415,7 +414,7 @@ impl NaiveDate {
     /// assert_eq!(from_ndays_opt(-100_000_000), None);
     /// ```
     pub fn from_num_days_from_ce_opt(days: i32) -> Option<NaiveDate> {
-        let days = days + 365; // make December 31, 1 BCE equal to day 0
+        let days = try_opt!(days.checked_add(365)); // make December 31, 1 BCE equal to day 0
         let (year_div_400, cycle) = div_mod_floor(days, 146_097);
         let (year_mod_400, ordinal) = internals::cycle_to_yo(cycle as u32);
         let flags = YearFlags::from_year_mod_400(year_mod_400 as i32);
@@ -971,6 +970,166 @@ impl NaiveDate {
         )
     }
 
+    /// Subtracts another `NaiveDate` from the current date.
+    /// Returns a [`Period`](../struct.Period.html) of full years, months and remaining days.
+    ///
+    /// The period is counted forward from the earlier date:
+    /// a month is complete once the day of month reaches the day of month of the earlier date.
+    /// When that day does not exist in a month, the last day of the month is used instead
+    /// for counting the remaining days, so February 29 anniversaries complete on March 1
+    /// in common years and one month from January 31 completes on March 1 (as "1 month 1 day")
+    /// while February 28 is still "28 days".
+    ///
+    /// If `self` is earlier than `rhs`, every component of the result is negative
+    /// and equals the negation of `rhs.period_since(self)`.
+    ///
+    /// This does not overflow or underflow at all,
+    /// as all possible output fits in the range of `Period`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{NaiveDate, Period};
+    ///
+    /// let from_ymd = NaiveDate::from_ymd;
+    ///
+    /// assert_eq!(from_ymd(2014, 3, 15).period_since(from_ymd(2012, 1, 10)), Period::new(2, 2, 5));
+    /// assert_eq!(from_ymd(2014, 3, 5).period_since(from_ymd(2014, 1, 10)), Period::new(0, 1, 23));
+    /// assert_eq!(from_ymd(2014, 1, 10).period_since(from_ymd(2014, 3, 5)), Period::new(0, -1, -23));
+    ///
+    /// // February 29 and end-of-month handling
+    /// assert_eq!(from_ymd(2001, 2, 28).period_since(from_ymd(2000, 2, 29)), Period::new(0, 11, 30));
+    /// assert_eq!(from_ymd(2001, 3, 1).period_since(from_ymd(2000, 2, 29)), Period::new(1, 0, 1));
+    /// assert_eq!(from_ymd(2015, 2, 28).period_since(from_ymd(2015, 1, 31)), Period::new(0, 0, 28));
+    /// assert_eq!(from_ymd(2015, 3, 1).period_since(from_ymd(2015, 1, 31)), Period::new(0, 1, 1));
+    /// ```
+    pub fn period_since(self, rhs: NaiveDate) -> Period {
+        if self < rhs {
+            return -rhs.period_since(self);
+        }
+
+        let mut months = (self.year() - rhs.year()) * 12 + self.month() as i32 - rhs.month() as i32;
+        let mut days = self.day() as i32 - rhs.day() as i32;
+        if days < 0 {
+            // the last month is not complete yet, count days from the previous anniversary
+            months -= 1;
+            let anchor =
+                rhs.add_months_clamped(i64::from(months)).expect("in-between date should exist");
+            days = self.signed_duration_since(anchor).num_days() as i32;
+        }
+        Period::new(months / 12, months % 12, days)
+    }
+
+    /// Returns the number of full years elapsed from `rhs` to the current date.
+    /// The result is negative when `self` is earlier than `rhs`.
+    ///
+    /// This is the `years` component of [`period_since`](#method.period_since)
+    /// and follows its end-of-month rules, which makes it suitable for computing ages.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    ///
+    /// let birth = NaiveDate::from_ymd(2000, 2, 29);
+    /// assert_eq!(NaiveDate::from_ymd(2018, 2, 28).years_since(birth), 17);
+    /// assert_eq!(NaiveDate::from_ymd(2018, 3, 1).years_since(birth), 18);
+    /// assert_eq!(NaiveDate::from_ymd(2020, 2, 29).years_since(birth), 20);
+    /// assert_eq!(birth.years_since(NaiveDate::from_ymd(2001, 3, 1)), -1);
+    /// ```
+    #[inline]
+    pub fn years_since(self, rhs: NaiveDate) -> i32 {
+        self.period_since(rhs).years()
+    }
+
+    /// Returns the number of full months elapsed from `rhs` to the current date.
+    /// The result is negative when `self` is earlier than `rhs`.
+    ///
+    /// This is the total number of months in [`period_since`](#method.period_since)
+    /// and follows its end-of-month rules.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    ///
+    /// let start = NaiveDate::from_ymd(2019, 10, 31);
+    /// assert_eq!(NaiveDate::from_ymd(2019, 11, 30).months_since(start), 0);
+    /// assert_eq!(NaiveDate::from_ymd(2019, 12, 1).months_since(start), 1);
+    /// assert_eq!(NaiveDate::from_ymd(2021, 1, 31).months_since(start), 15);
+    /// ```
+    #[inline]
+    pub fn months_since(self, rhs: NaiveDate) -> i32 {
+        self.period_since(rhs).total_months() as i32
+    }
+
+    /// Adds a [`Period`](../struct.Period.html) to the current date.
+    ///
+    /// Years and months are added first, clamping the day of month to the last day
+    /// of the resulting month when needed, and then the days are added.
+    /// Only the whole days of the time remainder of the period are taken into account.
+    ///
+    /// Returns `None` when it will result in overflow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{NaiveDate, Period};
+    /// use chrono::naive::MAX_DATE;
+    ///
+    /// let from_ymd = NaiveDate::from_ymd;
+    ///
+    /// assert_eq!(from_ymd(2020, 1, 31).checked_add_period(Period::new(0, 1, 0)),
+    ///            Some(from_ymd(2020, 2, 29)));
+    /// assert_eq!(from_ymd(2020, 1, 31).checked_add_period(Period::new(1, 1, 1)),
+    ///            Some(from_ymd(2021, 3, 1)));
+    /// assert_eq!(from_ymd(2020, 3, 31).checked_add_period(Period::new(0, -1, -1)),
+    ///            Some(from_ymd(2020, 2, 28)));
+    /// assert_eq!(MAX_DATE.checked_add_period(Period::new(0, 1, 0)), None);
+    /// ```
+    pub fn checked_add_period(self, rhs: Period) -> Option<NaiveDate> {
+        let date = try_opt!(self.add_months_clamped(rhs.total_months()));
+        date.checked_add_signed(OldDuration::days(i64::from(rhs.days()) + rhs.time().num_days()))
+    }
+
+    /// Adds given number of months to the current date,
+    /// clamping the day of month to the last day of the resulting month.
+    fn add_months_clamped(self, months: i64) -> Option<NaiveDate> {
+        self.add_months(months, MonthEndPolicy::Clamp)
+    }
+
+    /// Adds given number of months to the current date,
+    /// adjusting the day of month according to given policy.
+    fn add_months(self, months: i64, policy: MonthEndPolicy) -> Option<NaiveDate> {
+        let months =
+            try_opt!((i64::from(self.year()) * 12 + i64::from(self.month0())).checked_add(months));
+        let (year, month0) = div_mod_floor(months, 12);
+        let (year, month) = (try_opt!(year.to_i32()), month0 as u32 + 1);
+        let mut day = self.day();
+        match policy {
+            MonthEndPolicy::Skip => return NaiveDate::from_ymd_opt(year, month, day),
+            MonthEndPolicy::LastDay if self.is_last_day_of_month() => day = 31,
+            _ => {}
+        }
+        loop {
+            if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
+                return Some(date);
+            }
+            if day <= 28 {
+                return None;
+            }
+            day -= 1;
+        }
+    }
+
+    /// Returns `true` if the current date is the last day of its month.
+    fn is_last_day_of_month(&self) -> bool {
+        match self.succ_opt() {
+            Some(next) => next.day() == 1,
+            None => true,
+        }
+    }
+
     /// Formats the date with the specified formatting items.
     /// Otherwise it is the same as the ordinary `format` method.
     ///
@@ -998,7 +1157,6 @@ impl NaiveDate {
     /// # let d = NaiveDate::from_ymd(2015, 9, 5);
     /// assert_eq!(format!("{}", d.format_with_items(fmt)), "2015-09-05");
     /// ```
-    #[cfg(any(feature = "alloc", feature = "std", test))]
     #[inline]
     pub fn format_with_items<'a, I, B>(&self, items: I) -> DelayedFormat<I>
     where
@@ -1040,7 +1198,6 @@ impl NaiveDate {
     /// assert_eq!(format!("{}", d.format("%Y-%m-%d")), "2015-09-05");
     /// assert_eq!(format!("{}", d.format("%A, %-d %B, %C%y")), "Saturday, 5 September, 2015");
     /// ```
-    #[cfg(any(feature = "alloc", feature = "std", test))]
     #[inline]
     pub fn format<'a>(&self, fmt: &'a str) -> DelayedFormat<StrftimeItems<'a>> {
         self.format_with_items(StrftimeItems::new(fmt))
@@ -1067,9 +1224,26 @@ impl NaiveDate {
     /// }
     /// assert_eq!(count, 4);
     /// ```
+    ///
+    /// The iterator is double-ended and knows its exact length,
+    /// so it can be reversed or combined with `step_by` to step by any number of days.
+    /// [`Interval::iter_days`](../struct.Interval.html#method.iter_days) and
+    /// [`ClosedInterval::iter_days`](../struct.ClosedInterval.html#method.iter_days)
+    /// iterate over a bounded range of dates instead.
+    ///
+    /// ```
+    /// # use chrono::NaiveDate;
+    /// let mut iter = NaiveDate::from_ymd(2016, 2, 27).iter_days().step_by(10);
+    /// assert_eq!(iter.next(), Some(NaiveDate::from_ymd(2016, 2, 27)));
+    /// assert_eq!(iter.next(), Some(NaiveDate::from_ymd(2016, 3, 8)));
+    ///
+    /// let mut iter = NaiveDate::from_ymd(262143, 12, 29).iter_days();
+    /// assert_eq!(iter.len(), 2);
+    /// assert_eq!(iter.next_back(), Some(NaiveDate::from_ymd(262143, 12, 30)));
+    /// ```
     #[inline]
     pub fn iter_days(&self) -> NaiveDateDaysIterator {
-        NaiveDateDaysIterator { value: *self }
+        NaiveDateDaysIterator::new(*self, MAX_DATE.signed_duration_since(*self).num_days())
     }
 
     /// Returns an iterator that steps by weeks until the last representable date.
@@ -1093,9 +1267,67 @@ impl NaiveDate {
     /// }
     /// assert_eq!(count, 4);
     /// ```
+    ///
+    /// Like [`iter_days`](#method.iter_days), the iterator is double-ended and knows its exact length.
     #[inline]
     pub fn iter_weeks(&self) -> NaiveDateWeeksIterator {
-        NaiveDateWeeksIterator { value: *self }
+        NaiveDateWeeksIterator::new(*self, MAX_DATE.signed_duration_since(*self).num_weeks())
+    }
+
+    /// Returns an iterator that steps by months until the last representable date.
+    ///
+    /// Every date is computed from the current date rather than from the previous item,
+    /// so a day of month adjusted for a short month is restored in the following months.
+    /// The `policy` decides what to do when the day of month does not exist in some month.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use chrono::naive::MonthEndPolicy;
+    ///
+    /// let from_ymd = NaiveDate::from_ymd;
+    ///
+    /// let d = from_ymd(2020, 1, 31);
+    /// assert_eq!(d.iter_months(MonthEndPolicy::Clamp).take(3).collect::<Vec<_>>(),
+    ///            [from_ymd(2020, 1, 31), from_ymd(2020, 2, 29), from_ymd(2020, 3, 31)]);
+    /// assert_eq!(d.iter_months(MonthEndPolicy::Skip).take(3).collect::<Vec<_>>(),
+    ///            [from_ymd(2020, 1, 31), from_ymd(2020, 3, 31), from_ymd(2020, 5, 31)]);
+    ///
+    /// let d = from_ymd(2020, 4, 30);
+    /// assert_eq!(d.iter_months(MonthEndPolicy::Clamp).take(3).collect::<Vec<_>>(),
+    ///            [from_ymd(2020, 4, 30), from_ymd(2020, 5, 30), from_ymd(2020, 6, 30)]);
+    /// assert_eq!(d.iter_months(MonthEndPolicy::LastDay).take(3).collect::<Vec<_>>(),
+    ///            [from_ymd(2020, 4, 30), from_ymd(2020, 5, 31), from_ymd(2020, 6, 30)]);
+    /// ```
+    #[inline]
+    pub fn iter_months(&self, policy: MonthEndPolicy) -> NaiveDateMonthsIterator {
+        NaiveDateMonthsIterator::new(*self, 1, policy)
+    }
+
+    /// Returns an iterator that steps by years until the last representable date.
+    ///
+    /// This is same to stepping by 12 months with [`iter_months`](#method.iter_months),
+    /// so the `policy` decides what happens to February 29 in common years.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use chrono::naive::MonthEndPolicy;
+    ///
+    /// let from_ymd = NaiveDate::from_ymd;
+    ///
+    /// let d = from_ymd(2020, 2, 29);
+    /// assert_eq!(d.iter_years(MonthEndPolicy::Clamp).take(3).collect::<Vec<_>>(),
+    ///            [from_ymd(2020, 2, 29), from_ymd(2021, 2, 28), from_ymd(2022, 2, 28)]);
+    /// assert_eq!(d.iter_years(MonthEndPolicy::Skip).take(3).collect::<Vec<_>>(),
+    ///            [from_ymd(2020, 2, 29), from_ymd(2024, 2, 29), from_ymd(2028, 2, 29)]);
+    /// assert_eq!(d.iter_years(MonthEndPolicy::Clamp).rev().next(), Some(from_ymd(262143, 2, 28)));
+    /// ```
+    #[inline]
+    pub fn iter_years(&self, policy: MonthEndPolicy) -> NaiveDateMonthsIterator {
+        NaiveDateMonthsIterator::new(*self, 12, policy)
     }
 }
 
@@ -1558,62 +1790,222 @@ impl Sub<NaiveDate> for NaiveDate {
 }
 
 /// Iterator over `NaiveDate` with a step size of one day.
+///
+/// This is created by [`NaiveDate::iter_days`](./struct.NaiveDate.html#method.iter_days)
+/// and by iterating over an [`Interval`](../struct.Interval.html) of dates.
 #[derive(Debug, Copy, Clone, Hash, PartialEq, PartialOrd, Eq, Ord)]
 pub struct NaiveDateDaysIterator {
     value: NaiveDate,
+    remaining: u32,
+}
+
+impl NaiveDateDaysIterator {
+    /// Makes an iterator over `count` consecutive days starting from `value`.
+    /// All of those days should be representable.
+    pub(crate) fn new(value: NaiveDate, count: i64) -> NaiveDateDaysIterator {
+        NaiveDateDaysIterator { value: value, remaining: count.max(0) as u32 }
+    }
 }
 
 impl Iterator for NaiveDateDaysIterator {
     type Item = NaiveDate;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.value == MAX_DATE {
+        if self.remaining == 0 {
             return None;
         }
-        // current < MAX_DATE from here on:
         let current = self.value;
-        // This can't panic because current is < MAX_DATE:
-        self.value = current.succ();
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            // This can't panic because the next day is still in the range:
+            self.value = current.succ();
+        }
         Some(current)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let exact_size = MAX_DATE.signed_duration_since(self.value).num_days();
-        (exact_size as usize, Some(exact_size as usize))
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.remaining as usize {
+            self.remaining = 0;
+            return None;
+        }
+        self.value += OldDuration::days(n as i64);
+        self.remaining -= n as u32;
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for NaiveDateDaysIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(self.value + OldDuration::days(i64::from(self.remaining)))
     }
 }
 
 impl ExactSizeIterator for NaiveDateDaysIterator {}
 
+impl FusedIterator for NaiveDateDaysIterator {}
+
+/// Iterator over `NaiveDate` with a step size of one week.
+///
+/// This is created by [`NaiveDate::iter_weeks`](./struct.NaiveDate.html#method.iter_weeks).
 #[derive(Debug, Copy, Clone, Hash, PartialEq, PartialOrd, Eq, Ord)]
 pub struct NaiveDateWeeksIterator {
     value: NaiveDate,
+    remaining: u32,
+}
+
+impl NaiveDateWeeksIterator {
+    /// Makes an iterator over `count` consecutive weeks starting from `value`.
+    /// All of those weeks should be representable.
+    fn new(value: NaiveDate, count: i64) -> NaiveDateWeeksIterator {
+        NaiveDateWeeksIterator { value: value, remaining: count.max(0) as u32 }
+    }
 }
 
 impl Iterator for NaiveDateWeeksIterator {
     type Item = NaiveDate;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if MAX_DATE - self.value < OldDuration::weeks(1) {
+        if self.remaining == 0 {
             return None;
         }
         let current = self.value;
-        self.value = current + OldDuration::weeks(1);
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            self.value = current + OldDuration::weeks(1);
+        }
         Some(current)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let exact_size = MAX_DATE.signed_duration_since(self.value).num_weeks();
-        (exact_size as usize, Some(exact_size as usize))
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.remaining as usize {
+            self.remaining = 0;
+            return None;
+        }
+        self.value += OldDuration::weeks(n as i64);
+        self.remaining -= n as u32;
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for NaiveDateWeeksIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(self.value + OldDuration::weeks(i64::from(self.remaining)))
     }
 }
 
 impl ExactSizeIterator for NaiveDateWeeksIterator {}
 
-// TODO: NaiveDateDaysIterator and NaiveDateWeeksIterator should implement FusedIterator,
-// TrustedLen, and Step once they becomes stable.
+impl FusedIterator for NaiveDateWeeksIterator {}
+
+// TODO: NaiveDateDaysIterator and NaiveDateWeeksIterator should implement TrustedLen
+// and Step once they becomes stable.
 // See: https://github.com/chronotope/chrono/issues/208
 
+/// The way to handle a day of month that does not exist in the target month
+/// when stepping a date by months or years.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum MonthEndPolicy {
+    /// Use the last day of the target month instead.
+    ///
+    /// January 31 steps to February 28 (or 29), March 31, April 30 and so on.
+    Clamp,
+    /// Skip the months where the day of month does not exist.
+    ///
+    /// January 31 steps to March 31, May 31, July 31 and so on.
+    Skip,
+    /// Always use the last day of the target month when starting from the last day of a month,
+    /// and otherwise behave like `Clamp`.
+    ///
+    /// April 30 steps to May 31, June 30, July 31 and so on.
+    LastDay,
+}
+
+/// Iterator over `NaiveDate` with a step size of one month or one year.
+///
+/// This is created by [`NaiveDate::iter_months`](./struct.NaiveDate.html#method.iter_months)
+/// and [`NaiveDate::iter_years`](./struct.NaiveDate.html#method.iter_years).
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct NaiveDateMonthsIterator {
+    start: NaiveDate,
+    step: i64,
+    policy: MonthEndPolicy,
+    // the next step index from the front, and one past the next step index from the back
+    front: i64,
+    back: i64,
+}
+
+impl NaiveDateMonthsIterator {
+    fn new(start: NaiveDate, step: i64, policy: MonthEndPolicy) -> NaiveDateMonthsIterator {
+        let months = (i64::from(MAX_DATE.year()) - i64::from(start.year())) * 12
+            + i64::from(MAX_DATE.month0())
+            - i64::from(start.month0());
+        NaiveDateMonthsIterator {
+            start: start,
+            step: step,
+            policy: policy,
+            front: 0,
+            back: months / step + 1,
+        }
+    }
+
+    fn at(&self, index: i64) -> Option<NaiveDate> {
+        self.start.add_months(index * self.step, self.policy)
+    }
+}
+
+impl Iterator for NaiveDateMonthsIterator {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            let index = self.front;
+            self.front += 1;
+            if let Some(date) = self.at(index) {
+                return Some(date);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let steps = (self.back - self.front) as usize;
+        match self.policy {
+            MonthEndPolicy::Skip if self.start.day() > 28 => (0, Some(steps)),
+            _ => (steps, Some(steps)),
+        }
+    }
+}
+
+impl DoubleEndedIterator for NaiveDateMonthsIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            self.back -= 1;
+            if let Some(date) = self.at(self.back) {
+                return Some(date);
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for NaiveDateMonthsIterator {}
+
 /// The `Debug` output of the naive date `d` is the same as
 /// [`d.format("%Y-%m-%d")`](../format/strftime/index.html).
 ///
@@ -1814,10 +2206,13 @@ mod rustc_serialize {
 #[cfg(feature = "serde")]
 mod serde {
     use super::NaiveDate;
-    use core::fmt;
+    use compact_serde::compact::Compact;
+    use core::{fmt, i32};
     use serdelib::{de, ser};
+    use Datelike;
 
-    // TODO not very optimized for space (binary formats would want something better)
+    // binary formats use the string too, so that they can read data written by earlier
+    // versions; `serde::compact` is an opt-in representation which is better for space
 
     impl ser::Serialize for NaiveDate {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -1844,7 +2239,28 @@ mod serde {
         type Value = NaiveDate;
 
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            write!(formatter, "a formatted date string")
+            write!(formatter, "a formatted date string or a number of days")
+        }
+
+        fn visit_i64<E>(self, value: i64) -> Result<NaiveDate, E>
+        where
+            E: de::Error,
+        {
+            if value < i64::from(i32::MIN) || value > i64::from(i32::MAX) {
+                return Err(E::custom("date out of range"));
+            }
+            NaiveDate::from_num_days_from_ce_opt(value as i32)
+                .ok_or_else(|| E::custom("date out of range"))
+        }
+
+        fn visit_u64<E>(self, value: u64) -> Result<NaiveDate, E>
+        where
+            E: de::Error,
+        {
+            if value > i32::MAX as u64 {
+                return Err(E::custom("date out of range"));
+            }
+            self.visit_i64(value as i64)
         }
 
         #[cfg(any(feature = "std", test))]
@@ -1873,9 +2289,28 @@ mod serde {
         }
     }
 
+    /// The number of days from January 1, 1 (`num_days_from_ce`).
+    impl Compact for NaiveDate {
+        fn serialize_compact<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            serializer.serialize_i32(self.num_days_from_ce())
+        }
+
+        fn deserialize_compact<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_i32(NaiveDateVisitor)
+        }
+    }
+
     #[cfg(test)]
     extern crate bincode;
     #[cfg(test)]
+    extern crate bincode1;
+    #[cfg(test)]
     extern crate serde_json;
 
     #[test]
@@ -1899,11 +2334,23 @@ mod serde {
         let decoded: NaiveDate = deserialize(&encoded).unwrap();
         assert_eq!(d, decoded);
     }
+
+    #[test]
+    fn test_serde_bincode_1() {
+        // bincode 1.x is not human-readable, but still uses the string
+        // to read data written by earlier versions
+        use self::bincode1::{deserialize, serialize};
+
+        let d = NaiveDate::from_ymd(2014, 7, 24);
+        let encoded = serialize(&d).unwrap();
+        assert_eq!(encoded, serialize(&"2014-07-24").unwrap());
+        assert_eq!(deserialize::<NaiveDate>(&encoded).unwrap(), d);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::NaiveDate;
+    use super::{MonthEndPolicy, NaiveDate};
     use super::{MAX_DATE, MAX_DAYS_FROM_YEAR_0, MAX_YEAR};
     use super::{MIN_DATE, MIN_DAYS_FROM_YEAR_0, MIN_YEAR};
     use oldtime::Duration;
@@ -2390,6 +2837,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_date_period_since() {
+        use Period;
+
+        let ymd = NaiveDate::from_ymd;
+        assert_eq!(ymd(2014, 1, 1).period_since(ymd(2014, 1, 1)), Period::zero());
+        assert_eq!(ymd(2014, 1, 31).period_since(ymd(2013, 12, 31)), Period::new(0, 1, 0));
+        assert_eq!(ymd(2014, 2, 28).period_since(ymd(2013, 12, 31)), Period::new(0, 1, 28));
+        assert_eq!(ymd(2014, 3, 1).period_since(ymd(2013, 12, 31)), Period::new(0, 2, 1));
+        assert_eq!(ymd(2016, 2, 29).period_since(ymd(2015, 2, 28)), Period::new(1, 0, 1));
+        assert_eq!(ymd(2016, 2, 28).period_since(ymd(2012, 2, 29)), Period::new(3, 11, 30));
+        assert_eq!(ymd(2016, 2, 29).period_since(ymd(2012, 2, 29)), Period::new(4, 0, 0));
+        assert_eq!(ymd(1, 1, 1).period_since(ymd(-1, 12, 31)), Period::new(1, 0, 1));
+        assert_eq!(MAX_DATE.period_since(MIN_DATE).years(), MAX_YEAR - MIN_YEAR);
+        assert_eq!(MIN_DATE.period_since(MAX_DATE).years(), MIN_YEAR - MAX_YEAR);
+
+        // adding the months back to the earlier date and then the days gives the later date
+        let dates = (0..800).map(|i| ymd(1999, 11, 15) + Duration::days(i * 3 + i % 3));
+        for (i, d1) in dates.clone().enumerate() {
+            for d2 in dates.clone().skip(i) {
+                let p = d2.period_since(d1);
+                assert!(p.years() >= 0 && p.months() >= 0 && p.months() < 12 && p.days() >= 0);
+                assert_eq!(d1.period_since(d2), -p);
+                let anchor = d1.add_months_clamped(p.total_months()).unwrap();
+                assert_eq!(anchor + Duration::days(i64::from(p.days())), d2);
+                let next = d1.add_months_clamped(p.total_months() + 1).unwrap();
+                assert!(next > d2 || (next == d2 && d1.day() > next.day()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_date_years_and_months_since() {
+        let ymd = NaiveDate::from_ymd;
+        assert_eq!(ymd(2020, 6, 15).years_since(ymd(1990, 6, 15)), 30);
+        assert_eq!(ymd(2020, 6, 14).years_since(ymd(1990, 6, 15)), 29);
+        assert_eq!(ymd(1990, 6, 15).years_since(ymd(2020, 6, 14)), -29);
+        assert_eq!(ymd(2020, 6, 15).months_since(ymd(1990, 6, 15)), 360);
+        assert_eq!(ymd(2020, 6, 14).months_since(ymd(2020, 5, 15)), 0);
+        assert_eq!(ymd(2020, 5, 15).months_since(ymd(2020, 6, 14)), 0);
+        assert_eq!(ymd(2020, 4, 30).months_since(ymd(2020, 6, 14)), -1);
+    }
+
     #[test]
     fn test_day_iterator_limit() {
         assert_eq!(
@@ -2405,4 +2895,93 @@ mod tests {
             2
         );
     }
+
+    #[test]
+    fn test_day_iterator_double_ended() {
+        let start = NaiveDate::from_ymd(2016, 2, 27);
+        let forward = start.iter_days().take(10).collect::<Vec<_>>();
+        let mut iter = start.iter_days();
+        assert_eq!(iter.len(), MAX_DATE.signed_duration_since(start).num_days() as usize);
+        assert_eq!(iter.next_back(), Some(MAX_DATE.pred()));
+        assert_eq!(iter.nth(9), Some(NaiveDate::from_ymd(2016, 3, 7)));
+        assert_eq!(
+            iter.by_ref().step_by(3).take(2).collect::<Vec<_>>(),
+            [forward[0] + Duration::days(10), forward[0] + Duration::days(13)]
+        );
+        assert_eq!(iter.nth(usize::max_value()), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let mut iter = NaiveDate::from_ymd(262143, 12, 12).iter_weeks().rev();
+        assert_eq!(iter.next(), Some(NaiveDate::from_ymd(262143, 12, 19)));
+        assert_eq!(iter.next(), Some(NaiveDate::from_ymd(262143, 12, 12)));
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(MAX_DATE.iter_days().next(), None);
+        assert_eq!(MAX_DATE.iter_weeks().len(), 0);
+    }
+
+    #[test]
+    fn test_month_iterator() {
+        let ymd = NaiveDate::from_ymd;
+        let months = |d: NaiveDate, policy| d.iter_months(policy).take(5).collect::<Vec<_>>();
+
+        assert_eq!(
+            months(ymd(2019, 10, 31), MonthEndPolicy::Clamp),
+            [
+                ymd(2019, 10, 31),
+                ymd(2019, 11, 30),
+                ymd(2019, 12, 31),
+                ymd(2020, 1, 31),
+                ymd(2020, 2, 29)
+            ]
+        );
+        assert_eq!(
+            months(ymd(2019, 10, 31), MonthEndPolicy::Skip),
+            [
+                ymd(2019, 10, 31),
+                ymd(2019, 12, 31),
+                ymd(2020, 1, 31),
+                ymd(2020, 3, 31),
+                ymd(2020, 5, 31)
+            ]
+        );
+        assert_eq!(
+            months(ymd(2020, 2, 29), MonthEndPolicy::LastDay),
+            [
+                ymd(2020, 2, 29),
+                ymd(2020, 3, 31),
+                ymd(2020, 4, 30),
+                ymd(2020, 5, 31),
+                ymd(2020, 6, 30)
+            ]
+        );
+        assert_eq!(
+            months(ymd(2020, 2, 28), MonthEndPolicy::LastDay),
+            [
+                ymd(2020, 2, 28),
+                ymd(2020, 3, 28),
+                ymd(2020, 4, 28),
+                ymd(2020, 5, 28),
+                ymd(2020, 6, 28)
+            ]
+        );
+
+        let mut iter = ymd(262142, 11, 30).iter_months(MonthEndPolicy::LastDay);
+        assert_eq!(iter.size_hint(), (14, Some(14)));
+        assert_eq!(iter.next_back(), Some(MAX_DATE));
+        assert_eq!(iter.count(), 13);
+
+        let mut iter = ymd(262142, 1, 31).iter_months(MonthEndPolicy::Skip);
+        assert_eq!(iter.size_hint(), (0, Some(24)));
+        assert_eq!(
+            iter.by_ref().rev().take(3).collect::<Vec<_>>(),
+            [MAX_DATE, ymd(262143, 10, 31), ymd(262143, 8, 31)]
+        );
+        assert_eq!(iter.count(), 11);
+
+        let years = ymd(2000, 2, 29).iter_years(MonthEndPolicy::Skip);
+        assert!(years.take(100).all(|d| d.month() == 2 && d.day() == 29));
+        assert_eq!(MAX_DATE.iter_years(MonthEndPolicy::Clamp).collect::<Vec<_>>(), [MAX_DATE]);
+    }
 }
@@ -3,7 +3,6 @@
 
 //! ISO 8601 date and time without timezone.
 
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use core::borrow::Borrow;
 use core::ops::{Add, AddAssign, Sub, SubAssign};
 use core::{fmt, hash, str};
@@ -11,14 +10,13 @@ use num_traits::ToPrimitive;
 use oldtime::Duration as OldDuration;
 
 use div::div_mod_floor;
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use format::DelayedFormat;
 use format::{parse, ParseError, ParseResult, Parsed, StrftimeItems};
 use format::{Fixed, Item, Numeric, Pad};
 use naive::date::{MAX_DATE, MIN_DATE};
 use naive::time::{MAX_TIME, MIN_TIME};
 use naive::{IsoWeek, NaiveDate, NaiveTime};
-use {Datelike, Timelike, Weekday};
+use {Datelike, Period, Timelike, Weekday};
 
 /// The tight upper bound guarantees that a duration with `|Duration| >= 2^MAX_SECS_BITS`
 /// will always overflow the addition with any date and time type.
@@ -650,6 +648,76 @@ impl NaiveDateTime {
         self.date.signed_duration_since(rhs.date) + self.time.signed_duration_since(rhs.time)
     }
 
+    /// Subtracts another `NaiveDateTime` from the current date and time.
+    /// Returns a [`Period`](../struct.Period.html) of full years, months and days
+    /// with the remaining time shorter than a day.
+    ///
+    /// Years, months and days are counted as in
+    /// [`NaiveDate::period_since`](./struct.NaiveDate.html#method.period_since),
+    /// except that a day only counts once the time of day of the earlier value is reached.
+    /// If `self` is earlier than `rhs`, every component of the result is negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate chrono; fn main() {
+    /// use chrono::{Duration, NaiveDate, Period};
+    ///
+    /// let from_ymd = NaiveDate::from_ymd;
+    ///
+    /// let start = from_ymd(2019, 1, 31).and_hms(18, 0, 0);
+    /// assert_eq!(from_ymd(2019, 3, 1).and_hms(19, 30, 0).period_since(start),
+    ///            Period::with_time(0, 1, 1, Duration::minutes(90)));
+    /// assert_eq!(from_ymd(2019, 3, 1).and_hms(9, 0, 0).period_since(start),
+    ///            Period::with_time(0, 0, 28, Duration::hours(15)));
+    /// assert_eq!(start.period_since(from_ymd(2019, 3, 1).and_hms(9, 0, 0)),
+    ///            Period::with_time(0, 0, -28, Duration::hours(-15)));
+    /// # }
+    /// ```
+    pub fn period_since(self, rhs: NaiveDateTime) -> Period {
+        if self < rhs {
+            return -rhs.period_since(self);
+        }
+
+        let mut date = self.date;
+        let mut time = self.time.signed_duration_since(rhs.time);
+        if time < OldDuration::zero() {
+            // the last day is not complete yet; `self.date > rhs.date` is guaranteed here
+            date = date.pred();
+            time = time + OldDuration::days(1);
+        }
+        let period = date.period_since(rhs.date);
+        Period::with_time(period.years(), period.months(), period.days(), time)
+    }
+
+    /// Adds a [`Period`](../struct.Period.html) to the current date and time.
+    ///
+    /// The years, months and days are added to the date as in
+    /// [`NaiveDate::checked_add_period`](./struct.NaiveDate.html#method.checked_add_period),
+    /// then the time remainder of the period is added as a `Duration`.
+    ///
+    /// Returns `None` when it will result in overflow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate chrono; fn main() {
+    /// use chrono::{Duration, NaiveDate, Period};
+    ///
+    /// let dt = NaiveDate::from_ymd(2019, 1, 31).and_hms(18, 0, 0);
+    /// assert_eq!(dt.checked_add_period(Period::with_time(0, 1, 1, Duration::hours(7))),
+    ///            Some(NaiveDate::from_ymd(2019, 3, 2).and_hms(1, 0, 0)));
+    /// # }
+    /// ```
+    pub fn checked_add_period(self, rhs: Period) -> Option<NaiveDateTime> {
+        let date = try_opt!(self.date.checked_add_period(Period::new(
+            rhs.years(),
+            rhs.months(),
+            rhs.days()
+        )));
+        date.and_time(self.time).checked_add_signed(rhs.time())
+    }
+
     /// Formats the combined date and time with the specified formatting items.
     /// Otherwise it is the same as the ordinary [`format`](#method.format) method.
     ///
@@ -677,7 +745,6 @@ impl NaiveDateTime {
     /// # let dt = NaiveDate::from_ymd(2015, 9, 5).and_hms(23, 56, 4);
     /// assert_eq!(format!("{}", dt.format_with_items(fmt)), "2015-09-05 23:56:04");
     /// ```
-    #[cfg(any(feature = "alloc", feature = "std", test))]
     #[inline]
     pub fn format_with_items<'a, I, B>(&self, items: I) -> DelayedFormat<I>
     where
@@ -719,7 +786,6 @@ impl NaiveDateTime {
     /// assert_eq!(format!("{}", dt.format("%Y-%m-%d %H:%M:%S")), "2015-09-05 23:56:04");
     /// assert_eq!(format!("{}", dt.format("around %l %p on %b %-d")), "around 11 PM on Sep 5");
     /// ```
-    #[cfg(any(feature = "alloc", feature = "std", test))]
     #[inline]
     pub fn format<'a>(&self, fmt: &'a str) -> DelayedFormat<StrftimeItems<'a>> {
         self.format_with_items(StrftimeItems::new(fmt))
@@ -1723,6 +1789,7 @@ pub mod rustc_serialize {
 #[cfg(feature = "serde")]
 pub mod serde {
     use super::NaiveDateTime;
+    use compact_serde::compact::Compact;
     use core::fmt;
     use serdelib::{de, ser};
 
@@ -1755,7 +1822,10 @@ pub mod serde {
         type Value = NaiveDateTime;
 
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            write!(formatter, "a formatted date and time string")
+            write!(
+                formatter,
+                "a formatted date and time string or a tuple of a timestamp and nanoseconds"
+            )
         }
 
         fn visit_str<E>(self, value: &str) -> Result<NaiveDateTime, E>
@@ -1764,8 +1834,22 @@ pub mod serde {
         {
             value.parse().map_err(E::custom)
         }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<NaiveDateTime, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            let secs = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let nsecs = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            NaiveDateTime::from_timestamp_opt(secs, nsecs)
+                .ok_or_else(|| de::Error::custom("timestamp out of range"))
+        }
     }
 
+    /// Deserialize a `NaiveDateTime` from an RFC 3339 string
+    ///
+    /// See [the `serde` module](./serde/index.html) for alternate
+    /// serialization formats.
     impl<'de> de::Deserialize<'de> for NaiveDateTime {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
@@ -1775,6 +1859,30 @@ pub mod serde {
         }
     }
 
+    /// A tuple of the UNIX timestamp and the nanoseconds.
+    impl Compact for NaiveDateTime {
+        fn serialize_compact<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            ser::Serialize::serialize(
+                &(self.timestamp(), self.timestamp_subsec_nanos()),
+                serializer,
+            )
+        }
+
+        fn deserialize_compact<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_tuple(2, NaiveDateTimeVisitor)
+        }
+    }
+
+    fn timestamp_f64(dt: &NaiveDateTime) -> f64 {
+        dt.timestamp() as f64 + f64::from(dt.timestamp_subsec_nanos()) / 1e9
+    }
+
     /// Used to serialize/deserialize from nanosecond-precision timestamps
     ///
     /// # Example:
@@ -1814,6 +1922,7 @@ pub mod serde {
         use core::fmt;
         use serdelib::{de, ser};
 
+        use div::div_mod_floor;
         use {ne_timestamp, NaiveDateTime};
 
         /// Serialize a UTC datetime into an integer number of nanoseconds since the epoch
@@ -1823,10 +1932,6 @@ pub mod serde {
         /// # Example:
         ///
         /// ```rust
-        /// # // We mark this ignored so that we can test on 1.13 (which does not
-        /// # // support custom derive), and run tests with --ignored on beta and
-        /// # // nightly to actually trigger these.
-        /// #
         /// # #[macro_use] extern crate serde_derive;
         /// # #[macro_use] extern crate serde_json;
         /// # #[macro_use] extern crate serde;
@@ -1864,10 +1969,6 @@ pub mod serde {
         /// # Example:
         ///
         /// ```rust
-        /// # // We mark this ignored so that we can test on 1.13 (which does not
-        /// # // support custom derive), and run tests with --ignored on beta and
-        /// # // nightly to actually trigger these.
-        /// #
         /// # #[macro_use] extern crate serde_derive;
         /// # #[macro_use] extern crate serde_json;
         /// # extern crate serde;
@@ -1894,7 +1995,7 @@ pub mod serde {
             Ok(d.deserialize_i64(NaiveDateTimeFromNanoSecondsVisitor)?)
         }
 
-        struct NaiveDateTimeFromNanoSecondsVisitor;
+        pub(super) struct NaiveDateTimeFromNanoSecondsVisitor;
 
         impl<'de> de::Visitor<'de> for NaiveDateTimeFromNanoSecondsVisitor {
             type Value = NaiveDateTime;
@@ -1907,11 +2008,9 @@ pub mod serde {
             where
                 E: de::Error,
             {
-                NaiveDateTime::from_timestamp_opt(
-                    value / 1_000_000_000,
-                    (value % 1_000_000_000) as u32,
-                )
-                .ok_or_else(|| E::custom(ne_timestamp(value)))
+                let (secs, nsecs) = div_mod_floor(value, 1_000_000_000);
+                NaiveDateTime::from_timestamp_opt(secs, nsecs as u32)
+                    .ok_or_else(|| E::custom(ne_timestamp(value)))
             }
 
             fn visit_u64<E>(self, value: u64) -> Result<NaiveDateTime, E>
@@ -1919,234 +2018,219 @@ pub mod serde {
                 E: de::Error,
             {
                 NaiveDateTime::from_timestamp_opt(
-                    value as i64 / 1_000_000_000,
-                    (value as i64 % 1_000_000_000) as u32,
+                    (value / 1_000_000_000) as i64,
+                    (value % 1_000_000_000) as u32,
                 )
                 .ok_or_else(|| E::custom(ne_timestamp(value)))
             }
         }
     }
 
-    /// Used to serialize/deserialize from millisecond-precision timestamps
+    /// Used to serialize/deserialize from optional nanosecond-precision timestamps
     ///
     /// # Example:
     ///
     /// ```rust
-    /// # // We mark this ignored so that we can test on 1.13 (which does not
-    /// # // support custom derive), and run tests with --ignored on beta and
-    /// # // nightly to actually trigger these.
-    /// #
     /// # #[macro_use] extern crate serde_derive;
     /// # extern crate serde_json;
     /// # extern crate serde;
     /// # extern crate chrono;
-    /// # use chrono::{TimeZone, NaiveDate, NaiveDateTime, Utc};
-    /// use chrono::naive::serde::ts_milliseconds;
+    /// # use chrono::{NaiveDate, NaiveDateTime};
+    /// use chrono::naive::serde::ts_nanoseconds_option;
     /// #[derive(Deserialize, Serialize)]
     /// struct S {
-    ///     #[serde(with = "ts_milliseconds")]
-    ///     time: NaiveDateTime
+    ///     #[serde(with = "ts_nanoseconds_option")]
+    ///     time: Option<NaiveDateTime>
     /// }
     ///
     /// # fn example() -> Result<S, serde_json::Error> {
-    /// let time = NaiveDate::from_ymd(2018, 5, 17).and_hms_milli(02, 04, 59, 918);
+    /// let time = Some(NaiveDate::from_ymd(2018, 5, 17).and_hms_nano(02, 04, 59, 918355733));
     /// let my_s = S {
     ///     time: time.clone(),
     /// };
     ///
     /// let as_string = serde_json::to_string(&my_s)?;
-    /// assert_eq!(as_string, r#"{"time":1526522699918}"#);
+    /// assert_eq!(as_string, r#"{"time":1526522699918355733}"#);
     /// let my_s: S = serde_json::from_str(&as_string)?;
     /// assert_eq!(my_s.time, time);
     /// # Ok(my_s)
     /// # }
     /// # fn main() { example().unwrap(); }
     /// ```
-    pub mod ts_milliseconds {
+    pub mod ts_nanoseconds_option {
         use core::fmt;
         use serdelib::{de, ser};
 
-        use {ne_timestamp, NaiveDateTime};
+        use NaiveDateTime;
 
-        /// Serialize a UTC datetime into an integer number of milliseconds since the epoch
+        use super::ts_nanoseconds::NaiveDateTimeFromNanoSecondsVisitor;
+
+        /// Serialize a datetime into a nanosecond timestamp since the epoch or none
         ///
         /// Intended for use with `serde`s `serialize_with` attribute.
         ///
         /// # Example:
         ///
         /// ```rust
-        /// # // We mark this ignored so that we can test on 1.13 (which does not
-        /// # // support custom derive), and run tests with --ignored on beta and
-        /// # // nightly to actually trigger these.
-        /// #
         /// # #[macro_use] extern crate serde_derive;
-        /// # #[macro_use] extern crate serde_json;
-        /// # #[macro_use] extern crate serde;
+        /// # extern crate serde_json;
+        /// # extern crate serde;
         /// # extern crate chrono;
-        /// # use chrono::{TimeZone, NaiveDate, NaiveDateTime, Utc};
-        /// # use serde::Serialize;
-        /// use chrono::naive::serde::ts_milliseconds::serialize as to_milli_ts;
+        /// # use chrono::{NaiveDate, NaiveDateTime};
+        /// use chrono::naive::serde::ts_nanoseconds_option::serialize as to_nano_tsopt;
         /// #[derive(Serialize)]
         /// struct S {
-        ///     #[serde(serialize_with = "to_milli_ts")]
-        ///     time: NaiveDateTime
+        ///     #[serde(serialize_with = "to_nano_tsopt")]
+        ///     time: Option<NaiveDateTime>
         /// }
         ///
         /// # fn example() -> Result<String, serde_json::Error> {
         /// let my_s = S {
-        ///     time: NaiveDate::from_ymd(2018, 5, 17).and_hms_milli(02, 04, 59, 918),
+        ///     time: Some(NaiveDate::from_ymd(2018, 5, 17).and_hms_nano(02, 04, 59, 918355733)),
         /// };
         /// let as_string = serde_json::to_string(&my_s)?;
-        /// assert_eq!(as_string, r#"{"time":1526522699918}"#);
+        /// assert_eq!(as_string, r#"{"time":1526522699918355733}"#);
         /// # Ok(as_string)
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn serialize<S>(dt: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+        pub fn serialize<S>(opt: &Option<NaiveDateTime>, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: ser::Serializer,
         {
-            serializer.serialize_i64(dt.timestamp_millis())
+            match *opt {
+                Some(ref dt) => serializer.serialize_some(&dt.timestamp_nanos()),
+                None => serializer.serialize_none(),
+            }
         }
 
-        /// Deserialize a `DateTime` from a milliseconds timestamp
+        /// Deserialize a `NaiveDateTime` from a nanosecond timestamp or none
         ///
         /// Intended for use with `serde`s `deserialize_with` attribute.
         ///
         /// # Example:
         ///
         /// ```rust
-        /// # // We mark this ignored so that we can test on 1.13 (which does not
-        /// # // support custom derive), and run tests with --ignored on beta and
-        /// # // nightly to actually trigger these.
-        /// #
         /// # #[macro_use] extern crate serde_derive;
-        /// # #[macro_use] extern crate serde_json;
+        /// # extern crate serde_json;
         /// # extern crate serde;
         /// # extern crate chrono;
-        /// # use chrono::{NaiveDateTime, Utc};
-        /// # use serde::Deserialize;
-        /// use chrono::naive::serde::ts_milliseconds::deserialize as from_milli_ts;
+        /// # use chrono::NaiveDateTime;
+        /// use chrono::naive::serde::ts_nanoseconds_option::deserialize as from_nano_tsopt;
         /// #[derive(Deserialize)]
         /// struct S {
-        ///     #[serde(deserialize_with = "from_milli_ts")]
-        ///     time: NaiveDateTime
+        ///     #[serde(deserialize_with = "from_nano_tsopt")]
+        ///     time: Option<NaiveDateTime>
         /// }
         ///
         /// # fn example() -> Result<S, serde_json::Error> {
-        /// let my_s: S = serde_json::from_str(r#"{ "time": 1526522699918 }"#)?;
+        /// let my_s: S = serde_json::from_str(r#"{ "time": 1526522699918355733 }"#)?;
         /// # Ok(my_s)
         /// # }
         /// # fn main() { example().unwrap(); }
         /// ```
-        pub fn deserialize<'de, D>(d: D) -> Result<NaiveDateTime, D::Error>
+        pub fn deserialize<'de, D>(d: D) -> Result<Option<NaiveDateTime>, D::Error>
         where
             D: de::Deserializer<'de>,
         {
-            Ok(d.deserialize_i64(NaiveDateTimeFromMilliSecondsVisitor)?)
+            d.deserialize_option(OptionVisitor)
         }
 
-        struct NaiveDateTimeFromMilliSecondsVisitor;
+        struct OptionVisitor;
 
-        impl<'de> de::Visitor<'de> for NaiveDateTimeFromMilliSecondsVisitor {
-            type Value = NaiveDateTime;
+        impl<'de> de::Visitor<'de> for OptionVisitor {
+            type Value = Option<NaiveDateTime>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("a unix timestamp")
+                formatter.write_str("a unix timestamp or none")
             }
 
-            fn visit_i64<E>(self, value: i64) -> Result<NaiveDateTime, E>
+            fn visit_some<D>(self, d: D) -> Result<Option<NaiveDateTime>, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                d.deserialize_i64(NaiveDateTimeFromNanoSecondsVisitor).map(Some)
+            }
+
+            fn visit_none<E>(self) -> Result<Option<NaiveDateTime>, E>
             where
                 E: de::Error,
             {
-                NaiveDateTime::from_timestamp_opt(value / 1000, ((value % 1000) * 1_000_000) as u32)
-                    .ok_or_else(|| E::custom(ne_timestamp(value)))
+                Ok(None)
             }
 
-            fn visit_u64<E>(self, value: u64) -> Result<NaiveDateTime, E>
+            fn visit_unit<E>(self) -> Result<Option<NaiveDateTime>, E>
             where
                 E: de::Error,
             {
-                NaiveDateTime::from_timestamp_opt(
-                    (value / 1000) as i64,
-                    ((value % 1000) * 1_000_000) as u32,
-                )
-                .ok_or_else(|| E::custom(ne_timestamp(value)))
+                Ok(None)
             }
         }
     }
 
-    /// Used to serialize/deserialize from second-precision timestamps
+    /// Used to serialize/deserialize from microsecond-precision timestamps
     ///
     /// # Example:
     ///
     /// ```rust
-    /// # // We mark this ignored so that we can test on 1.13 (which does not
-    /// # // support custom derive), and run tests with --ignored on beta and
-    /// # // nightly to actually trigger these.
-    /// #
     /// # #[macro_use] extern crate serde_derive;
     /// # extern crate serde_json;
     /// # extern crate serde;
     /// # extern crate chrono;
-    /// # use chrono::{TimeZone, NaiveDate, NaiveDateTime, Utc};
-    /// use chrono::naive::serde::ts_seconds;
+    /// # use chrono::{NaiveDate, NaiveDateTime};
+    /// use chrono::naive::serde::ts_microseconds;
     /// #[derive(Deserialize, Serialize)]
     /// struct S {
-    ///     #[serde(with = "ts_seconds")]
+    ///     #[serde(with = "ts_microseconds")]
     ///     time: NaiveDateTime
     /// }
     ///
     /// # fn example() -> Result<S, serde_json::Error> {
-    /// let time = NaiveDate::from_ymd(2015, 5, 15).and_hms(10, 0, 0);
+    /// let time = NaiveDate::from_ymd(2018, 5, 17).and_hms_micro(02, 04, 59, 918355);
     /// let my_s = S {
     ///     time: time.clone(),
     /// };
     ///
     /// let as_string = serde_json::to_string(&my_s)?;
-    /// assert_eq!(as_string, r#"{"time":1431684000}"#);
+    /// assert_eq!(as_string, r#"{"time":1526522699918355}"#);
     /// let my_s: S = serde_json::from_str(&as_string)?;
     /// assert_eq!(my_s.time, time);
     /// # Ok(my_s)
     /// # }
     /// # fn main() { example().unwrap(); }
     /// ```
-    pub mod ts_seconds {
+    pub mod ts_microseconds {
         use core::fmt;
         use serdelib::{de, ser};
 
+        use div::div_mod_floor;
         use {ne_timestamp, NaiveDateTime};
 
-        /// Serialize a UTC datetime into an integer number of seconds since the epoch
+        /// Serialize a datetime into an integer number of microseconds since the epoch
         ///
         /// Intended for use with `serde`s `serialize_with` attribute.
         ///
         /// # Example:
         ///
         /// ```rust
-        /// # // We mark this ignored so that we can test on 1.13 (which does not
-        /// # // support custom derive), and run tests with --ignored on beta and
-        /// # // nightly to actually trigger these.
-        /// #
         /// # #[macro_use] extern crate serde_derive;
-        /// # #[macro_use] extern crate serde_json;
-        /// # #[macro_use] extern crate serde;
+        /// # extern crate serde_json;
+        /// # extern crate serde;
         /// # extern crate chrono;
-        /// # use chrono::{TimeZone, NaiveDate, NaiveDateTime, Utc};
-        /// # use serde::Serialize;
-        /// use chrono::naive::serde::ts_seconds::serialize as to_ts;
+        /// # use chrono::{NaiveDate, NaiveDateTime};
+        /// use chrono::naive::serde::ts_microseconds::serialize as to_micro_ts;
         /// #[derive(Serialize)]
         /// struct S {
-        ///     #[serde(serialize_with = "to_ts")]
+        ///     #[serde(serialize_with = "to_micro_ts")]
         ///     time: NaiveDateTime
         /// }
         ///
         /// # fn example() -> Result<String, serde_json::Error> {
         /// let my_s = S {
-        ///     time: NaiveDate::from_ymd(2015, 5, 15).and_hms(10, 0, 0),
+        ///     time: NaiveDate::from_ymd(2018, 5, 17).and_hms_micro(02, 04, 59, 918355),
         /// };
         /// let as_string = serde_json::to_string(&my_s)?;
-        /// assert_eq!(as_string, r#"{"time":1431684000}"#);
+        /// assert_eq!(as_string, r#"{"time":1526522699918355}"#);
         /// # Ok(as_string)
         /// # }
         /// # fn main() { example().unwrap(); }
@@ -2155,35 +2239,30 @@ pub mod serde {
         where
             S: ser::Serializer,
         {
-            serializer.serialize_i64(dt.timestamp())
+            serializer.serialize_i64(dt.timestamp_micros())
         }
 
-        /// Deserialize a `DateTime` from a seconds timestamp
+        /// Deserialize a `NaiveDateTime` from a microseconds timestamp
         ///
         /// Intended for use with `serde`s `deserialize_with` attribute.
         ///
         /// # Example:
         ///
         /// ```rust
-        /// # // We mark this ignored so that we can test on 1.13 (which does not
-        /// # // support custom derive), and run tests with --ignored on beta and
-        /// # // nightly to actually trigger these.
-        /// #
         /// # #[macro_use] extern crate serde_derive;
-        /// # #[macro_use] extern crate serde_json;
+        /// # extern crate serde_json;
         /// # extern crate serde;
         /// # extern crate chrono;
-        /// # use chrono::{NaiveDateTime, Utc};
-        /// # use serde::Deserialize;
-        /// use chrono::naive::serde::ts_seconds::deserialize as from_ts;
+        /// # use chrono::NaiveDateTime;
+        /// use chrono::naive::serde::ts_microseconds::deserialize as from_micro_ts;
         /// #[derive(Deserialize)]
         /// struct S {
-        ///     #[serde(deserialize_with = "from_ts")]
+        ///     #[serde(deserialize_with = "from_micro_ts")]
         ///     time: NaiveDateTime
         /// }
         ///
         /// # fn example() -> Result<S, serde_json::Error> {
-        /// let my_s: S = serde_json::from_str(r#"{ "time": 1431684000 }"#)?;
+        /// let my_s: S = serde_json::from_str(r#"{ "time": 1526522699918355 }"#)?;
         /// # Ok(my_s)
         /// # }
         /// # fn main() { example().unwrap(); }
@@ -2192,23 +2271,24 @@ pub mod serde {
         where
             D: de::Deserializer<'de>,
         {
-            Ok(d.deserialize_i64(NaiveDateTimeFromSecondsVisitor)?)
+            d.deserialize_i64(NaiveDateTimeFromMicroSecondsVisitor)
         }
 
-        struct NaiveDateTimeFromSecondsVisitor;
+        pub(super) struct NaiveDateTimeFromMicroSecondsVisitor;
 
-        impl<'de> de::Visitor<'de> for NaiveDateTimeFromSecondsVisitor {
+        impl<'de> de::Visitor<'de> for NaiveDateTimeFromMicroSecondsVisitor {
             type Value = NaiveDateTime;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("a unix timestamp")
+                formatter.write_str("a unix timestamp in microseconds")
             }
 
             fn visit_i64<E>(self, value: i64) -> Result<NaiveDateTime, E>
             where
                 E: de::Error,
             {
-                NaiveDateTime::from_timestamp_opt(value, 0)
+                let (secs, micros) = div_mod_floor(value, 1_000_000);
+                NaiveDateTime::from_timestamp_opt(secs, micros as u32 * 1_000)
                     .ok_or_else(|| E::custom(ne_timestamp(value)))
             }
 
@@ -2216,46 +2296,1022 @@ pub mod serde {
             where
                 E: de::Error,
             {
-                NaiveDateTime::from_timestamp_opt(value as i64, 0)
-                    .ok_or_else(|| E::custom(ne_timestamp(value)))
+                NaiveDateTime::from_timestamp_opt(
+                    (value / 1_000_000) as i64,
+                    (value % 1_000_000) as u32 * 1_000,
+                )
+                .ok_or_else(|| E::custom(ne_timestamp(value)))
             }
         }
     }
 
-    #[cfg(test)]
-    extern crate bincode;
-    #[cfg(test)]
-    extern crate serde_derive;
-    #[cfg(test)]
-    extern crate serde_json;
-
-    #[test]
-    fn test_serde_serialize() {
-        super::test_encodable_json(self::serde_json::to_string);
-    }
+    /// Used to serialize/deserialize from optional microsecond-precision timestamps
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate serde_derive;
+    /// # extern crate serde_json;
+    /// # extern crate serde;
+    /// # extern crate chrono;
+    /// # use chrono::{NaiveDate, NaiveDateTime};
+    /// use chrono::naive::serde::ts_microseconds_option;
+    /// #[derive(Deserialize, Serialize)]
+    /// struct S {
+    ///     #[serde(with = "ts_microseconds_option")]
+    ///     time: Option<NaiveDateTime>
+    /// }
+    ///
+    /// # fn example() -> Result<S, serde_json::Error> {
+    /// let time = Some(NaiveDate::from_ymd(2018, 5, 17).and_hms_micro(02, 04, 59, 918355));
+    /// let my_s = S {
+    ///     time: time.clone(),
+    /// };
+    ///
+    /// let as_string = serde_json::to_string(&my_s)?;
+    /// assert_eq!(as_string, r#"{"time":1526522699918355}"#);
+    /// let my_s: S = serde_json::from_str(&as_string)?;
+    /// assert_eq!(my_s.time, time);
+    /// # Ok(my_s)
+    /// # }
+    /// # fn main() { example().unwrap(); }
+    /// ```
+    pub mod ts_microseconds_option {
+        use core::fmt;
+        use serdelib::{de, ser};
 
-    #[test]
-    fn test_serde_deserialize() {
-        super::test_decodable_json(|input| self::serde_json::from_str(&input));
-    }
+        use NaiveDateTime;
 
-    // Bincode is relevant to test separately from JSON because
-    // it is not self-describing.
-    #[test]
-    fn test_serde_bincode() {
-        use self::bincode::{deserialize, serialize, Infinite};
-        use naive::NaiveDate;
+        use super::ts_microseconds::NaiveDateTimeFromMicroSecondsVisitor;
 
-        let dt = NaiveDate::from_ymd(2016, 7, 8).and_hms_milli(9, 10, 48, 90);
-        let encoded = serialize(&dt, Infinite).unwrap();
-        let decoded: NaiveDateTime = deserialize(&encoded).unwrap();
-        assert_eq!(dt, decoded);
-    }
+        /// Serialize a datetime into a microsecond timestamp since the epoch or none
+        ///
+        /// Intended for use with `serde`s `serialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate serde_derive;
+        /// # extern crate serde_json;
+        /// # extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::{NaiveDate, NaiveDateTime};
+        /// use chrono::naive::serde::ts_microseconds_option::serialize as to_micro_tsopt;
+        /// #[derive(Serialize)]
+        /// struct S {
+        ///     #[serde(serialize_with = "to_micro_tsopt")]
+        ///     time: Option<NaiveDateTime>
+        /// }
+        ///
+        /// # fn example() -> Result<String, serde_json::Error> {
+        /// let my_s = S {
+        ///     time: Some(NaiveDate::from_ymd(2018, 5, 17).and_hms_micro(02, 04, 59, 918355)),
+        /// };
+        /// let as_string = serde_json::to_string(&my_s)?;
+        /// assert_eq!(as_string, r#"{"time":1526522699918355}"#);
+        /// # Ok(as_string)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn serialize<S>(opt: &Option<NaiveDateTime>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            match *opt {
+                Some(ref dt) => serializer.serialize_some(&dt.timestamp_micros()),
+                None => serializer.serialize_none(),
+            }
+        }
 
-    #[test]
-    fn test_serde_bincode_optional() {
-        use self::bincode::{deserialize, serialize, Infinite};
-        use self::serde_derive::{Deserialize, Serialize};
+        /// Deserialize a `NaiveDateTime` from a microsecond timestamp or none
+        ///
+        /// Intended for use with `serde`s `deserialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate serde_derive;
+        /// # extern crate serde_json;
+        /// # extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::NaiveDateTime;
+        /// use chrono::naive::serde::ts_microseconds_option::deserialize as from_micro_tsopt;
+        /// #[derive(Deserialize)]
+        /// struct S {
+        ///     #[serde(deserialize_with = "from_micro_tsopt")]
+        ///     time: Option<NaiveDateTime>
+        /// }
+        ///
+        /// # fn example() -> Result<S, serde_json::Error> {
+        /// let my_s: S = serde_json::from_str(r#"{ "time": 1526522699918355 }"#)?;
+        /// # Ok(my_s)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn deserialize<'de, D>(d: D) -> Result<Option<NaiveDateTime>, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            d.deserialize_option(OptionVisitor)
+        }
+
+        struct OptionVisitor;
+
+        impl<'de> de::Visitor<'de> for OptionVisitor {
+            type Value = Option<NaiveDateTime>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a unix timestamp or none")
+            }
+
+            fn visit_some<D>(self, d: D) -> Result<Option<NaiveDateTime>, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                d.deserialize_i64(NaiveDateTimeFromMicroSecondsVisitor).map(Some)
+            }
+
+            fn visit_none<E>(self) -> Result<Option<NaiveDateTime>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+
+            fn visit_unit<E>(self) -> Result<Option<NaiveDateTime>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+        }
+    }
+
+    /// Used to serialize/deserialize from millisecond-precision timestamps
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate serde_derive;
+    /// # extern crate serde_json;
+    /// # extern crate serde;
+    /// # extern crate chrono;
+    /// # use chrono::{TimeZone, NaiveDate, NaiveDateTime, Utc};
+    /// use chrono::naive::serde::ts_milliseconds;
+    /// #[derive(Deserialize, Serialize)]
+    /// struct S {
+    ///     #[serde(with = "ts_milliseconds")]
+    ///     time: NaiveDateTime
+    /// }
+    ///
+    /// # fn example() -> Result<S, serde_json::Error> {
+    /// let time = NaiveDate::from_ymd(2018, 5, 17).and_hms_milli(02, 04, 59, 918);
+    /// let my_s = S {
+    ///     time: time.clone(),
+    /// };
+    ///
+    /// let as_string = serde_json::to_string(&my_s)?;
+    /// assert_eq!(as_string, r#"{"time":1526522699918}"#);
+    /// let my_s: S = serde_json::from_str(&as_string)?;
+    /// assert_eq!(my_s.time, time);
+    /// # Ok(my_s)
+    /// # }
+    /// # fn main() { example().unwrap(); }
+    /// ```
+    pub mod ts_milliseconds {
+        use core::fmt;
+        use serdelib::{de, ser};
+
+        use div::div_mod_floor;
+        use {ne_timestamp, NaiveDateTime};
+
+        /// Serialize a UTC datetime into an integer number of milliseconds since the epoch
+        ///
+        /// Intended for use with `serde`s `serialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate serde_derive;
+        /// # #[macro_use] extern crate serde_json;
+        /// # #[macro_use] extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::{TimeZone, NaiveDate, NaiveDateTime, Utc};
+        /// # use serde::Serialize;
+        /// use chrono::naive::serde::ts_milliseconds::serialize as to_milli_ts;
+        /// #[derive(Serialize)]
+        /// struct S {
+        ///     #[serde(serialize_with = "to_milli_ts")]
+        ///     time: NaiveDateTime
+        /// }
+        ///
+        /// # fn example() -> Result<String, serde_json::Error> {
+        /// let my_s = S {
+        ///     time: NaiveDate::from_ymd(2018, 5, 17).and_hms_milli(02, 04, 59, 918),
+        /// };
+        /// let as_string = serde_json::to_string(&my_s)?;
+        /// assert_eq!(as_string, r#"{"time":1526522699918}"#);
+        /// # Ok(as_string)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn serialize<S>(dt: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            serializer.serialize_i64(dt.timestamp_millis())
+        }
+
+        /// Deserialize a `DateTime` from a milliseconds timestamp
+        ///
+        /// Intended for use with `serde`s `deserialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate serde_derive;
+        /// # #[macro_use] extern crate serde_json;
+        /// # extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::{NaiveDateTime, Utc};
+        /// # use serde::Deserialize;
+        /// use chrono::naive::serde::ts_milliseconds::deserialize as from_milli_ts;
+        /// #[derive(Deserialize)]
+        /// struct S {
+        ///     #[serde(deserialize_with = "from_milli_ts")]
+        ///     time: NaiveDateTime
+        /// }
+        ///
+        /// # fn example() -> Result<S, serde_json::Error> {
+        /// let my_s: S = serde_json::from_str(r#"{ "time": 1526522699918 }"#)?;
+        /// # Ok(my_s)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn deserialize<'de, D>(d: D) -> Result<NaiveDateTime, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            Ok(d.deserialize_i64(NaiveDateTimeFromMilliSecondsVisitor)?)
+        }
+
+        pub(super) struct NaiveDateTimeFromMilliSecondsVisitor;
+
+        impl<'de> de::Visitor<'de> for NaiveDateTimeFromMilliSecondsVisitor {
+            type Value = NaiveDateTime;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a unix timestamp")
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<NaiveDateTime, E>
+            where
+                E: de::Error,
+            {
+                let (secs, millis) = div_mod_floor(value, 1000);
+                NaiveDateTime::from_timestamp_opt(secs, millis as u32 * 1_000_000)
+                    .ok_or_else(|| E::custom(ne_timestamp(value)))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<NaiveDateTime, E>
+            where
+                E: de::Error,
+            {
+                NaiveDateTime::from_timestamp_opt(
+                    (value / 1000) as i64,
+                    ((value % 1000) * 1_000_000) as u32,
+                )
+                .ok_or_else(|| E::custom(ne_timestamp(value)))
+            }
+        }
+    }
+
+    /// Used to serialize/deserialize from optional millisecond-precision timestamps
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate serde_derive;
+    /// # extern crate serde_json;
+    /// # extern crate serde;
+    /// # extern crate chrono;
+    /// # use chrono::{NaiveDate, NaiveDateTime};
+    /// use chrono::naive::serde::ts_milliseconds_option;
+    /// #[derive(Deserialize, Serialize)]
+    /// struct S {
+    ///     #[serde(with = "ts_milliseconds_option")]
+    ///     time: Option<NaiveDateTime>
+    /// }
+    ///
+    /// # fn example() -> Result<S, serde_json::Error> {
+    /// let time = Some(NaiveDate::from_ymd(2018, 5, 17).and_hms_milli(02, 04, 59, 918));
+    /// let my_s = S {
+    ///     time: time.clone(),
+    /// };
+    ///
+    /// let as_string = serde_json::to_string(&my_s)?;
+    /// assert_eq!(as_string, r#"{"time":1526522699918}"#);
+    /// let my_s: S = serde_json::from_str(&as_string)?;
+    /// assert_eq!(my_s.time, time);
+    /// # Ok(my_s)
+    /// # }
+    /// # fn main() { example().unwrap(); }
+    /// ```
+    pub mod ts_milliseconds_option {
+        use core::fmt;
+        use serdelib::{de, ser};
+
+        use NaiveDateTime;
+
+        use super::ts_milliseconds::NaiveDateTimeFromMilliSecondsVisitor;
+
+        /// Serialize a datetime into a millisecond timestamp since the epoch or none
+        ///
+        /// Intended for use with `serde`s `serialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate serde_derive;
+        /// # extern crate serde_json;
+        /// # extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::{NaiveDate, NaiveDateTime};
+        /// use chrono::naive::serde::ts_milliseconds_option::serialize as to_milli_tsopt;
+        /// #[derive(Serialize)]
+        /// struct S {
+        ///     #[serde(serialize_with = "to_milli_tsopt")]
+        ///     time: Option<NaiveDateTime>
+        /// }
+        ///
+        /// # fn example() -> Result<String, serde_json::Error> {
+        /// let my_s = S {
+        ///     time: Some(NaiveDate::from_ymd(2018, 5, 17).and_hms_milli(02, 04, 59, 918)),
+        /// };
+        /// let as_string = serde_json::to_string(&my_s)?;
+        /// assert_eq!(as_string, r#"{"time":1526522699918}"#);
+        /// # Ok(as_string)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn serialize<S>(opt: &Option<NaiveDateTime>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            match *opt {
+                Some(ref dt) => serializer.serialize_some(&dt.timestamp_millis()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        /// Deserialize a `NaiveDateTime` from a millisecond timestamp or none
+        ///
+        /// Intended for use with `serde`s `deserialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate serde_derive;
+        /// # extern crate serde_json;
+        /// # extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::NaiveDateTime;
+        /// use chrono::naive::serde::ts_milliseconds_option::deserialize as from_milli_tsopt;
+        /// #[derive(Deserialize)]
+        /// struct S {
+        ///     #[serde(deserialize_with = "from_milli_tsopt")]
+        ///     time: Option<NaiveDateTime>
+        /// }
+        ///
+        /// # fn example() -> Result<S, serde_json::Error> {
+        /// let my_s: S = serde_json::from_str(r#"{ "time": 1526522699918 }"#)?;
+        /// # Ok(my_s)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn deserialize<'de, D>(d: D) -> Result<Option<NaiveDateTime>, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            d.deserialize_option(OptionVisitor)
+        }
+
+        struct OptionVisitor;
+
+        impl<'de> de::Visitor<'de> for OptionVisitor {
+            type Value = Option<NaiveDateTime>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a unix timestamp or none")
+            }
+
+            fn visit_some<D>(self, d: D) -> Result<Option<NaiveDateTime>, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                d.deserialize_i64(NaiveDateTimeFromMilliSecondsVisitor).map(Some)
+            }
+
+            fn visit_none<E>(self) -> Result<Option<NaiveDateTime>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+
+            fn visit_unit<E>(self) -> Result<Option<NaiveDateTime>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+        }
+    }
+
+    /// Used to serialize/deserialize from second-precision timestamps
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate serde_derive;
+    /// # extern crate serde_json;
+    /// # extern crate serde;
+    /// # extern crate chrono;
+    /// # use chrono::{TimeZone, NaiveDate, NaiveDateTime, Utc};
+    /// use chrono::naive::serde::ts_seconds;
+    /// #[derive(Deserialize, Serialize)]
+    /// struct S {
+    ///     #[serde(with = "ts_seconds")]
+    ///     time: NaiveDateTime
+    /// }
+    ///
+    /// # fn example() -> Result<S, serde_json::Error> {
+    /// let time = NaiveDate::from_ymd(2015, 5, 15).and_hms(10, 0, 0);
+    /// let my_s = S {
+    ///     time: time.clone(),
+    /// };
+    ///
+    /// let as_string = serde_json::to_string(&my_s)?;
+    /// assert_eq!(as_string, r#"{"time":1431684000}"#);
+    /// let my_s: S = serde_json::from_str(&as_string)?;
+    /// assert_eq!(my_s.time, time);
+    /// # Ok(my_s)
+    /// # }
+    /// # fn main() { example().unwrap(); }
+    /// ```
+    pub mod ts_seconds {
+        use core::fmt;
+        use serdelib::{de, ser};
+
+        use {ne_timestamp, NaiveDateTime};
+
+        /// Serialize a UTC datetime into an integer number of seconds since the epoch
+        ///
+        /// Intended for use with `serde`s `serialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # // We mark this ignored so that we can test on 1.13 (which does not
+        /// # // support custom derive), and run tests with --ignored on beta and
+        /// # // nightly to actually trigger these.
+        /// #
+        /// # #[macro_use] extern crate serde_derive;
+        /// # #[macro_use] extern crate serde_json;
+        /// # #[macro_use] extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::{TimeZone, NaiveDate, NaiveDateTime, Utc};
+        /// # use serde::Serialize;
+        /// use chrono::naive::serde::ts_seconds::serialize as to_ts;
+        /// #[derive(Serialize)]
+        /// struct S {
+        ///     #[serde(serialize_with = "to_ts")]
+        ///     time: NaiveDateTime
+        /// }
+        ///
+        /// # fn example() -> Result<String, serde_json::Error> {
+        /// let my_s = S {
+        ///     time: NaiveDate::from_ymd(2015, 5, 15).and_hms(10, 0, 0),
+        /// };
+        /// let as_string = serde_json::to_string(&my_s)?;
+        /// assert_eq!(as_string, r#"{"time":1431684000}"#);
+        /// # Ok(as_string)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn serialize<S>(dt: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            serializer.serialize_i64(dt.timestamp())
+        }
+
+        /// Deserialize a `DateTime` from a seconds timestamp
+        ///
+        /// Intended for use with `serde`s `deserialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # // We mark this ignored so that we can test on 1.13 (which does not
+        /// # // support custom derive), and run tests with --ignored on beta and
+        /// # // nightly to actually trigger these.
+        /// #
+        /// # #[macro_use] extern crate serde_derive;
+        /// # #[macro_use] extern crate serde_json;
+        /// # extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::{NaiveDateTime, Utc};
+        /// # use serde::Deserialize;
+        /// use chrono::naive::serde::ts_seconds::deserialize as from_ts;
+        /// #[derive(Deserialize)]
+        /// struct S {
+        ///     #[serde(deserialize_with = "from_ts")]
+        ///     time: NaiveDateTime
+        /// }
+        ///
+        /// # fn example() -> Result<S, serde_json::Error> {
+        /// let my_s: S = serde_json::from_str(r#"{ "time": 1431684000 }"#)?;
+        /// # Ok(my_s)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn deserialize<'de, D>(d: D) -> Result<NaiveDateTime, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            Ok(d.deserialize_i64(NaiveDateTimeFromSecondsVisitor)?)
+        }
+
+        pub(super) struct NaiveDateTimeFromSecondsVisitor;
+
+        impl<'de> de::Visitor<'de> for NaiveDateTimeFromSecondsVisitor {
+            type Value = NaiveDateTime;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a unix timestamp")
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<NaiveDateTime, E>
+            where
+                E: de::Error,
+            {
+                NaiveDateTime::from_timestamp_opt(value, 0)
+                    .ok_or_else(|| E::custom(ne_timestamp(value)))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<NaiveDateTime, E>
+            where
+                E: de::Error,
+            {
+                NaiveDateTime::from_timestamp_opt(value as i64, 0)
+                    .ok_or_else(|| E::custom(ne_timestamp(value)))
+            }
+        }
+    }
+
+    /// Used to serialize/deserialize from optional second-precision timestamps
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # // We mark this ignored so that we can test on 1.13 (which does not
+    /// # // support custom derive), and run tests with --ignored on beta and
+    /// # // nightly to actually trigger these.
+    /// #
+    /// # #[macro_use] extern crate serde_derive;
+    /// # extern crate serde_json;
+    /// # extern crate serde;
+    /// # extern crate chrono;
+    /// # use chrono::{NaiveDate, NaiveDateTime};
+    /// use chrono::naive::serde::ts_seconds_option;
+    /// #[derive(Deserialize, Serialize)]
+    /// struct S {
+    ///     #[serde(with = "ts_seconds_option")]
+    ///     time: Option<NaiveDateTime>
+    /// }
+    ///
+    /// # fn example() -> Result<S, serde_json::Error> {
+    /// let time = Some(NaiveDate::from_ymd(2018, 5, 17).and_hms(02, 04, 59));
+    /// let my_s = S {
+    ///     time: time.clone(),
+    /// };
+    ///
+    /// let as_string = serde_json::to_string(&my_s)?;
+    /// assert_eq!(as_string, r#"{"time":1526522699}"#);
+    /// let my_s: S = serde_json::from_str(&as_string)?;
+    /// assert_eq!(my_s.time, time);
+    /// # Ok(my_s)
+    /// # }
+    /// # fn main() { example().unwrap(); }
+    /// ```
+    pub mod ts_seconds_option {
+        use core::fmt;
+        use serdelib::{de, ser};
+
+        use NaiveDateTime;
+
+        use super::ts_seconds::NaiveDateTimeFromSecondsVisitor;
+
+        /// Serialize a datetime into a second timestamp since the epoch or none
+        ///
+        /// Intended for use with `serde`s `serialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # // We mark this ignored so that we can test on 1.13 (which does not
+        /// # // support custom derive), and run tests with --ignored on beta and
+        /// # // nightly to actually trigger these.
+        /// #
+        /// # #[macro_use] extern crate serde_derive;
+        /// # extern crate serde_json;
+        /// # extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::{NaiveDate, NaiveDateTime};
+        /// use chrono::naive::serde::ts_seconds_option::serialize as to_tsopt;
+        /// #[derive(Serialize)]
+        /// struct S {
+        ///     #[serde(serialize_with = "to_tsopt")]
+        ///     time: Option<NaiveDateTime>
+        /// }
+        ///
+        /// # fn example() -> Result<String, serde_json::Error> {
+        /// let my_s = S {
+        ///     time: Some(NaiveDate::from_ymd(2018, 5, 17).and_hms(02, 04, 59)),
+        /// };
+        /// let as_string = serde_json::to_string(&my_s)?;
+        /// assert_eq!(as_string, r#"{"time":1526522699}"#);
+        /// # Ok(as_string)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn serialize<S>(opt: &Option<NaiveDateTime>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            match *opt {
+                Some(ref dt) => serializer.serialize_some(&dt.timestamp()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        /// Deserialize a `NaiveDateTime` from a second timestamp or none
+        ///
+        /// Intended for use with `serde`s `deserialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # // We mark this ignored so that we can test on 1.13 (which does not
+        /// # // support custom derive), and run tests with --ignored on beta and
+        /// # // nightly to actually trigger these.
+        /// #
+        /// # #[macro_use] extern crate serde_derive;
+        /// # extern crate serde_json;
+        /// # extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::NaiveDateTime;
+        /// use chrono::naive::serde::ts_seconds_option::deserialize as from_tsopt;
+        /// #[derive(Deserialize)]
+        /// struct S {
+        ///     #[serde(deserialize_with = "from_tsopt")]
+        ///     time: Option<NaiveDateTime>
+        /// }
+        ///
+        /// # fn example() -> Result<S, serde_json::Error> {
+        /// let my_s: S = serde_json::from_str(r#"{ "time": 1526522699 }"#)?;
+        /// # Ok(my_s)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn deserialize<'de, D>(d: D) -> Result<Option<NaiveDateTime>, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            d.deserialize_option(OptionVisitor)
+        }
+
+        struct OptionVisitor;
+
+        impl<'de> de::Visitor<'de> for OptionVisitor {
+            type Value = Option<NaiveDateTime>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a unix timestamp or none")
+            }
+
+            fn visit_some<D>(self, d: D) -> Result<Option<NaiveDateTime>, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                d.deserialize_i64(NaiveDateTimeFromSecondsVisitor).map(Some)
+            }
+
+            fn visit_none<E>(self) -> Result<Option<NaiveDateTime>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+
+            fn visit_unit<E>(self) -> Result<Option<NaiveDateTime>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+        }
+    }
+
+    /// Used to serialize/deserialize from timestamps in fractional seconds
+    ///
+    /// This is the representation of `time.time()` and `datetime.timestamp()` in Python.
+    /// A `f64` only has a precision of about a microsecond for current dates, so
+    /// nanoseconds do not survive a round-trip. Integers are accepted as well.
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate serde_derive;
+    /// # extern crate serde_json;
+    /// # extern crate serde;
+    /// # extern crate chrono;
+    /// # use chrono::{NaiveDate, NaiveDateTime};
+    /// use chrono::naive::serde::ts_seconds_f64;
+    /// #[derive(Deserialize, Serialize)]
+    /// struct S {
+    ///     #[serde(with = "ts_seconds_f64")]
+    ///     time: NaiveDateTime
+    /// }
+    ///
+    /// # fn example() -> Result<S, serde_json::Error> {
+    /// let time = NaiveDate::from_ymd(2018, 5, 17).and_hms_milli(02, 04, 59, 500);
+    /// let my_s = S {
+    ///     time: time.clone(),
+    /// };
+    ///
+    /// let as_string = serde_json::to_string(&my_s)?;
+    /// assert_eq!(as_string, r#"{"time":1526522699.5}"#);
+    /// let my_s: S = serde_json::from_str(&as_string)?;
+    /// assert_eq!(my_s.time, time);
+    /// # Ok(my_s)
+    /// # }
+    /// # fn main() { example().unwrap(); }
+    /// ```
+    pub mod ts_seconds_f64 {
+        use core::fmt;
+        use serdelib::{de, ser};
+
+        use {from_timestamp_f64, ne_timestamp, NaiveDateTime};
+
+        use super::timestamp_f64;
+
+        /// Serialize a datetime into a floating point number of seconds since the epoch
+        ///
+        /// Intended for use with `serde`s `serialize_with` attribute.
+        pub fn serialize<S>(dt: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            serializer.serialize_f64(timestamp_f64(dt))
+        }
+
+        /// Deserialize a `NaiveDateTime` from a floating point seconds timestamp
+        ///
+        /// Intended for use with `serde`s `deserialize_with` attribute.
+        pub fn deserialize<'de, D>(d: D) -> Result<NaiveDateTime, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            d.deserialize_f64(NaiveDateTimeFromFloatSecondsVisitor)
+        }
+
+        pub(super) struct NaiveDateTimeFromFloatSecondsVisitor;
+
+        impl<'de> de::Visitor<'de> for NaiveDateTimeFromFloatSecondsVisitor {
+            type Value = NaiveDateTime;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a unix timestamp in fractional seconds")
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<NaiveDateTime, E>
+            where
+                E: de::Error,
+            {
+                from_timestamp_f64(value)
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<NaiveDateTime, E>
+            where
+                E: de::Error,
+            {
+                NaiveDateTime::from_timestamp_opt(value, 0)
+                    .ok_or_else(|| E::custom(ne_timestamp(value)))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<NaiveDateTime, E>
+            where
+                E: de::Error,
+            {
+                NaiveDateTime::from_timestamp_opt(value as i64, 0)
+                    .ok_or_else(|| E::custom(ne_timestamp(value)))
+            }
+        }
+    }
+
+    /// Used to serialize/deserialize from optional timestamps in fractional seconds
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # // We mark this ignored so that we can test on 1.13 (which does not
+    /// # // support custom derive), and run tests with --ignored on beta and
+    /// # // nightly to actually trigger these.
+    /// #
+    /// # #[macro_use] extern crate serde_derive;
+    /// # extern crate serde_json;
+    /// # extern crate serde;
+    /// # extern crate chrono;
+    /// # use chrono::{NaiveDate, NaiveDateTime};
+    /// use chrono::naive::serde::ts_seconds_f64_option;
+    /// #[derive(Deserialize, Serialize)]
+    /// struct S {
+    ///     #[serde(with = "ts_seconds_f64_option")]
+    ///     time: Option<NaiveDateTime>
+    /// }
+    ///
+    /// # fn example() -> Result<S, serde_json::Error> {
+    /// let time = Some(NaiveDate::from_ymd(2018, 5, 17).and_hms_milli(02, 04, 59, 500));
+    /// let my_s = S {
+    ///     time: time.clone(),
+    /// };
+    ///
+    /// let as_string = serde_json::to_string(&my_s)?;
+    /// assert_eq!(as_string, r#"{"time":1526522699.5}"#);
+    /// let my_s: S = serde_json::from_str(&as_string)?;
+    /// assert_eq!(my_s.time, time);
+    /// # Ok(my_s)
+    /// # }
+    /// # fn main() { example().unwrap(); }
+    /// ```
+    pub mod ts_seconds_f64_option {
+        use core::fmt;
+        use serdelib::{de, ser};
+
+        use NaiveDateTime;
+
+        use super::timestamp_f64;
+        use super::ts_seconds_f64::NaiveDateTimeFromFloatSecondsVisitor;
+
+        /// Serialize a datetime into a floating point number of seconds since the epoch or none
+        ///
+        /// Intended for use with `serde`s `serialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # // We mark this ignored so that we can test on 1.13 (which does not
+        /// # // support custom derive), and run tests with --ignored on beta and
+        /// # // nightly to actually trigger these.
+        /// #
+        /// # #[macro_use] extern crate serde_derive;
+        /// # extern crate serde_json;
+        /// # extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::{NaiveDate, NaiveDateTime};
+        /// use chrono::naive::serde::ts_seconds_f64_option::serialize as to_float_tsopt;
+        /// #[derive(Serialize)]
+        /// struct S {
+        ///     #[serde(serialize_with = "to_float_tsopt")]
+        ///     time: Option<NaiveDateTime>
+        /// }
+        ///
+        /// # fn example() -> Result<String, serde_json::Error> {
+        /// let my_s = S {
+        ///     time: Some(NaiveDate::from_ymd(2018, 5, 17).and_hms_milli(02, 04, 59, 500)),
+        /// };
+        /// let as_string = serde_json::to_string(&my_s)?;
+        /// assert_eq!(as_string, r#"{"time":1526522699.5}"#);
+        /// # Ok(as_string)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn serialize<S>(opt: &Option<NaiveDateTime>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            match *opt {
+                Some(ref dt) => serializer.serialize_some(&timestamp_f64(dt)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        /// Deserialize a `NaiveDateTime` from a floating point seconds timestamp or none
+        ///
+        /// Intended for use with `serde`s `deserialize_with` attribute.
+        ///
+        /// # Example:
+        ///
+        /// ```rust
+        /// # // We mark this ignored so that we can test on 1.13 (which does not
+        /// # // support custom derive), and run tests with --ignored on beta and
+        /// # // nightly to actually trigger these.
+        /// #
+        /// # #[macro_use] extern crate serde_derive;
+        /// # extern crate serde_json;
+        /// # extern crate serde;
+        /// # extern crate chrono;
+        /// # use chrono::NaiveDateTime;
+        /// use chrono::naive::serde::ts_seconds_f64_option::deserialize as from_float_tsopt;
+        /// #[derive(Deserialize)]
+        /// struct S {
+        ///     #[serde(deserialize_with = "from_float_tsopt")]
+        ///     time: Option<NaiveDateTime>
+        /// }
+        ///
+        /// # fn example() -> Result<S, serde_json::Error> {
+        /// let my_s: S = serde_json::from_str(r#"{ "time": 1526522699.5 }"#)?;
+        /// # Ok(my_s)
+        /// # }
+        /// # fn main() { example().unwrap(); }
+        /// ```
+        pub fn deserialize<'de, D>(d: D) -> Result<Option<NaiveDateTime>, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            d.deserialize_option(OptionVisitor)
+        }
+
+        struct OptionVisitor;
+
+        impl<'de> de::Visitor<'de> for OptionVisitor {
+            type Value = Option<NaiveDateTime>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a unix timestamp or none")
+            }
+
+            fn visit_some<D>(self, d: D) -> Result<Option<NaiveDateTime>, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                d.deserialize_f64(NaiveDateTimeFromFloatSecondsVisitor).map(Some)
+            }
+
+            fn visit_none<E>(self) -> Result<Option<NaiveDateTime>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+
+            fn visit_unit<E>(self) -> Result<Option<NaiveDateTime>, E>
+            where
+                E: de::Error,
+            {
+                Ok(None)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    extern crate bincode;
+    #[cfg(test)]
+    extern crate bincode1;
+    #[cfg(test)]
+    extern crate serde_derive;
+    #[cfg(test)]
+    extern crate serde_json;
+
+    #[test]
+    fn test_serde_serialize() {
+        super::test_encodable_json(self::serde_json::to_string);
+    }
+
+    #[test]
+    fn test_serde_deserialize() {
+        super::test_decodable_json(|input| self::serde_json::from_str(&input));
+    }
+
+    // Bincode is relevant to test separately from JSON because
+    // it is not self-describing.
+    #[test]
+    fn test_serde_bincode() {
+        use self::bincode::{deserialize, serialize, Infinite};
+        use naive::NaiveDate;
+
+        let dt = NaiveDate::from_ymd(2016, 7, 8).and_hms_milli(9, 10, 48, 90);
+        let encoded = serialize(&dt, Infinite).unwrap();
+        let decoded: NaiveDateTime = deserialize(&encoded).unwrap();
+        assert_eq!(dt, decoded);
+    }
+
+    #[test]
+    fn test_serde_bincode_optional() {
+        use self::bincode::{deserialize, serialize, Infinite};
+        use self::serde_derive::{Deserialize, Serialize};
         use prelude::*;
         use serde::ts_nanoseconds_option;
 
@@ -2272,6 +3328,91 @@ pub mod serde {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_serde_ts_modules() {
+        use self::serde_derive::{Deserialize, Serialize};
+        use self::serde_json::{from_str, to_string};
+        use naive::NaiveDate;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Test {
+            #[serde(with = "ts_microseconds")]
+            us: NaiveDateTime,
+            #[serde(with = "ts_nanoseconds_option")]
+            ns_opt: Option<NaiveDateTime>,
+            #[serde(with = "ts_microseconds_option")]
+            us_opt: Option<NaiveDateTime>,
+            #[serde(with = "ts_milliseconds_option")]
+            ms_opt: Option<NaiveDateTime>,
+            #[serde(with = "ts_seconds_option")]
+            s_opt: Option<NaiveDateTime>,
+            #[serde(with = "ts_seconds_f64")]
+            f: NaiveDateTime,
+            #[serde(with = "ts_seconds_f64_option")]
+            f_opt: Option<NaiveDateTime>,
+        }
+
+        let dt = NaiveDate::from_ymd(2018, 5, 17).and_hms_milli(2, 4, 59, 250);
+        let value = Test {
+            us: dt,
+            ns_opt: Some(dt),
+            us_opt: None,
+            ms_opt: Some(dt),
+            s_opt: None,
+            f: dt,
+            f_opt: Some(dt),
+        };
+        let json = to_string(&value).unwrap();
+        assert_eq!(
+            json,
+            r#"{"us":1526522699250000,"ns_opt":1526522699250000000,"us_opt":null,"ms_opt":1526522699250,"s_opt":null,"f":1526522699.25,"f_opt":1526522699.25}"#
+        );
+        assert_eq!(from_str::<Test>(&json).unwrap(), value);
+
+        // negative timestamps are rounded towards negative infinity
+        let before = NaiveDate::from_ymd(1969, 12, 31).and_hms_micro(23, 59, 59, 500_000);
+        let value = Test {
+            us: before,
+            ns_opt: Some(before),
+            us_opt: Some(before),
+            ms_opt: Some(before),
+            s_opt: Some(NaiveDate::from_ymd(1970, 1, 1).and_hms(0, 0, 1)),
+            f: before,
+            f_opt: None,
+        };
+        let json = to_string(&value).unwrap();
+        assert_eq!(
+            json,
+            r#"{"us":-500000,"ns_opt":-500000000,"us_opt":-500000,"ms_opt":-500,"s_opt":1,"f":-0.5,"f_opt":null}"#
+        );
+        assert_eq!(from_str::<Test>(&json).unwrap(), value);
+        let ms =
+            ts_milliseconds::deserialize(&mut self::serde_json::Deserializer::from_str("-500"));
+        assert_eq!(ms.unwrap(), before);
+
+        // integral floats as written by Python
+        let json =
+            r#"{"us":0,"ns_opt":null,"us_opt":null,"ms_opt":null,"s_opt":null,"f":1,"f_opt":2}"#;
+        let decoded = from_str::<Test>(json).unwrap();
+        assert_eq!(decoded.f, NaiveDateTime::from_timestamp(1, 0));
+        assert_eq!(decoded.f_opt, Some(NaiveDateTime::from_timestamp(2, 0)));
+        let json = r#"{"us":0,"ns_opt":null,"us_opt":null,"ms_opt":null,"s_opt":null,"f":1e300,"f_opt":null}"#;
+        assert!(from_str::<Test>(json).is_err());
+    }
+
+    #[test]
+    fn test_serde_bincode_1() {
+        // bincode 1.x is not human-readable, but still uses the string
+        // to read data written by earlier versions
+        use self::bincode1::{deserialize, serialize};
+        use naive::NaiveDate;
+
+        let dt = NaiveDate::from_ymd(2016, 7, 8).and_hms_milli(9, 10, 48, 90);
+        let encoded = serialize(&dt).unwrap();
+        assert_eq!(encoded, serialize(&"2016-07-08T09:10:48.090").unwrap());
+        assert_eq!(deserialize::<NaiveDateTime>(&encoded).unwrap(), dt);
+    }
 }
 
 #[cfg(test)]
@@ -2369,6 +3510,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_datetime_period_since() {
+        use Period;
+
+        let ymdhms = |y, m, d, h, n, s| NaiveDate::from_ymd(y, m, d).and_hms(h, n, s);
+        let since = |a: NaiveDateTime, b| a.period_since(b);
+        assert_eq!(
+            since(ymdhms(2016, 2, 29, 0, 0, 0), ymdhms(2016, 2, 29, 0, 0, 0)),
+            Period::zero()
+        );
+        assert_eq!(
+            since(ymdhms(2016, 3, 29, 0, 0, 0), ymdhms(2016, 2, 29, 0, 0, 1)),
+            Period::with_time(0, 0, 28, Duration::seconds(86_399))
+        );
+        assert_eq!(
+            since(ymdhms(2017, 2, 28, 12, 0, 0), ymdhms(2016, 2, 29, 6, 0, 0)),
+            Period::with_time(0, 11, 30, Duration::hours(6))
+        );
+        assert_eq!(
+            since(ymdhms(2016, 2, 29, 6, 0, 0), ymdhms(2017, 2, 28, 12, 0, 0)),
+            Period::with_time(0, -11, -30, Duration::hours(-6))
+        );
+        assert_eq!(
+            since(ymdhms(2017, 3, 1, 5, 0, 0), ymdhms(2016, 2, 29, 6, 0, 0)),
+            Period::with_time(0, 11, 30, Duration::hours(23))
+        );
+    }
+
     #[test]
     fn test_datetime_addassignment() {
         let ymdhms = |y, m, d, h, n, s| NaiveDate::from_ymd(y, m, d).and_hms(h, n, s);
@@ -3,9 +3,12 @@
 
 //! ISO 8601 week.
 
-use core::fmt;
+use core::{fmt, str};
 
 use super::internals::{DateImpl, Of, YearFlags};
+use format::{ParseError, ParseResult, INVALID, OUT_OF_RANGE};
+use naive::NaiveDate;
+use {Datelike, Weekday};
 
 /// ISO 8601 week.
 ///
@@ -140,6 +143,127 @@ impl fmt::Debug for IsoWeek {
     }
 }
 
+/// The `Display` output is the same as the `Debug` output, e.g. `2015-W36`.
+impl fmt::Display for IsoWeek {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Parsing the `Display` form of an ISO week, e.g. `2015-W36`.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Datelike, NaiveDate, IsoWeek};
+///
+/// let week = NaiveDate::from_ymd(2020, 1, 29).iso_week();
+/// assert_eq!("2020-W05".parse::<IsoWeek>(), Ok(week));
+/// assert_eq!("+10000-W52".parse::<IsoWeek>(), Ok(NaiveDate::from_ymd(10000, 12, 31).iso_week()));
+/// assert!("2021-W53".parse::<IsoWeek>().is_err()); // 2021 has only 52 weeks
+/// assert!("2020-05".parse::<IsoWeek>().is_err());
+/// ```
+impl str::FromStr for IsoWeek {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> ParseResult<IsoWeek> {
+        let sep = s.rfind("-W").ok_or(INVALID)?;
+        let (year, week) = (&s[..sep], &s[sep + 2..]);
+        let digits = year.trim_start_matches(|c| c == '+' || c == '-');
+        if digits.len() < 4
+            || year.len() - digits.len() > 1
+            || !digits.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(INVALID);
+        }
+        if week.len() != 2 || !week.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(INVALID);
+        }
+        let year = year.parse::<i32>().map_err(|_| OUT_OF_RANGE)?;
+        let week = week.parse::<u32>().map_err(|_| INVALID)?;
+        // the first and the last supported weeks are only partially representable
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+            .or_else(|| NaiveDate::from_isoywd_opt(year, week, Weekday::Sun))
+            .map(|date| date.iso_week())
+            .ok_or(OUT_OF_RANGE)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::IsoWeek;
+    use core::fmt;
+    use serdelib::{de, ser};
+
+    /// Serialize into the `Display` form, e.g. `2015-W36`
+    impl ser::Serialize for IsoWeek {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            serializer.collect_str(self)
+        }
+    }
+
+    struct IsoWeekVisitor;
+
+    impl<'de> de::Visitor<'de> for IsoWeekVisitor {
+        type Value = IsoWeek;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "an ISO week like `2015-W36`")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<IsoWeek, E>
+        where
+            E: de::Error,
+        {
+            value.parse().map_err(E::custom)
+        }
+    }
+
+    impl<'de> de::Deserialize<'de> for IsoWeek {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_str(IsoWeekVisitor)
+        }
+    }
+
+    #[cfg(test)]
+    extern crate bincode;
+    #[cfg(test)]
+    extern crate serde_json;
+
+    #[test]
+    fn test_serde_json() {
+        use self::serde_json::{from_str, to_string};
+        use naive::NaiveDate;
+        use Datelike;
+
+        let week = NaiveDate::from_ymd(2020, 1, 29).iso_week();
+        assert_eq!(to_string(&week).ok(), Some(r#""2020-W05""#.into()));
+        assert_eq!(from_str::<IsoWeek>(r#""2020-W05""#).ok(), Some(week));
+        assert!(from_str::<IsoWeek>(r#""2020-W5""#).is_err());
+        assert!(from_str::<IsoWeek>("202005").is_err());
+    }
+
+    #[test]
+    fn test_serde_bincode() {
+        // Bincode is relevant to test separately from JSON because
+        // it is not self-describing.
+        use self::bincode::{deserialize, serialize, Infinite};
+        use naive::NaiveDate;
+        use Datelike;
+
+        let week = NaiveDate::from_ymd(2015, 9, 5).iso_week();
+        let encoded = serialize(&week, Infinite).unwrap();
+        let decoded: IsoWeek = deserialize(&encoded).unwrap();
+        assert_eq!(week, decoded);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use naive::{internals, MAX_DATE, MIN_DATE};
@@ -160,4 +284,22 @@ mod tests {
         assert_eq!(maxweek.week0(), 0);
         assert_eq!(format!("{:?}", maxweek), MAX_DATE.format("%G-W%V").to_string());
     }
+
+    #[test]
+    fn test_iso_week_from_str() {
+        use super::IsoWeek;
+
+        for week in &[MIN_DATE.iso_week(), MAX_DATE.iso_week()] {
+            assert_eq!(week.to_string().parse::<IsoWeek>(), Ok(*week));
+        }
+        assert_eq!("-0001-W52".parse::<IsoWeek>().map(|w| (w.year(), w.week())), Ok((-1, 52)));
+        assert_eq!("2015-W53".parse::<IsoWeek>().map(|w| (w.year(), w.week())), Ok((2015, 53)));
+        assert!("2015-W00".parse::<IsoWeek>().is_err());
+        assert!("2015-W54".parse::<IsoWeek>().is_err());
+        assert!("15-W01".parse::<IsoWeek>().is_err());
+        assert!("+-2015-W01".parse::<IsoWeek>().is_err());
+        assert!("2015-W1".parse::<IsoWeek>().is_err());
+        assert!("2015-W01 ".parse::<IsoWeek>().is_err());
+        assert!("".parse::<IsoWeek>().is_err());
+    }
 }
@@ -3,14 +3,12 @@
 
 //! ISO 8601 time without timezone.
 
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use core::borrow::Borrow;
 use core::ops::{Add, AddAssign, Sub, SubAssign};
 use core::{fmt, hash, str};
 use oldtime::Duration as OldDuration;
 
 use div::div_mod_floor;
-#[cfg(any(feature = "alloc", feature = "std", test))]
 use format::DelayedFormat;
 use format::{parse, ParseError, ParseResult, Parsed, StrftimeItems};
 use format::{Fixed, Item, Numeric, Pad};
@@ -742,7 +740,6 @@ impl NaiveTime {
     /// # let t = NaiveTime::from_hms(23, 56, 4);
     /// assert_eq!(format!("{}", t.format_with_items(fmt)), "23:56:04");
     /// ```
-    #[cfg(any(feature = "alloc", feature = "std", test))]
     #[inline]
     pub fn format_with_items<'a, I, B>(&self, items: I) -> DelayedFormat<I>
     where
@@ -786,7 +783,6 @@ impl NaiveTime {
     /// assert_eq!(format!("{}", t.format("%H:%M:%S%.6f")), "23:56:04.012345");
     /// assert_eq!(format!("{}", t.format("%-I:%M %p")), "11:56 PM");
     /// ```
-    #[cfg(any(feature = "alloc", feature = "std", test))]
     #[inline]
     pub fn format<'a>(&self, fmt: &'a str) -> DelayedFormat<StrftimeItems<'a>> {
         self.format_with_items(StrftimeItems::new(fmt))
@@ -1469,10 +1465,13 @@ mod rustc_serialize {
 #[cfg(feature = "serde")]
 mod serde {
     use super::NaiveTime;
+    use compact_serde::compact::Compact;
     use core::fmt;
     use serdelib::{de, ser};
+    use Timelike;
 
-    // TODO not very optimized for space (binary formats would want something better)
+    // binary formats use the string too, so that they can read data written by earlier
+    // versions; `serde::compact` is an opt-in representation which is better for space
     // TODO round-trip for general leap seconds (not just those with second = 60)
 
     impl ser::Serialize for NaiveTime {
@@ -1490,7 +1489,7 @@ mod serde {
         type Value = NaiveTime;
 
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            write!(formatter, "a formatted time string")
+            write!(formatter, "a formatted time string or a tuple of seconds and nanoseconds")
         }
 
         fn visit_str<E>(self, value: &str) -> Result<NaiveTime, E>
@@ -1499,6 +1498,16 @@ mod serde {
         {
             value.parse().map_err(E::custom)
         }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<NaiveTime, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            let secs = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let nanos = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)
+                .ok_or_else(|| de::Error::custom("time out of range"))
+        }
     }
 
     impl<'de> de::Deserialize<'de> for NaiveTime {
@@ -1510,9 +1519,31 @@ mod serde {
         }
     }
 
+    /// A tuple of the seconds from midnight and the nanoseconds (including any leap second).
+    impl Compact for NaiveTime {
+        fn serialize_compact<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            ser::Serialize::serialize(
+                &(self.num_seconds_from_midnight(), self.nanosecond()),
+                serializer,
+            )
+        }
+
+        fn deserialize_compact<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_tuple(2, NaiveTimeVisitor)
+        }
+    }
+
     #[cfg(test)]
     extern crate bincode;
     #[cfg(test)]
+    extern crate bincode1;
+    #[cfg(test)]
     extern crate serde_json;
 
     #[test]
@@ -1536,6 +1567,18 @@ mod serde {
         let decoded: NaiveTime = deserialize(&encoded).unwrap();
         assert_eq!(t, decoded);
     }
+
+    #[test]
+    fn test_serde_bincode_1() {
+        // bincode 1.x is not human-readable, but still uses the string
+        // to read data written by earlier versions
+        use self::bincode1::{deserialize, serialize};
+
+        let t = NaiveTime::from_hms_nano(3, 5, 7, 98765432);
+        let encoded = serialize(&t).unwrap();
+        assert_eq!(encoded, serialize(&"03:05:07.098765432").unwrap());
+        assert_eq!(deserialize::<NaiveTime>(&encoded).unwrap(), t);
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,245 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Configurable week numbering rules.
+
+use core::i32;
+use div::{div_floor, mod_floor};
+use naive::NaiveDate;
+use {Datelike, Weekday};
+
+/// A rule for numbering weeks, given by the first day of the week and the minimal number of days
+/// in the first week of a year or month (as in CLDR and Java's `WeekFields`).
+///
+/// The first week of a year is the first week having at least the minimal number of days
+/// in that year. Days before it belong to the last week of the previous *week-based year*,
+/// and days after the last week of a year may belong to the first week of the next one.
+/// [`IsoWeek`](./struct.IsoWeek.html) uses the rule with weeks starting on Monday
+/// and at least 4 days in the first week.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{NaiveDate, WeekRule, Weekday};
+///
+/// let us = WeekRule::new(Weekday::Sun, 1);
+/// let date = NaiveDate::from_ymd(2021, 12, 27);
+/// assert_eq!(us.week_based_year(date), 2022);
+/// assert_eq!(us.week(date), 1);
+/// assert_eq!(us.from_week_date_opt(2022, 1, Weekday::Mon), Some(date));
+///
+/// let iso = WeekRule::iso();
+/// assert_eq!((iso.week_based_year(date), iso.week(date)), (2021, 52));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WeekRule {
+    first_day: Weekday,
+    min_days: u8,
+}
+
+impl WeekRule {
+    /// Makes a new week rule with weeks starting on `first_day`
+    /// and at least `min_days` days in the first week.
+    ///
+    /// Panics if `min_days` is not between 1 and 7.
+    pub fn new(first_day: Weekday, min_days: u32) -> WeekRule {
+        assert!(1 <= min_days && min_days <= 7, "invalid minimal number of days");
+        WeekRule { first_day: first_day, min_days: min_days as u8 }
+    }
+
+    /// The ISO 8601 rule, with weeks starting on Monday and at least 4 days in the first week.
+    pub fn iso() -> WeekRule {
+        WeekRule::new(Weekday::Mon, 4)
+    }
+
+    /// Returns the first day of the week.
+    pub fn first_day(&self) -> Weekday {
+        self.first_day
+    }
+
+    /// Returns the minimal number of days in the first week.
+    pub fn min_days(&self) -> u32 {
+        u32::from(self.min_days)
+    }
+
+    /// Returns the week-based year of the date, which differs from its calendar year
+    /// for days near the start or end of the year.
+    pub fn week_based_year(&self, date: NaiveDate) -> i32 {
+        let (year, day) = (date.year(), i64::from(date.num_days_from_ce()));
+        if day < self.first_week_start(days_from_ce(year, 1)) {
+            year - 1
+        } else if day >= self.first_week_start(days_from_ce(year, 1) + days_in_year(year)) {
+            year + 1
+        } else {
+            year
+        }
+    }
+
+    /// Returns the week number of the date in its week-based year, from 1 to 53.
+    pub fn week(&self, date: NaiveDate) -> u32 {
+        let start = self.first_week_start(days_from_ce(self.week_based_year(date), 1));
+        ((i64::from(date.num_days_from_ce()) - start) / 7 + 1) as u32
+    }
+
+    /// Returns the week number of the date in its calendar month, from 0 to 6.
+    ///
+    /// Days before the first week of the month (which has at least the minimal number of days
+    /// in the month) are in week 0.
+    pub fn week_of_month(&self, date: NaiveDate) -> u32 {
+        let day = i64::from(date.num_days_from_ce());
+        let start = self.first_week_start(day - i64::from(date.day0()));
+        (div_floor(day - start, 7) + 1) as u32
+    }
+
+    /// Returns the number of weeks in given week-based year, either 52 or 53.
+    pub fn weeks_in_year(&self, year: i32) -> u32 {
+        let first = days_from_ce(year, 1);
+        let start = self.first_week_start(first);
+        let end = self.first_week_start(first + days_in_year(year));
+        ((end - start) / 7) as u32
+    }
+
+    /// Makes a new `NaiveDate` from the week-based year, week number and day of the week.
+    ///
+    /// Panics on an out-of-range date and/or invalid week number.
+    pub fn from_week_date(&self, year: i32, week: u32, weekday: Weekday) -> NaiveDate {
+        self.from_week_date_opt(year, week, weekday).expect("invalid or out-of-range date")
+    }
+
+    /// Makes a new `NaiveDate` from the week-based year, week number and day of the week.
+    ///
+    /// Returns `None` on an out-of-range date and/or invalid week number.
+    pub fn from_week_date_opt(&self, year: i32, week: u32, weekday: Weekday) -> Option<NaiveDate> {
+        if week < 1 || week > self.weeks_in_year(year) {
+            return None;
+        }
+        let start = self.first_week_start(days_from_ce(year, 1));
+        let day = start + (i64::from(week) - 1) * 7 + i64::from(self.days_since_first_day(weekday));
+        if day < i64::from(i32::MIN) || day > i64::from(i32::MAX) {
+            return None;
+        }
+        NaiveDate::from_num_days_from_ce_opt(day as i32)
+    }
+
+    /// Returns the number of days from the first day of the week to given weekday.
+    fn days_since_first_day(&self, weekday: Weekday) -> u32 {
+        (weekday.num_days_from_monday() + 7 - self.first_day.num_days_from_monday()) % 7
+    }
+
+    /// Returns the first day of the first week of a year or month starting on given day
+    /// (counted from January 1, 1 CE as day 1).
+    fn first_week_start(&self, first: i64) -> i64 {
+        // January 1, 1 CE is a Monday
+        let weekday = mod_floor(first - 1, 7) as u32;
+        let offset = (weekday + 7 - self.first_day.num_days_from_monday()) % 7;
+        if 7 - offset >= u32::from(self.min_days) {
+            first - i64::from(offset)
+        } else {
+            first - i64::from(offset) + 7
+        }
+    }
+}
+
+/// Returns the day of the year counted from January 1, 1 CE as day 1,
+/// without the range restriction of `NaiveDate`.
+///
+/// This is computed in `i64`, so that it doesn't overflow for any `i32` year.
+fn days_from_ce(year: i32, ordinal: i64) -> i64 {
+    let y = i64::from(year) - 1;
+    365 * y + div_floor(y, 4) - div_floor(y, 100) + div_floor(y, 400) + ordinal
+}
+
+/// Returns the number of days in given year, without the range restriction of `NaiveDate`.
+fn days_in_year(year: i32) -> i64 {
+    let leap = mod_floor(year, 4) == 0 && (mod_floor(year, 100) != 0 || mod_floor(year, 400) == 0);
+    if leap {
+        366
+    } else {
+        365
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WeekRule;
+    use naive::{NaiveDate, MAX_DATE, MIN_DATE};
+    use {Datelike, Weekday};
+
+    #[test]
+    fn test_week_rule_iso() {
+        let iso = WeekRule::iso();
+        let mut date = NaiveDate::from_ymd(1995, 1, 1);
+        while date < NaiveDate::from_ymd(2030, 1, 1) {
+            let week = date.iso_week();
+            assert_eq!(iso.week_based_year(date), week.year());
+            assert_eq!(iso.week(date), week.week());
+            assert_eq!(iso.from_week_date(week.year(), week.week(), date.weekday()), date);
+            date = date.succ();
+        }
+        for &date in &[MIN_DATE, MAX_DATE] {
+            let week = date.iso_week();
+            assert_eq!((iso.week_based_year(date), iso.week(date)), (week.year(), week.week()));
+        }
+        assert_eq!(iso.weeks_in_year(2020), 53);
+        assert_eq!(iso.weeks_in_year(2021), 52);
+
+        // years far out of range don't overflow
+        let us = WeekRule::new(Weekday::Sun, 1);
+        for &year in &[i32::MIN, MIN_DATE.year() - 2, MAX_DATE.year() + 2, i32::MAX] {
+            assert_eq!(us.from_week_date_opt(year, 1, Weekday::Mon), None);
+            assert!(us.weeks_in_year(year) == 52 || us.weeks_in_year(year) == 53);
+        }
+    }
+
+    #[test]
+    fn test_week_rule() {
+        let ymd = |y, m, d| NaiveDate::from_ymd(y, m, d);
+
+        // weeks starting on Sunday, where the week with January 1 is the first week
+        let us = WeekRule::new(Weekday::Sun, 1);
+        assert_eq!(us.week_based_year(ymd(2022, 1, 1)), 2022);
+        assert_eq!(us.week(ymd(2022, 1, 1)), 1);
+        assert_eq!(us.week(ymd(2022, 1, 2)), 2);
+        assert_eq!(us.week_based_year(ymd(2021, 12, 26)), 2022);
+        assert_eq!(us.week(ymd(2021, 12, 25)), 52);
+        assert_eq!(us.weeks_in_year(2021), 52);
+        assert_eq!(us.from_week_date_opt(2022, 1, Weekday::Sun), Some(ymd(2021, 12, 26)));
+        assert_eq!(us.from_week_date_opt(2021, 53, Weekday::Sun), None);
+        assert_eq!(us.from_week_date_opt(2021, 0, Weekday::Sun), None);
+
+        // weeks starting on Saturday
+        let middle_east = WeekRule::new(Weekday::Sat, 1);
+        assert_eq!(middle_east.week(ymd(2022, 1, 1)), 1);
+        assert_eq!(middle_east.week(ymd(2022, 1, 7)), 1);
+        assert_eq!(middle_east.week(ymd(2022, 1, 8)), 2);
+        assert_eq!(middle_east.week_based_year(ymd(2021, 12, 31)), 2021);
+
+        // week of month, with the first week of June 2021 (starting on Tuesday) having 5 days
+        let iso = WeekRule::iso();
+        assert_eq!(iso.week_of_month(ymd(2021, 6, 1)), 1);
+        assert_eq!(iso.week_of_month(ymd(2021, 6, 7)), 2);
+        assert_eq!(iso.week_of_month(ymd(2021, 6, 30)), 5);
+        let full = WeekRule::new(Weekday::Mon, 7);
+        assert_eq!(full.week_of_month(ymd(2021, 6, 1)), 0);
+        assert_eq!(full.week_of_month(ymd(2021, 6, 7)), 1);
+        assert_eq!(us.week_of_month(ymd(2021, 5, 1)), 1);
+        assert_eq!(us.week_of_month(ymd(2021, 5, 2)), 2);
+        assert_eq!(us.week_of_month(ymd(2021, 5, 31)), 6);
+
+        // every date round-trips for every rule
+        let mut first_day = Weekday::Mon;
+        for _ in 0..7 {
+            first_day = first_day.succ();
+            for min_days in 1..8 {
+                let rule = WeekRule::new(first_day, min_days);
+                let mut date = ymd(2019, 12, 1);
+                while date < ymd(2022, 2, 1) {
+                    let (year, week) = (rule.week_based_year(date), rule.week(date));
+                    assert!(week >= 1 && week <= rule.weeks_in_year(year));
+                    assert_eq!(rule.from_week_date(year, week, date.weekday()), date);
+                    date = date.succ();
+                }
+            }
+        }
+    }
+}
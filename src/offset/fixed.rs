@@ -3,12 +3,13 @@
 
 //! The time zone which has a fixed offset from UTC.
 
-use core::fmt;
 use core::ops::{Add, Sub};
+use core::{fmt, str};
 use oldtime::Duration as OldDuration;
 
 use super::{LocalResult, Offset, TimeZone};
 use div::div_mod_floor;
+use format::{ParseError, ParseResult, INVALID, OUT_OF_RANGE};
 use naive::{NaiveDate, NaiveDateTime, NaiveTime};
 use DateTime;
 use Timelike;
@@ -146,6 +147,136 @@ impl fmt::Display for FixedOffset {
     }
 }
 
+/// Parsing the `Display` form of a `FixedOffset`, i.e. `+HH:MM` or `+HH:MM:SS`.
+///
+/// # Example
+///
+/// ```
+/// use chrono::FixedOffset;
+///
+/// assert_eq!("+09:00".parse::<FixedOffset>(), Ok(FixedOffset::east(9 * 3600)));
+/// assert_eq!("-03:30".parse::<FixedOffset>(), Ok(FixedOffset::west(3 * 3600 + 30 * 60)));
+/// assert_eq!("+23:59:59".parse::<FixedOffset>(), Ok(FixedOffset::east(86399)));
+/// assert!("+24:00".parse::<FixedOffset>().is_err());
+/// assert!("09:00".parse::<FixedOffset>().is_err());
+/// ```
+impl str::FromStr for FixedOffset {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> ParseResult<FixedOffset> {
+        fn two_digits(s: &[u8], max: i32) -> ParseResult<i32> {
+            match *s {
+                [a @ b'0'..=b'9', b @ b'0'..=b'9'] => {
+                    let value = i32::from(a - b'0') * 10 + i32::from(b - b'0');
+                    if value > max {
+                        Err(OUT_OF_RANGE)
+                    } else {
+                        Ok(value)
+                    }
+                }
+                _ => Err(INVALID),
+            }
+        }
+
+        let bytes = s.as_bytes();
+        let sign = match bytes.first() {
+            Some(&b'+') => 1,
+            Some(&b'-') => -1,
+            _ => return Err(INVALID),
+        };
+        let secs = match bytes.len() {
+            6 if bytes[3] == b':' => {
+                two_digits(&bytes[1..3], 23)? * 3600 + two_digits(&bytes[4..6], 59)? * 60
+            }
+            9 if bytes[3] == b':' && bytes[6] == b':' => {
+                two_digits(&bytes[1..3], 23)? * 3600
+                    + two_digits(&bytes[4..6], 59)? * 60
+                    + two_digits(&bytes[7..9], 59)?
+            }
+            _ => return Err(INVALID),
+        };
+        FixedOffset::east_opt(sign * secs).ok_or(OUT_OF_RANGE)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::FixedOffset;
+    use core::fmt;
+    use serdelib::{de, ser};
+
+    /// Serialize into the `Display` form, e.g. `+09:00`
+    impl ser::Serialize for FixedOffset {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            serializer.collect_str(self)
+        }
+    }
+
+    struct FixedOffsetVisitor;
+
+    impl<'de> de::Visitor<'de> for FixedOffsetVisitor {
+        type Value = FixedOffset;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "an offset from UTC like `+09:00`")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<FixedOffset, E>
+        where
+            E: de::Error,
+        {
+            value.parse().map_err(E::custom)
+        }
+    }
+
+    impl<'de> de::Deserialize<'de> for FixedOffset {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_str(FixedOffsetVisitor)
+        }
+    }
+
+    #[cfg(test)]
+    extern crate bincode;
+    #[cfg(test)]
+    extern crate serde_json;
+
+    #[test]
+    fn test_serde_serialize() {
+        use self::serde_json::to_string;
+        assert_eq!(to_string(&FixedOffset::east(3600)).ok(), Some(r#""+01:00""#.into()));
+        assert_eq!(to_string(&FixedOffset::west(86399)).ok(), Some(r#""-23:59:59""#.into()));
+        assert_eq!(to_string(&FixedOffset::east(0)).ok(), Some(r#""+00:00""#.into()));
+    }
+
+    #[test]
+    fn test_serde_deserialize() {
+        use self::serde_json::from_str;
+        assert_eq!(from_str::<FixedOffset>(r#""+01:00""#).ok(), Some(FixedOffset::east(3600)));
+        assert_eq!(from_str::<FixedOffset>(r#""-23:59:59""#).ok(), Some(FixedOffset::west(86399)));
+        assert!(from_str::<FixedOffset>(r#""+0100""#).is_err());
+        assert!(from_str::<FixedOffset>(r#""Z""#).is_err());
+        assert!(from_str::<FixedOffset>("3600").is_err());
+    }
+
+    #[test]
+    fn test_serde_bincode() {
+        // Bincode is relevant to test separately from JSON because
+        // it is not self-describing.
+        use self::bincode::{deserialize, serialize, Infinite};
+
+        let offset = FixedOffset::west(5 * 3600);
+        let encoded = serialize(&offset, Infinite).unwrap();
+        let decoded: FixedOffset = deserialize(&encoded).unwrap();
+        assert_eq!(offset, decoded);
+    }
+}
+
 // addition or subtraction of FixedOffset to/from Timelike values is the same as
 // adding or subtracting the offset's local_minus_utc value
 // but keep keeps the leap second information.
@@ -193,6 +193,63 @@ impl TimeZone for Local {
     }
 }
 
+#[cfg(feature = "serde")]
+mod serde {
+    use super::Local;
+    use core::fmt;
+    use serdelib::{de, ser};
+
+    /// Serialize into the `Debug` form, `Local`
+    impl ser::Serialize for Local {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            serializer.serialize_str("Local")
+        }
+    }
+
+    struct LocalVisitor;
+
+    impl<'de> de::Visitor<'de> for LocalVisitor {
+        type Value = Local;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "`Local`")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Local, E>
+        where
+            E: de::Error,
+        {
+            match value {
+                "Local" => Ok(Local),
+                _ => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+            }
+        }
+    }
+
+    impl<'de> de::Deserialize<'de> for Local {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_str(LocalVisitor)
+        }
+    }
+
+    #[cfg(test)]
+    extern crate serde_json;
+
+    #[test]
+    fn test_serde() {
+        use self::serde_json::{from_str, to_string};
+        assert_eq!(to_string(&Local).ok(), Some(r#""Local""#.into()));
+        assert!(from_str::<Local>(r#""Local""#).is_ok());
+        assert!(from_str::<Local>(r#""UTC""#).is_err());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Local;
@@ -467,6 +467,9 @@ mod fixed;
 mod local;
 mod utc;
 
+#[cfg(test)]
+pub(crate) mod test_zone;
+
 pub use self::fixed::FixedOffset;
 #[cfg(feature = "clock")]
 pub use self::local::Local;
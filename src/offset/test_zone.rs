@@ -0,0 +1,72 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! A time zone with daylight saving time, only used for testing.
+
+use super::{FixedOffset, LocalResult, TimeZone};
+use naive::{NaiveDate, NaiveDateTime};
+use {Datelike, Weekday};
+
+/// The time zone of the United States Eastern Time under the rules in effect since 2007.
+///
+/// Daylight saving time starts at 02:00 local time on the second Sunday of March
+/// (so 02:00 to 03:00 is skipped) and ends at 02:00 local time on the first Sunday of November
+/// (so 01:00 to 02:00 is repeated).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Eastern;
+
+impl Eastern {
+    pub(crate) fn est() -> FixedOffset {
+        FixedOffset::west(5 * 3600)
+    }
+
+    pub(crate) fn edt() -> FixedOffset {
+        FixedOffset::west(4 * 3600)
+    }
+
+    /// Returns the local date and time when the daylight saving time starts and ends.
+    fn transitions(year: i32) -> (NaiveDateTime, NaiveDateTime) {
+        let start = NaiveDate::from_weekday_of_month(year, 3, Weekday::Sun, 2).and_hms(2, 0, 0);
+        let end = NaiveDate::from_weekday_of_month(year, 11, Weekday::Sun, 1).and_hms(2, 0, 0);
+        (start, end)
+    }
+}
+
+impl TimeZone for Eastern {
+    type Offset = FixedOffset;
+
+    fn from_offset(_offset: &FixedOffset) -> Eastern {
+        Eastern
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+        self.offset_from_local_datetime(&local.and_hms(12, 0, 0))
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        let (start, end) = Eastern::transitions(local.year());
+        let hour = ::oldtime::Duration::hours(1);
+        if *local < start || *local >= end {
+            LocalResult::Single(Eastern::est())
+        } else if *local < start + hour {
+            LocalResult::None
+        } else if *local < end - hour {
+            LocalResult::Single(Eastern::edt())
+        } else {
+            LocalResult::Ambiguous(Eastern::edt(), Eastern::est())
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+        self.offset_from_utc_datetime(&utc.and_hms(12, 0, 0))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        let (start, end) = Eastern::transitions(utc.year());
+        if *utc >= start - Eastern::est() && *utc < end - Eastern::edt() {
+            Eastern::edt()
+        } else {
+            Eastern::est()
+        }
+    }
+}
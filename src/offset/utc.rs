@@ -98,3 +98,63 @@ impl fmt::Display for Utc {
         write!(f, "UTC")
     }
 }
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::Utc;
+    use core::fmt;
+    use serdelib::{de, ser};
+
+    /// Serialize into the `Display` form, `UTC`
+    impl ser::Serialize for Utc {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            serializer.collect_str(self)
+        }
+    }
+
+    struct UtcVisitor;
+
+    impl<'de> de::Visitor<'de> for UtcVisitor {
+        type Value = Utc;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "`UTC` or `Z`")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Utc, E>
+        where
+            E: de::Error,
+        {
+            match value {
+                "UTC" | "Z" => Ok(Utc),
+                _ => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+            }
+        }
+    }
+
+    /// Deserialize from the `Display` form `UTC`, or the `Debug` form `Z`
+    impl<'de> de::Deserialize<'de> for Utc {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_str(UtcVisitor)
+        }
+    }
+
+    #[cfg(test)]
+    extern crate serde_json;
+
+    #[test]
+    fn test_serde() {
+        use self::serde_json::{from_str, to_string};
+        assert_eq!(to_string(&Utc).ok(), Some(r#""UTC""#.into()));
+        assert_eq!(from_str::<Utc>(r#""UTC""#).ok(), Some(Utc));
+        assert_eq!(from_str::<Utc>(r#""Z""#).ok(), Some(Utc));
+        assert!(from_str::<Utc>(r#""+00:00""#).is_err());
+        assert!(from_str::<Utc>("null").is_err());
+    }
+}
@@ -10,12 +10,15 @@
 
 //! Temporal quantification
 
-use core::ops::{Add, Div, Mul, Neg, Sub};
+use core::iter::Sum;
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
 use core::time::Duration as StdDuration;
 use core::{fmt, i64};
 #[cfg(any(feature = "std", test))]
 use std::error::Error;
 
+use round::Rounding;
+
 /// The number of nanoseconds in a microsecond.
 const NANOS_PER_MICRO: i32 = 1000;
 /// The number of nanoseconds in a millisecond.
@@ -206,6 +209,48 @@ impl Duration {
         secs_part.checked_add(nanos_part as i64)
     }
 
+    /// Returns the fractional part of the duration in whole milliseconds,
+    /// with the same sign as the duration (i.e. between -999 and 999).
+    #[inline]
+    pub fn subsec_millis(&self) -> i32 {
+        self.nanos_mod_sec() / NANOS_PER_MILLI
+    }
+
+    /// Returns the fractional part of the duration in whole microseconds,
+    /// with the same sign as the duration (i.e. between -999,999 and 999,999).
+    #[inline]
+    pub fn subsec_micros(&self) -> i32 {
+        self.nanos_mod_sec() / NANOS_PER_MICRO
+    }
+
+    /// Returns the fractional part of the duration in nanoseconds,
+    /// with the same sign as the duration (i.e. between -999,999,999 and 999,999,999).
+    #[inline]
+    pub fn subsec_nanos(&self) -> i32 {
+        self.nanos_mod_sec()
+    }
+
+    /// Returns the total number of nanoseconds in the duration, which never overflows `i128`.
+    #[inline]
+    pub fn as_nanos(&self) -> i128 {
+        i128::from(self.secs) * i128::from(NANOS_PER_SEC) + i128::from(self.nanos)
+    }
+
+    /// Makes a new `Duration` with given number of nanoseconds,
+    /// or returns `None` when it is out of bounds.
+    pub fn from_nanos(nanos: i128) -> Option<Duration> {
+        if nanos < MIN.as_nanos() || nanos > MAX.as_nanos() {
+            return None;
+        }
+        let mut secs = nanos / i128::from(NANOS_PER_SEC);
+        let mut nanos = nanos % i128::from(NANOS_PER_SEC);
+        if nanos < 0 {
+            secs -= 1;
+            nanos += i128::from(NANOS_PER_SEC);
+        }
+        Some(Duration { secs: secs as i64, nanos: nanos as i32 })
+    }
+
     /// Add two durations, returning `None` if overflow occurred.
     pub fn checked_add(&self, rhs: &Duration) -> Option<Duration> {
         let mut secs = try_opt!(self.secs.checked_add(rhs.secs));
@@ -242,6 +287,47 @@ impl Duration {
         }
     }
 
+    /// Multiply a duration by a scalar, returning `None` if overflow occurred.
+    pub fn checked_mul(&self, rhs: i64) -> Option<Duration> {
+        Duration::from_nanos(try_opt!(self.as_nanos().checked_mul(i128::from(rhs))))
+    }
+
+    /// Divide a duration by a scalar, truncating towards zero,
+    /// returning `None` if `rhs` is zero or overflow occurred.
+    pub fn checked_div(&self, rhs: i64) -> Option<Duration> {
+        Duration::from_nanos(try_opt!(self.as_nanos().checked_div(i128::from(rhs))))
+    }
+
+    /// Add two durations, saturating at the bounds instead of overflowing.
+    pub fn saturating_add(&self, rhs: &Duration) -> Duration {
+        Duration::saturate(self.as_nanos() + rhs.as_nanos())
+    }
+
+    /// Subtract two durations, saturating at the bounds instead of overflowing.
+    pub fn saturating_sub(&self, rhs: &Duration) -> Duration {
+        Duration::saturate(self.as_nanos() - rhs.as_nanos())
+    }
+
+    /// Multiply a duration by a scalar, saturating at the bounds instead of overflowing.
+    pub fn saturating_mul(&self, rhs: i64) -> Duration {
+        match self.as_nanos().checked_mul(i128::from(rhs)) {
+            Some(nanos) => Duration::saturate(nanos),
+            None if (self.secs < 0) == (rhs < 0) => MAX,
+            None => MIN,
+        }
+    }
+
+    /// Makes a `Duration` from nanoseconds, clamped to the bounds.
+    fn saturate(nanos: i128) -> Duration {
+        if nanos < MIN.as_nanos() {
+            MIN
+        } else if nanos > MAX.as_nanos() {
+            MAX
+        } else {
+            Duration::from_nanos(nanos).unwrap()
+        }
+    }
+
     /// Returns the duration as an absolute (non-negative) value.
     #[inline]
     pub fn abs(&self) -> Duration {
@@ -272,6 +358,41 @@ impl Duration {
         self.secs == 0 && self.nanos == 0
     }
 
+    /// Returns the number of seconds in the duration as `f64`.
+    #[inline]
+    pub fn as_secs_f64(&self) -> f64 {
+        self.secs as f64 + self.nanos as f64 / NANOS_PER_SEC as f64
+    }
+
+    /// Makes a new `Duration` from a number of seconds given as `f64`, rounded to the nearest
+    /// nanosecond. Returns `None` if the number is not finite or out of bounds.
+    #[inline]
+    pub fn from_secs_f64(secs: f64) -> Option<Duration> {
+        Duration::from_secs_f64_rounded(secs, Rounding::Nearest)
+    }
+
+    /// Makes a new `Duration` from a number of seconds given as `f64`, rounded to a nanosecond
+    /// in the given way. Returns `None` if the number is not finite or out of bounds.
+    pub fn from_secs_f64_rounded(secs: f64, rounding: Rounding) -> Option<Duration> {
+        if !secs.is_finite() || secs <= MIN.secs as f64 - 1.0 || secs >= MAX.secs as f64 + 1.0 {
+            return None;
+        }
+        // both the whole seconds and the fraction are exact
+        let whole = secs as i64;
+        let frac = (secs - whole as f64) * NANOS_PER_SEC as f64;
+        let mut nanos = frac as i64;
+        let rem = frac - nanos as f64;
+        let away = match rounding {
+            Rounding::TowardZero => false,
+            Rounding::Nearest => rem >= 0.5 || rem <= -0.5,
+            Rounding::AwayFromZero => rem != 0.0,
+        };
+        if away {
+            nanos += if frac < 0.0 { -1 } else { 1 };
+        }
+        Duration::from_nanos(i128::from(whole) * i128::from(NANOS_PER_SEC) + i128::from(nanos))
+    }
+
     /// Creates a `time::Duration` object from `std::time::Duration`
     ///
     /// This function errors when original duration is larger than the maximum
@@ -373,6 +494,55 @@ impl Div<i32> for Duration {
     }
 }
 
+impl Mul<i64> for Duration {
+    type Output = Duration;
+
+    fn mul(self, rhs: i64) -> Duration {
+        self.checked_mul(rhs).expect("`Duration * i64` overflowed")
+    }
+}
+
+impl Div<i64> for Duration {
+    type Output = Duration;
+
+    /// Divides the duration, truncating towards zero.
+    /// Panics when `rhs` is zero or the result is out of bounds.
+    fn div(self, rhs: i64) -> Duration {
+        self.checked_div(rhs).expect("`Duration / i64` divided by zero or overflowed")
+    }
+}
+
+impl Div for Duration {
+    type Output = f64;
+
+    /// Returns the ratio of two durations.
+    fn div(self, rhs: Duration) -> f64 {
+        self.as_nanos() as f64 / rhs.as_nanos() as f64
+    }
+}
+
+impl Rem for Duration {
+    type Output = Duration;
+
+    /// Returns the remainder of dividing by another duration, with the same sign as `self`.
+    /// Panics when `rhs` is zero.
+    fn rem(self, rhs: Duration) -> Duration {
+        Duration::from_nanos(self.as_nanos() % rhs.as_nanos()).unwrap()
+    }
+}
+
+impl Sum for Duration {
+    fn sum<I: Iterator<Item = Duration>>(iter: I) -> Duration {
+        iter.fold(Duration::zero(), |acc, d| acc + d)
+    }
+}
+
+impl<'a> Sum<&'a Duration> for Duration {
+    fn sum<I: Iterator<Item = &'a Duration>>(iter: I) -> Duration {
+        iter.fold(Duration::zero(), |acc, d| acc + *d)
+    }
+}
+
 impl fmt::Display for Duration {
     /// Format a duration using the [ISO 8601] format
     ///
@@ -457,9 +627,132 @@ fn div_rem_64(this: i64, other: i64) -> (i64, i64) {
     (this / other, this % other)
 }
 
+#[cfg(feature = "serde")]
+mod serde {
+    use super::{Duration, MAX, MIN, NANOS_PER_SEC};
+    use core::fmt;
+    use format::duration::parse_iso8601;
+    use serdelib::{de, ser};
+
+    /// Serialize into an ISO 8601 string like `P1DT2.345S` for human-readable formats,
+    /// and into a tuple of seconds and non-negative nanoseconds for binary formats.
+    impl ser::Serialize for Duration {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            if serializer.is_human_readable() {
+                serializer.collect_str(self)
+            } else {
+                (self.secs, self.nanos).serialize(serializer)
+            }
+        }
+    }
+
+    struct DurationVisitor;
+
+    impl<'de> de::Visitor<'de> for DurationVisitor {
+        type Value = Duration;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "an ISO 8601 duration or a tuple of seconds and nanoseconds")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Duration, E>
+        where
+            E: de::Error,
+        {
+            parse_iso8601(value).map_err(E::custom)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Duration, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            let secs: i64 =
+                seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let nanos: i32 =
+                seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            let d = Duration { secs: secs, nanos: nanos };
+            if nanos < 0 || nanos >= NANOS_PER_SEC || d < MIN || d > MAX {
+                return Err(de::Error::custom("duration out of range"));
+            }
+            Ok(d)
+        }
+    }
+
+    /// Deserialize from an ISO 8601 string for human-readable formats,
+    /// and from a tuple of seconds and nanoseconds for binary formats.
+    impl<'de> de::Deserialize<'de> for Duration {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(DurationVisitor)
+            } else {
+                deserializer.deserialize_tuple(2, DurationVisitor)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    extern crate bincode;
+    #[cfg(test)]
+    extern crate serde_cbor;
+    #[cfg(test)]
+    extern crate serde_json;
+
+    #[test]
+    fn test_serde_json() {
+        let d = Duration::days(1) + Duration::milliseconds(2345);
+        assert_eq!(self::serde_json::to_string(&d).unwrap(), r#""P1DT2.345S""#);
+        assert_eq!(self::serde_json::to_string(&-d).unwrap(), r#""-P1DT2.345S""#);
+        assert_eq!(self::serde_json::from_str::<Duration>(r#""P1DT2.345S""#).unwrap(), d);
+        assert_eq!(
+            self::serde_json::from_str::<Duration>(r#""-PT1H30M""#).unwrap(),
+            Duration::minutes(-90)
+        );
+        for &d in &[Duration::zero(), Duration::nanoseconds(-1), MAX, MIN] {
+            let json = self::serde_json::to_string(&d).unwrap();
+            assert_eq!(self::serde_json::from_str::<Duration>(&json).unwrap(), d);
+        }
+        assert!(self::serde_json::from_str::<Duration>(r#""P1Y""#).is_err());
+        assert!(self::serde_json::from_str::<Duration>("[1, 0]").is_err());
+    }
+
+    #[test]
+    fn test_serde_bincode() {
+        use self::bincode::{deserialize, serialize, Infinite};
+
+        for &d in &[Duration::zero(), Duration::milliseconds(-1500), MAX, MIN] {
+            let encoded = serialize(&d, Infinite).unwrap();
+            assert_eq!(deserialize::<Duration>(&encoded).unwrap(), d);
+        }
+    }
+
+    #[test]
+    fn test_serde_compact() {
+        // CBOR is not human-readable, so this uses the tuple
+        let d = Duration::milliseconds(-1500);
+        let encoded = self::serde_cbor::to_vec(&d).unwrap();
+        assert_eq!(encoded, self::serde_cbor::to_vec(&(-2i64, 500_000_000i32)).unwrap());
+        assert_eq!(self::serde_cbor::from_slice::<Duration>(&encoded).unwrap(), d);
+        for &d in &[Duration::zero(), MAX, MIN] {
+            let encoded = self::serde_cbor::to_vec(&d).unwrap();
+            assert_eq!(self::serde_cbor::from_slice::<Duration>(&encoded).unwrap(), d);
+        }
+        let invalid = self::serde_cbor::to_vec(&(0i64, 1_000_000_000i32)).unwrap();
+        assert!(self::serde_cbor::from_slice::<Duration>(&invalid).is_err());
+        let invalid = self::serde_cbor::to_vec(&(i64::MAX, 0i32)).unwrap();
+        assert!(self::serde_cbor::from_slice::<Duration>(&invalid).is_err());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Duration, OutOfRangeError, MAX, MIN};
+    use round::Rounding;
     use std::time::Duration as StdDuration;
     use std::{i32, i64};
 
@@ -630,6 +923,120 @@ mod tests {
         assert_eq!(Duration::seconds(-4) / -3, Duration::nanoseconds(1_333_333_333));
     }
 
+    #[test]
+    fn test_duration_mul_div_i64() {
+        assert_eq!(Duration::seconds(3) * 4i64, Duration::seconds(12));
+        assert_eq!(
+            Duration::milliseconds(-1500) * 3_000_000_000i64,
+            Duration::seconds(-4_500_000_000)
+        );
+        assert_eq!(Duration::seconds(12) / 5i64, Duration::milliseconds(2400));
+        assert_eq!(Duration::nanoseconds(-7) / 2i64, Duration::nanoseconds(-3));
+        assert_eq!(MAX / 1_000_000i64, Duration::nanoseconds(i64::MAX));
+
+        assert_eq!(MAX.checked_mul(1), Some(MAX));
+        assert_eq!(MAX.checked_mul(2), None);
+        assert_eq!(MIN.checked_mul(-1), None);
+        assert_eq!(Duration::seconds(1).checked_mul(i64::MIN), None);
+        assert_eq!(
+            Duration::nanoseconds(1).checked_mul(i64::MIN),
+            Some(Duration::nanoseconds(i64::MIN))
+        );
+        assert_eq!(Duration::seconds(1).checked_div(0), None);
+        assert_eq!(Duration::seconds(1).checked_div(-4), Some(Duration::milliseconds(-250)));
+        assert_eq!(MIN.checked_div(-1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duration_div_by_zero() {
+        let _ = Duration::seconds(1) / 0i64;
+    }
+
+    #[test]
+    fn test_duration_ratio_rem_sum() {
+        assert_eq!(Duration::minutes(90) / Duration::hours(1), 1.5);
+        assert_eq!(Duration::seconds(-1) / Duration::milliseconds(250), -4.0);
+        assert_eq!(Duration::seconds(10) % Duration::seconds(3), Duration::seconds(1));
+        assert_eq!(Duration::seconds(-10) % Duration::seconds(3), Duration::seconds(-1));
+        assert_eq!(
+            Duration::milliseconds(2500) % Duration::seconds(-1),
+            Duration::milliseconds(500)
+        );
+        assert_eq!(MIN % Duration::seconds(1), Duration::milliseconds(-808));
+
+        let durations = [Duration::seconds(1), Duration::milliseconds(-250), Duration::days(1)];
+        let expected = Duration::days(1) + Duration::milliseconds(750);
+        assert_eq!(durations.iter().sum::<Duration>(), expected);
+        assert_eq!(durations.iter().cloned().sum::<Duration>(), expected);
+        assert_eq!([].iter().sum::<Duration>(), Duration::zero());
+    }
+
+    #[test]
+    fn test_duration_saturating_ops() {
+        let one = Duration::nanoseconds(1);
+        assert_eq!(MAX.saturating_add(&one), MAX);
+        assert_eq!(MAX.saturating_add(&-one), MAX - one);
+        assert_eq!(MIN.saturating_sub(&one), MIN);
+        assert_eq!(MIN.saturating_sub(&MIN), Duration::zero());
+        assert_eq!(MAX.saturating_mul(i64::MAX), MAX);
+        assert_eq!(MAX.saturating_mul(i64::MIN), MIN);
+        assert_eq!(Duration::seconds(2).saturating_mul(-3), Duration::seconds(-6));
+    }
+
+    #[test]
+    fn test_duration_nanos_and_subsec() {
+        assert_eq!(Duration::nanoseconds(-1).as_nanos(), -1);
+        assert_eq!(MAX.as_nanos(), i128::from(i64::MAX) * 1_000_000);
+        assert_eq!(MIN.as_nanos(), i128::from(i64::MIN) * 1_000_000);
+        assert_eq!(Duration::from_nanos(MAX.as_nanos()), Some(MAX));
+        assert_eq!(Duration::from_nanos(MIN.as_nanos()), Some(MIN));
+        assert_eq!(Duration::from_nanos(MAX.as_nanos() + 1), None);
+        assert_eq!(Duration::from_nanos(MIN.as_nanos() - 1), None);
+        assert_eq!(Duration::from_nanos(-1_500_000_000), Some(Duration::milliseconds(-1500)));
+
+        let d = Duration::nanoseconds(-1_234_567_891);
+        assert_eq!((d.num_seconds(), d.subsec_millis()), (-1, -234));
+        assert_eq!(d.subsec_micros(), -234_567);
+        assert_eq!(d.subsec_nanos(), -234_567_891);
+        assert_eq!((-d).subsec_nanos(), 234_567_891);
+        assert_eq!(Duration::seconds(-3).subsec_nanos(), 0);
+    }
+
+    #[test]
+    fn test_duration_secs_f64() {
+        assert_eq!(Duration::milliseconds(-1500).as_secs_f64(), -1.5);
+        assert_eq!(Duration::nanoseconds(1).as_secs_f64(), 1e-9);
+        assert_eq!(Duration::from_secs_f64(-1.5), Some(Duration::milliseconds(-1500)));
+        assert_eq!(Duration::from_secs_f64(0.1), Some(Duration::milliseconds(100)));
+        assert_eq!(Duration::from_secs_f64(1e-10), Some(Duration::zero()));
+        assert_eq!(Duration::from_secs_f64(6e-10), Some(Duration::nanoseconds(1)));
+        assert_eq!(Duration::from_secs_f64(-6e-10), Some(Duration::nanoseconds(-1)));
+        assert_eq!(Duration::from_secs_f64(1e15), Some(Duration::seconds(1_000_000_000_000_000)));
+        assert_eq!(Duration::from_secs_f64(1e16), None);
+        assert_eq!(Duration::from_secs_f64(-1e16), None);
+        assert_eq!(Duration::from_secs_f64(f64::NAN), None);
+        assert_eq!(Duration::from_secs_f64(f64::INFINITY), None);
+
+        let secs = 2.0000000014;
+        assert_eq!(
+            Duration::from_secs_f64_rounded(secs, Rounding::TowardZero),
+            Some(Duration::nanoseconds(2_000_000_001))
+        );
+        assert_eq!(
+            Duration::from_secs_f64_rounded(secs, Rounding::AwayFromZero),
+            Some(Duration::nanoseconds(2_000_000_002))
+        );
+        assert_eq!(
+            Duration::from_secs_f64_rounded(-secs, Rounding::AwayFromZero),
+            Some(Duration::nanoseconds(-2_000_000_002))
+        );
+        assert_eq!(
+            Duration::from_secs_f64_rounded(-secs, Rounding::TowardZero),
+            Some(Duration::nanoseconds(-2_000_000_001))
+        );
+    }
+
     #[test]
     fn test_duration_fmt() {
         assert_eq!(Duration::zero().to_string(), "PT0S");
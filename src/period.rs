@@ -0,0 +1,240 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Calendar periods expressed in years, months and days.
+
+use core::fmt;
+use core::ops::Neg;
+use oldtime::Duration as OldDuration;
+
+/// A calendar period, i.e. a difference between two dates
+/// expressed as a number of years, months and days
+/// (with an optional sub-day remainder for date and time values).
+///
+/// Unlike [`Duration`](./struct.Duration.html), which measures an exact amount of elapsed time,
+/// the length of a `Period` depends on the date it is applied to:
+/// one month from January 31 is shorter than one month from March 31.
+///
+/// `Period` values are normally obtained from
+/// [`NaiveDate::period_since`](./naive/struct.NaiveDate.html#method.period_since),
+/// [`NaiveDateTime::period_since`](./naive/struct.NaiveDateTime.html#method.period_since) or
+/// [`DateTime::period_since`](./struct.DateTime.html#method.period_since).
+/// All components of a period computed this way have the same sign,
+/// `months` ranges from -11 to 11 and the time remainder is always shorter than a day.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{NaiveDate, Period};
+///
+/// let birth = NaiveDate::from_ymd(1988, 7, 14);
+/// let today = NaiveDate::from_ymd(2021, 3, 2);
+/// let age = today.period_since(birth);
+/// assert_eq!((age.years(), age.months(), age.days()), (32, 7, 16));
+/// assert_eq!(age, Period::new(32, 7, 16));
+/// assert_eq!(age.to_string(), "P32Y7M16D");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Period {
+    years: i32,
+    months: i32,
+    days: i32,
+    time: OldDuration,
+}
+
+impl Period {
+    /// Makes a new `Period` from the number of years, months and days.
+    ///
+    /// The components are kept as given; they are not normalized in any way.
+    #[inline]
+    pub fn new(years: i32, months: i32, days: i32) -> Period {
+        Period { years: years, months: months, days: days, time: OldDuration::zero() }
+    }
+
+    /// Makes a new `Period` from the number of years, months and days
+    /// and a time remainder.
+    #[inline]
+    pub fn with_time(years: i32, months: i32, days: i32, time: OldDuration) -> Period {
+        Period { years: years, months: months, days: days, time: time }
+    }
+
+    /// Returns a zero-length `Period`.
+    #[inline]
+    pub fn zero() -> Period {
+        Period::new(0, 0, 0)
+    }
+
+    /// Returns the number of years.
+    #[inline]
+    pub fn years(&self) -> i32 {
+        self.years
+    }
+
+    /// Returns the number of months, not counting those included in [`years`](#method.years).
+    #[inline]
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    /// Returns the number of days, not counting those included in months and years.
+    #[inline]
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    /// Returns the remaining time not counted in whole days.
+    ///
+    /// This is always zero for periods between two `NaiveDate`s.
+    #[inline]
+    pub fn time(&self) -> OldDuration {
+        self.time
+    }
+
+    /// Returns the total number of months, i.e. `years * 12 + months`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Period;
+    ///
+    /// assert_eq!(Period::new(2, 3, 20).total_months(), 27);
+    /// assert_eq!(Period::new(-1, -11, 0).total_months(), -23);
+    /// ```
+    #[inline]
+    pub fn total_months(&self) -> i64 {
+        i64::from(self.years) * 12 + i64::from(self.months)
+    }
+
+    /// Returns `true` if every component of the period is zero.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.years == 0 && self.months == 0 && self.days == 0 && self.time.is_zero()
+    }
+
+    /// Returns `true` if any component of the period is negative.
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.years < 0 || self.months < 0 || self.days < 0 || self.time < OldDuration::zero()
+    }
+}
+
+impl Neg for Period {
+    type Output = Period;
+
+    #[inline]
+    fn neg(self) -> Period {
+        Period { years: -self.years, months: -self.months, days: -self.days, time: -self.time }
+    }
+}
+
+impl Default for Period {
+    fn default() -> Self {
+        Period::zero()
+    }
+}
+
+/// The `Display` output of a period is its ISO 8601 duration representation,
+/// e.g. `P1Y2M3DT4H5M6.5S`.
+///
+/// ISO 8601 does not define negative durations;
+/// a period with only non-positive components is prefixed with a minus sign
+/// (`-P1Y2M`) as is commonly done, and mixed signs are written per component.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, Period};
+///
+/// assert_eq!(Period::new(1, 2, 3).to_string(), "P1Y2M3D");
+/// assert_eq!(Period::zero().to_string(), "P0D");
+/// assert_eq!(Period::new(-1, -2, 0).to_string(), "-P1Y2M");
+/// assert_eq!(Period::with_time(0, 0, 1, Duration::milliseconds(3_723_500)).to_string(),
+///            "P1DT1H2M3.5S");
+/// ```
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let neg = self.is_negative()
+            && self.years <= 0
+            && self.months <= 0
+            && self.days <= 0
+            && self.time <= OldDuration::zero();
+        let p = if neg { -*self } else { *self };
+        if neg {
+            write!(f, "-")?;
+        }
+        write!(f, "P")?;
+        if p.years != 0 {
+            write!(f, "{}Y", p.years)?;
+        }
+        if p.months != 0 {
+            write!(f, "{}M", p.months)?;
+        }
+        if p.days != 0 || (p.years == 0 && p.months == 0 && p.time.is_zero()) {
+            write!(f, "{}D", p.days)?;
+        }
+        if !p.time.is_zero() {
+            let (sign, time) =
+                if p.time < OldDuration::zero() { ("-", -p.time) } else { ("", p.time) };
+            let hours = time.num_hours();
+            let minutes = time.num_minutes() % 60;
+            let secs = time - OldDuration::minutes(time.num_minutes());
+            let nanos = (secs - OldDuration::seconds(secs.num_seconds()))
+                .num_nanoseconds()
+                .expect("sub-second remainder should fit in nanoseconds");
+            write!(f, "T")?;
+            if hours != 0 {
+                write!(f, "{}{}H", sign, hours)?;
+            }
+            if minutes != 0 {
+                write!(f, "{}{}M", sign, minutes)?;
+            }
+            if secs.num_seconds() != 0 || nanos != 0 {
+                write!(f, "{}{}", sign, secs.num_seconds())?;
+                if nanos != 0 {
+                    // trailing zeroes are dropped from the fraction
+                    let (mut frac, mut width) = (nanos, 9);
+                    while frac % 10 == 0 {
+                        frac /= 10;
+                        width -= 1;
+                    }
+                    write!(f, ".{:0width$}", frac, width = width)?;
+                }
+                write!(f, "S")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Period;
+    use oldtime::Duration;
+
+    #[test]
+    fn test_period_accessors() {
+        let p = Period::with_time(1, 2, 3, Duration::hours(4));
+        assert_eq!((p.years(), p.months(), p.days(), p.time()), (1, 2, 3, Duration::hours(4)));
+        assert_eq!(p.total_months(), 14);
+        assert!(!p.is_zero());
+        assert!(!p.is_negative());
+        assert!((-p).is_negative());
+        assert!(Period::zero().is_zero());
+        assert_eq!(Period::default(), Period::zero());
+    }
+
+    #[test]
+    fn test_period_display() {
+        assert_eq!(Period::new(0, 0, 0).to_string(), "P0D");
+        assert_eq!(Period::new(3, 0, 0).to_string(), "P3Y");
+        assert_eq!(Period::new(0, 11, 30).to_string(), "P11M30D");
+        assert_eq!(Period::new(0, 0, -1).to_string(), "-P1D");
+        assert_eq!(Period::new(1, -1, 0).to_string(), "P1Y-1M");
+        assert_eq!(Period::with_time(0, 0, 0, Duration::minutes(90)).to_string(), "PT1H30M");
+        assert_eq!(
+            Period::with_time(0, 0, 0, Duration::nanoseconds(1)).to_string(),
+            "PT0.000000001S"
+        );
+        assert_eq!(Period::with_time(0, 0, -2, -Duration::seconds(5)).to_string(), "-P2DT5S");
+    }
+}
@@ -300,6 +300,34 @@ impl std::error::Error for RoundingError {
     }
 }
 
+/// How a duration is rounded to a lower precision, e.g. when it is formatted.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Rounding {
+    /// Drops the excess precision.
+    TowardZero,
+    /// Rounds to the nearest value, with ties away from zero.
+    Nearest,
+    /// Rounds any excess precision up to the next value away from zero.
+    AwayFromZero,
+}
+
+impl Rounding {
+    /// Divides a non-negative `value` by a positive `divisor` with this rounding.
+    pub(crate) fn div(&self, value: i128, divisor: i128) -> i128 {
+        let (quot, rem) = (value / divisor, value % divisor);
+        let up = match *self {
+            Rounding::TowardZero => false,
+            Rounding::Nearest => rem * 2 >= divisor,
+            Rounding::AwayFromZero => rem > 0,
+        };
+        if up {
+            quot + 1
+        } else {
+            quot
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Duration, DurationRound, SubsecRound};
@@ -0,0 +1,1288 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Recurrence rules as defined by the iCalendar specification,
+//! [RFC 5545](https://tools.ietf.org/html/rfc5545#section-3.3.10).
+//!
+//! An [`RRule`](./struct.RRule.html) describes a repeating pattern
+//! (e.g. "the last weekday of every month") and can be parsed from and formatted to
+//! the `RRULE` property value syntax.
+//! A [`Recurrence`](./struct.Recurrence.html) combines a rule with its start (`DTSTART`)
+//! and additional (`RDATE`) or excluded (`EXDATE`) occurrences.
+//!
+//! Rules are evaluated in the local time of the start's time zone, so that
+//! an event at 09:00 stays at 09:00 across daylight saving time transitions.
+//! As required by RFC 5545, a local time which does not exist because of a transition
+//! is shifted forward by the length of the gap, and an ambiguous local time
+//! resolves to the first of both instants.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono::rrule::{Recurrence, RRule};
+//! use chrono::{TimeZone, Utc};
+//!
+//! // every month on the last weekday, three times
+//! let rule = "FREQ=MONTHLY;COUNT=3;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1".parse::<RRule>().unwrap();
+//! let start = Utc.ymd(2021, 1, 29).and_hms(9, 0, 0);
+//! assert_eq!(rule.iter(&start).collect::<Vec<_>>(),
+//!            [Utc.ymd(2021, 1, 29).and_hms(9, 0, 0),
+//!             Utc.ymd(2021, 2, 26).and_hms(9, 0, 0),
+//!             Utc.ymd(2021, 3, 31).and_hms(9, 0, 0)]);
+//!
+//! // skip February and add an extra occurrence
+//! let recurrence = Recurrence::new(start)
+//!     .with_rule(rule)
+//!     .with_exdate(Utc.ymd(2021, 2, 26).and_hms(9, 0, 0))
+//!     .with_rdate(Utc.ymd(2021, 2, 15).and_hms(9, 0, 0));
+//! assert_eq!(recurrence.iter().collect::<Vec<_>>(),
+//!            [Utc.ymd(2021, 1, 29).and_hms(9, 0, 0),
+//!             Utc.ymd(2021, 2, 15).and_hms(9, 0, 0),
+//!             Utc.ymd(2021, 3, 31).and_hms(9, 0, 0)]);
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::{cmp, fmt, str};
+use num_traits::ToPrimitive;
+use oldtime::Duration as OldDuration;
+
+use datetime::resolve_local;
+use div::{div_mod_floor, gcd};
+use format::{ParseError, ParseResult, INVALID, NOT_ENOUGH, OUT_OF_RANGE};
+use naive::{NaiveDate, NaiveDateTime};
+use offset::TimeZone;
+use {DateTime, Datelike, Weekday};
+
+/// The frequency of a recurrence rule (the `FREQ` rule part).
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Frequency {
+    /// Repeats every second.
+    Secondly,
+    /// Repeats every minute.
+    Minutely,
+    /// Repeats every hour.
+    Hourly,
+    /// Repeats every day.
+    Daily,
+    /// Repeats every week.
+    Weekly,
+    /// Repeats every month.
+    Monthly,
+    /// Repeats every year.
+    Yearly,
+}
+
+impl Frequency {
+    fn name(&self) -> &'static str {
+        match *self {
+            Frequency::Secondly => "SECONDLY",
+            Frequency::Minutely => "MINUTELY",
+            Frequency::Hourly => "HOURLY",
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+
+    /// Returns the number of periods of this frequency in the 400 years after which
+    /// the Gregorian calendar repeats, which are 146097 days or 20871 weeks.
+    fn cycle(&self) -> i64 {
+        match *self {
+            Frequency::Yearly => 400,
+            Frequency::Monthly => 4800,
+            Frequency::Weekly => 20_871,
+            Frequency::Daily => 146_097,
+            _ => 146_097 * 86_400 / self.seconds().unwrap_or(1),
+        }
+    }
+
+    /// Returns the length of the period in seconds for rules more frequent than daily.
+    fn seconds(&self) -> Option<i64> {
+        match *self {
+            Frequency::Secondly => Some(1),
+            Frequency::Minutely => Some(60),
+            Frequency::Hourly => Some(3600),
+            _ => None,
+        }
+    }
+}
+
+/// A weekday with an optional ordinal, as used in the `BYDAY` rule part.
+///
+/// `MO` matches every Monday of the period, while `2MO` matches the second Monday
+/// and `-1FR` matches the last Friday of the month (or of the year for yearly rules).
+/// Ordinals are ignored for rules more frequent than monthly.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NthWeekday {
+    weekday: Weekday,
+    n: i32,
+}
+
+impl NthWeekday {
+    /// Makes a new `NthWeekday` matching every given weekday.
+    #[inline]
+    pub fn every(weekday: Weekday) -> NthWeekday {
+        NthWeekday { weekday: weekday, n: 0 }
+    }
+
+    /// Makes a new `NthWeekday` matching the `n`-th given weekday of the period,
+    /// counting from the end when `n` is negative.
+    ///
+    /// Panics if `n` is zero.
+    #[inline]
+    pub fn nth(weekday: Weekday, n: i32) -> NthWeekday {
+        assert!(n != 0, "ordinal should not be zero");
+        NthWeekday { weekday: weekday, n: n }
+    }
+
+    /// Returns the weekday.
+    #[inline]
+    pub fn weekday(&self) -> Weekday {
+        self.weekday
+    }
+
+    /// Returns the ordinal, or `None` if every such weekday matches.
+    #[inline]
+    pub fn n(&self) -> Option<i32> {
+        if self.n == 0 {
+            None
+        } else {
+            Some(self.n)
+        }
+    }
+}
+
+impl fmt::Display for NthWeekday {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.n != 0 {
+            write!(f, "{}", self.n)?;
+        }
+        f.write_str(weekday_code(self.weekday))
+    }
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_weekday_code(s: &str) -> ParseResult<Weekday> {
+    let weekdays = [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ];
+    weekdays.iter().cloned().find(|&wd| s.eq_ignore_ascii_case(weekday_code(wd))).ok_or(INVALID)
+}
+
+/// The end of a recurrence (the `UNTIL` rule part).
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+enum Until {
+    /// The last local date (inclusive).
+    Date(NaiveDate),
+    /// The last local date and time (inclusive).
+    Local(NaiveDateTime),
+    /// The last instant in UTC (inclusive).
+    Utc(NaiveDateTime),
+}
+
+impl Until {
+    fn is_before<Tz: TimeZone>(&self, local: &NaiveDateTime, dt: &DateTime<Tz>) -> bool {
+        match *self {
+            Until::Date(date) => date < local.date(),
+            Until::Local(until) => until < *local,
+            Until::Utc(until) => until < dt.naive_utc(),
+        }
+    }
+}
+
+/// A recurrence rule (the value of the `RRULE` property) as defined by RFC 5545.
+///
+/// The rule supports the `FREQ`, `INTERVAL`, `COUNT`, `UNTIL`, `WKST`, `BYMONTH`, `BYWEEKNO`,
+/// `BYMONTHDAY`, `BYDAY` and `BYSETPOS` rule parts.
+/// It can be built with the `with_*` methods or parsed from a string,
+/// and its `Display` output is the `RRULE` property value.
+///
+/// The rule is expanded from a start date and time with [`iter`](#method.iter).
+/// Like most implementations, only the occurrences matching the rule are returned;
+/// use [`Recurrence`](./struct.Recurrence.html) to always include the start as RFC 5545 requires.
+///
+/// # Example
+///
+/// ```
+/// use chrono::rrule::{Frequency, NthWeekday, RRule};
+/// use chrono::{NaiveDate, TimeZone, Utc, Weekday};
+///
+/// // the first Friday of every other month
+/// let rule = RRule::new(Frequency::Monthly)
+///     .with_interval(2)
+///     .with_by_day(&[NthWeekday::nth(Weekday::Fri, 1)]);
+/// assert_eq!(rule.to_string(), "FREQ=MONTHLY;INTERVAL=2;BYDAY=1FR");
+/// assert_eq!("FREQ=MONTHLY;INTERVAL=2;BYDAY=1FR".parse(), Ok(rule.clone()));
+///
+/// let start = Utc.ymd(2021, 1, 1).and_hms(18, 0, 0);
+/// let dates = rule.iter(&start).map(|dt| dt.date().naive_utc()).take(3).collect::<Vec<_>>();
+/// assert_eq!(dates, [NaiveDate::from_ymd(2021, 1, 1),
+///                    NaiveDate::from_ymd(2021, 3, 5),
+///                    NaiveDate::from_ymd(2021, 5, 7)]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RRule {
+    freq: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<Until>,
+    week_start: Weekday,
+    by_month: Vec<u32>,
+    by_week_no: Vec<i32>,
+    by_month_day: Vec<i32>,
+    by_day: Vec<NthWeekday>,
+    by_set_pos: Vec<i32>,
+}
+
+impl RRule {
+    /// Makes a new rule repeating at given frequency, without an end.
+    pub fn new(freq: Frequency) -> RRule {
+        RRule {
+            freq: freq,
+            interval: 1,
+            count: None,
+            until: None,
+            week_start: Weekday::Mon,
+            by_month: Vec::new(),
+            by_week_no: Vec::new(),
+            by_month_day: Vec::new(),
+            by_day: Vec::new(),
+            by_set_pos: Vec::new(),
+        }
+    }
+
+    /// Sets the number of periods between occurrences (the `INTERVAL` rule part).
+    ///
+    /// Panics if `interval` is zero.
+    pub fn with_interval(mut self, interval: u32) -> RRule {
+        assert!(interval > 0, "interval should be positive");
+        self.interval = interval;
+        self
+    }
+
+    /// Limits the number of occurrences (the `COUNT` rule part).
+    pub fn with_count(mut self, count: u32) -> RRule {
+        self.count = Some(count);
+        self
+    }
+
+    /// Sets the last instant of the recurrence, inclusive (the `UNTIL` rule part).
+    pub fn with_until<Tz: TimeZone>(mut self, until: DateTime<Tz>) -> RRule {
+        self.until = Some(Until::Utc(until.naive_utc()));
+        self
+    }
+
+    /// Sets the last local date and time of the recurrence, inclusive
+    /// (the `UNTIL` rule part in the "floating" local time).
+    pub fn with_until_local(mut self, until: NaiveDateTime) -> RRule {
+        self.until = Some(Until::Local(until));
+        self
+    }
+
+    /// Sets the first day of the week (the `WKST` rule part), Monday by default.
+    ///
+    /// This affects weekly rules with an interval greater than one and week numbers.
+    pub fn with_week_start(mut self, week_start: Weekday) -> RRule {
+        self.week_start = week_start;
+        self
+    }
+
+    /// Restricts the occurrences to given months, from 1 to 12 (the `BYMONTH` rule part).
+    pub fn with_by_month(mut self, months: &[u32]) -> RRule {
+        self.by_month = months.to_vec();
+        self
+    }
+
+    /// Restricts the occurrences to given week numbers (the `BYWEEKNO` rule part).
+    ///
+    /// Week numbers range from 1 to 53, or from -53 to -1 counting from the end of the year.
+    /// The first week of a year is the first week, starting at the [week start](#method.with_week_start),
+    /// with at least four days in that year;
+    /// this is the ISO 8601 week number ([`IsoWeek`](../naive/struct.IsoWeek.html)) by default.
+    pub fn with_by_week_no(mut self, weeks: &[i32]) -> RRule {
+        self.by_week_no = weeks.to_vec();
+        self
+    }
+
+    /// Restricts the occurrences to given days of month (the `BYMONTHDAY` rule part).
+    ///
+    /// Days range from 1 to 31, or from -31 to -1 counting from the end of the month.
+    pub fn with_by_month_day(mut self, days: &[i32]) -> RRule {
+        self.by_month_day = days.to_vec();
+        self
+    }
+
+    /// Restricts the occurrences to given weekdays (the `BYDAY` rule part).
+    pub fn with_by_day(mut self, weekdays: &[NthWeekday]) -> RRule {
+        self.by_day = weekdays.to_vec();
+        self
+    }
+
+    /// Selects the occurrences at given positions in each period (the `BYSETPOS` rule part).
+    ///
+    /// Positions start at 1, or at -1 counting from the end of the period.
+    pub fn with_by_set_pos(mut self, positions: &[i32]) -> RRule {
+        self.by_set_pos = positions.to_vec();
+        self
+    }
+
+    /// Returns the frequency of the rule.
+    #[inline]
+    pub fn frequency(&self) -> Frequency {
+        self.freq
+    }
+
+    /// Returns the number of periods between occurrences.
+    #[inline]
+    pub fn interval(&self) -> u32 {
+        self.interval
+    }
+
+    /// Returns the maximum number of occurrences, if any.
+    #[inline]
+    pub fn count(&self) -> Option<u32> {
+        self.count
+    }
+
+    /// Returns an iterator over the occurrences of the rule starting from `start`.
+    ///
+    /// The time of day and, unless given by the rule, the day of month, weekday and month
+    /// of the occurrences are taken from `start`.
+    /// Occurrences before `start` are never returned.
+    pub fn iter<Tz: TimeZone>(&self, start: &DateTime<Tz>) -> RRuleIter<Tz> {
+        let mut rule = self.clone();
+        let local = start.naive_local();
+        if rule.by_week_no.is_empty() && rule.by_month_day.is_empty() && rule.by_day.is_empty() {
+            match rule.freq {
+                Frequency::Yearly => {
+                    if rule.by_month.is_empty() {
+                        rule.by_month.push(local.month());
+                    }
+                    rule.by_month_day.push(local.day() as i32);
+                }
+                Frequency::Monthly => rule.by_month_day.push(local.day() as i32),
+                Frequency::Weekly => rule.by_day.push(NthWeekday::every(local.weekday())),
+                _ => {}
+            }
+        }
+        RRuleIter {
+            rule: rule,
+            start: start.clone(),
+            period: 0,
+            pending: Vec::new(),
+            last: None,
+            empty_since: None,
+            found: 0,
+            finished: false,
+        }
+    }
+
+    /// Returns `true` if the date satisfies all date restrictions of the rule.
+    fn matches(&self, date: NaiveDate) -> bool {
+        if !self.by_month.is_empty() && !self.by_month.contains(&date.month()) {
+            return false;
+        }
+        if !self.by_week_no.is_empty() {
+            let (week, weeks) = match week_number(date, self.week_start) {
+                Some((week, weeks)) => (week as i32, weeks as i32),
+                None => return false,
+            };
+            if !self.by_week_no.iter().any(|&n| n == week || n == week - weeks - 1) {
+                return false;
+            }
+        }
+        if !self.by_month_day.is_empty() {
+            let (day, last) = (date.day() as i32, days_in_month(date) as i32);
+            if !self.by_month_day.iter().any(|&n| n == day || n == day - last - 1) {
+                return false;
+            }
+        }
+        if !self.by_day.is_empty() && !self.by_day.iter().any(|wd| self.matches_weekday(date, wd)) {
+            return false;
+        }
+        true
+    }
+
+    fn matches_weekday(&self, date: NaiveDate, weekday: &NthWeekday) -> bool {
+        if date.weekday() != weekday.weekday {
+            return false;
+        }
+        let n = weekday.n;
+        match self.freq {
+            _ if n == 0 => true,
+            Frequency::Monthly => nth_in_month(date, n),
+            Frequency::Yearly if !self.by_month.is_empty() => nth_in_month(date, n),
+            Frequency::Yearly => {
+                let (ordinal, last) = (date.ordinal() as i32, days_in_year(date.year()) as i32);
+                n == (ordinal - 1) / 7 + 1 || n == -((last - ordinal) / 7 + 1)
+            }
+            // ordinals only make sense in monthly and yearly rules
+            _ => true,
+        }
+    }
+}
+
+/// Returns `true` if the date is the `n`-th such weekday in its month.
+fn nth_in_month(date: NaiveDate, n: i32) -> bool {
+    if n > 0 {
+        n <= 5
+            && NaiveDate::from_weekday_of_month_opt(
+                date.year(),
+                date.month(),
+                date.weekday(),
+                n as u8,
+            ) == Some(date)
+    } else {
+        n == -((days_in_month(date) as i32 - date.day() as i32) / 7 + 1)
+    }
+}
+
+fn days_in_month(date: NaiveDate) -> u32 {
+    let (year, month) = (date.year(), date.month());
+    (28..32).rev().find(|&day| NaiveDate::from_ymd_opt(year, month, day).is_some()).unwrap_or(28)
+}
+
+fn days_in_year(year: i32) -> u32 {
+    if NaiveDate::from_yo_opt(year, 366).is_some() {
+        366
+    } else {
+        365
+    }
+}
+
+/// Returns the first day of week 1 of given year, where weeks start at `week_start`
+/// and week 1 is the first week with at least four days in the year.
+fn first_week_start(year: i32, week_start: Weekday) -> Option<NaiveDate> {
+    let jan4 = try_opt!(NaiveDate::from_ymd_opt(year, 1, 4));
+    let days = (jan4.weekday().num_days_from_monday() + 7 - week_start.num_days_from_monday()) % 7;
+    jan4.checked_sub_signed(OldDuration::days(i64::from(days)))
+}
+
+/// Returns the week number of the date and the number of weeks in its week-based year.
+fn week_number(date: NaiveDate, week_start: Weekday) -> Option<(u32, u32)> {
+    let mut year = date.year();
+    if date < try_opt!(first_week_start(year, week_start)) {
+        year -= 1;
+    } else if date >= try_opt!(first_week_start(year + 1, week_start)) {
+        year += 1;
+    }
+    let first = try_opt!(first_week_start(year, week_start));
+    let next = try_opt!(first_week_start(year + 1, week_start));
+    let week = date.signed_duration_since(first).num_weeks() + 1;
+    Some((week as u32, next.signed_duration_since(first).num_weeks() as u32))
+}
+
+/// Iterator over the occurrences of an [`RRule`](./struct.RRule.html).
+///
+/// This is created by [`RRule::iter`](./struct.RRule.html#method.iter).
+#[derive(Clone, Debug)]
+pub struct RRuleIter<Tz: TimeZone> {
+    /// the rule with the defaults from the start filled in
+    rule: RRule,
+    start: DateTime<Tz>,
+    period: i64,
+    /// candidates of the current period in the reverse order
+    pending: Vec<NaiveDateTime>,
+    last: Option<DateTime<Tz>>,
+    /// the index of the first period in the current run of periods without candidates
+    empty_since: Option<i64>,
+    found: u32,
+    finished: bool,
+}
+
+impl<Tz: TimeZone> RRuleIter<Tz> {
+    /// Returns the first date of the period at given index and the sorted local candidates in it,
+    /// or `None` if the period is out of range.
+    fn candidates(&self, index: i64) -> Option<(NaiveDate, Vec<NaiveDateTime>)> {
+        let rule = &self.rule;
+        let start = self.start.naive_local();
+        let steps = try_opt!(index.checked_mul(i64::from(rule.interval)));
+        let (first, days) = match rule.freq {
+            Frequency::Yearly => {
+                let year = try_opt!(try_opt!(i64::from(start.year()).checked_add(steps)).to_i32());
+                (try_opt!(NaiveDate::from_yo_opt(year, 1)), days_in_year(year))
+            }
+            Frequency::Monthly => {
+                let month = i64::from(start.year()) * 12 + i64::from(start.month0());
+                let (year, month0) = div_mod_floor(try_opt!(month.checked_add(steps)), 12);
+                let year = try_opt!(year.to_i32());
+                let first = try_opt!(NaiveDate::from_ymd_opt(year, month0 as u32 + 1, 1));
+                (first, days_in_month(first))
+            }
+            Frequency::Weekly => {
+                let back = (start.weekday().num_days_from_monday() + 7
+                    - rule.week_start.num_days_from_monday())
+                    % 7;
+                let first =
+                    try_opt!(start.date().checked_sub_signed(OldDuration::days(i64::from(back))));
+                (try_opt!(first.checked_add_signed(OldDuration::weeks(steps))), 7)
+            }
+            Frequency::Daily => {
+                (try_opt!(start.date().checked_add_signed(OldDuration::days(steps))), 1)
+            }
+            Frequency::Hourly | Frequency::Minutely | Frequency::Secondly => {
+                let secs = try_opt!(steps.checked_mul(try_opt!(rule.freq.seconds())));
+                let local = try_opt!(start.checked_add_signed(OldDuration::seconds(secs)));
+                let mut candidates = Vec::new();
+                if rule.matches(local.date()) {
+                    candidates.push(local);
+                }
+                return Some((local.date(), candidates));
+            }
+        };
+
+        let mut candidates = Vec::new();
+        let mut date = first;
+        for i in 0..days {
+            if i > 0 {
+                date = try_opt!(date.succ_opt());
+            }
+            if rule.matches(date) {
+                candidates.push(date.and_time(start.time()));
+            }
+        }
+        if !rule.by_set_pos.is_empty() {
+            let len = candidates.len() as i64;
+            let mut selected = Vec::new();
+            for &pos in &rule.by_set_pos {
+                let i = if pos > 0 { i64::from(pos) - 1 } else { len + i64::from(pos) };
+                if 0 <= i && i < len {
+                    selected.push(candidates[i as usize]);
+                }
+            }
+            selected.sort();
+            selected.dedup();
+            candidates = selected;
+        }
+        Some((first, candidates))
+    }
+
+    /// Returns the index of the first period after the current one,
+    /// or `None` if it is out of range.
+    ///
+    /// For rules more frequent than daily, the periods in the rest of the day are skipped
+    /// when the current period has no candidates, as they are all on an excluded date.
+    fn next_period(&self, date: NaiveDate, candidates: &[NaiveDateTime]) -> Option<i64> {
+        let unit = match self.rule.freq.seconds() {
+            Some(unit) if candidates.is_empty() => unit,
+            _ => return self.period.checked_add(1),
+        };
+        let step = try_opt!(unit.checked_mul(i64::from(self.rule.interval)));
+        let next_day = try_opt!(date.succ_opt()).and_hms(0, 0, 0);
+        let until_next_day = next_day.signed_duration_since(self.start.naive_local());
+        let mut secs = until_next_day.num_seconds();
+        if until_next_day > OldDuration::seconds(secs) {
+            secs += 1;
+        }
+        Some(cmp::max((secs + step - 1) / step, self.period + 1))
+    }
+}
+
+impl<Tz: TimeZone> Iterator for RRuleIter<Tz> {
+    type Item = DateTime<Tz>;
+
+    fn next(&mut self) -> Option<DateTime<Tz>> {
+        while !self.finished {
+            if self.rule.count == Some(self.found) {
+                self.finished = true;
+                break;
+            }
+
+            let local = match self.pending.pop() {
+                Some(local) => local,
+                None => {
+                    match self.candidates(self.period) {
+                        Some((first, mut candidates)) => {
+                            if candidates.is_empty() {
+                                // the Gregorian calendar repeats every 400 years, so once the
+                                // periods without candidates cover every position in that
+                                // cycle, no more occurrences can be found
+                                let since = *self.empty_since.get_or_insert(self.period);
+                                let cycle = self.rule.freq.cycle();
+                                let interval = i64::from(self.rule.interval);
+                                if self.period - since >= cycle / gcd(cycle, interval) {
+                                    self.finished = true;
+                                    continue;
+                                }
+                            } else {
+                                self.empty_since = None;
+                            }
+                            match self.next_period(first, &candidates) {
+                                Some(period) => self.period = period,
+                                None => self.finished = true,
+                            }
+                            candidates.reverse();
+                            self.pending = candidates;
+                        }
+                        None => self.finished = true,
+                    }
+                    continue;
+                }
+            };
+
+            let dt = if local == self.start.naive_local() {
+                self.start.clone()
+            } else {
+                match resolve_local(&self.start.timezone(), &local) {
+                    Some(dt) => dt,
+                    None => continue,
+                }
+            };
+            if dt < self.start {
+                continue;
+            }
+            match self.rule.until {
+                Some(until) if until.is_before(&local, &dt) => {
+                    self.finished = true;
+                    break;
+                }
+                _ => {}
+            }
+            if let Some(ref last) = self.last {
+                if dt <= *last {
+                    continue;
+                }
+            }
+            self.found += 1;
+            self.last = Some(dt.clone());
+            return Some(dt);
+        }
+        None
+    }
+}
+
+/// The `Display` output of a rule is the value of the `RRULE` property,
+/// e.g. `FREQ=MONTHLY;COUNT=10;BYDAY=-1FR`.
+impl fmt::Display for RRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn write_list<T: fmt::Display>(
+            f: &mut fmt::Formatter,
+            name: &str,
+            items: &[T],
+        ) -> fmt::Result {
+            for (i, item) in items.iter().enumerate() {
+                let sep = if i == 0 { ";" } else { "," };
+                let name = if i == 0 { name } else { "" };
+                write!(f, "{}{}{}", sep, name, item)?;
+            }
+            Ok(())
+        }
+
+        write!(f, "FREQ={}", self.freq.name())?;
+        match self.until {
+            Some(Until::Date(date)) => write!(f, ";UNTIL={}", date.format("%Y%m%d"))?,
+            Some(Until::Local(dt)) => write!(f, ";UNTIL={}", dt.format("%Y%m%dT%H%M%S"))?,
+            Some(Until::Utc(dt)) => write!(f, ";UNTIL={}", dt.format("%Y%m%dT%H%M%SZ"))?,
+            None => {}
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if self.week_start != Weekday::Mon {
+            write!(f, ";WKST={}", weekday_code(self.week_start))?;
+        }
+        write_list(f, "BYMONTH=", &self.by_month)?;
+        write_list(f, "BYWEEKNO=", &self.by_week_no)?;
+        write_list(f, "BYMONTHDAY=", &self.by_month_day)?;
+        write_list(f, "BYDAY=", &self.by_day)?;
+        write_list(f, "BYSETPOS=", &self.by_set_pos)
+    }
+}
+
+/// Parses a comma-separated list of non-zero numbers whose absolute value is at most `max`.
+fn parse_numbers(s: &str, max: i32, signed: bool) -> ParseResult<Vec<i32>> {
+    let mut numbers = Vec::new();
+    for item in s.split(',') {
+        let n = item.trim().parse::<i32>().map_err(|_| INVALID)?;
+        if n == 0 || n.abs() > max || (n < 0 && !signed) {
+            return Err(OUT_OF_RANGE);
+        }
+        numbers.push(n);
+    }
+    Ok(numbers)
+}
+
+fn parse_until(s: &str) -> ParseResult<Until> {
+    if s.len() == 8 {
+        Ok(Until::Date(NaiveDate::parse_from_str(s, "%Y%m%d")?))
+    } else if s.ends_with('Z') || s.ends_with('z') {
+        Ok(Until::Utc(NaiveDateTime::parse_from_str(&s[..s.len() - 1], "%Y%m%dT%H%M%S")?))
+    } else {
+        Ok(Until::Local(NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%S")?))
+    }
+}
+
+/// Parsing a `str` into an `RRule` accepts the value of the `RRULE` property
+/// as defined by RFC 5545, optionally prefixed by `RRULE:`.
+///
+/// Rule part names and values are case-insensitive.
+/// Unsupported rule parts (e.g. `BYHOUR`) are rejected, as are rules with both
+/// `COUNT` and `UNTIL`, which RFC 5545 does not allow.
+///
+/// # Example
+///
+/// ```
+/// use chrono::rrule::{Frequency, RRule};
+///
+/// let rule = "RRULE:FREQ=YEARLY;BYMONTH=1;BYDAY=SU,MO".parse::<RRule>().unwrap();
+/// assert_eq!(rule.frequency(), Frequency::Yearly);
+/// assert!("FREQ=YEARLY;BYHOUR=9".parse::<RRule>().is_err());
+/// assert!("BYMONTH=13".parse::<RRule>().is_err());
+/// ```
+impl str::FromStr for RRule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> ParseResult<RRule> {
+        let s = s.trim();
+        let s = match s.get(..6) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => &s[6..],
+            _ => s,
+        };
+
+        let mut freq = None;
+        let mut rule = RRule::new(Frequency::Yearly);
+        for part in s.split(';') {
+            let mut name_value = part.splitn(2, '=');
+            let (name, value) = match (name_value.next(), name_value.next()) {
+                (Some(name), Some(value)) => (name.trim().to_ascii_uppercase(), value.trim()),
+                _ => return Err(INVALID),
+            };
+            match &*name {
+                "FREQ" => {
+                    let freqs = [
+                        Frequency::Secondly,
+                        Frequency::Minutely,
+                        Frequency::Hourly,
+                        Frequency::Daily,
+                        Frequency::Weekly,
+                        Frequency::Monthly,
+                        Frequency::Yearly,
+                    ];
+                    let found = freqs.iter().find(|f| value.eq_ignore_ascii_case(f.name()));
+                    freq = Some(*found.ok_or(INVALID)?);
+                }
+                "INTERVAL" => {
+                    rule.interval = value.parse().map_err(|_| INVALID)?;
+                    if rule.interval == 0 {
+                        return Err(OUT_OF_RANGE);
+                    }
+                }
+                "COUNT" => rule.count = Some(value.parse().map_err(|_| INVALID)?),
+                "UNTIL" => rule.until = Some(parse_until(value)?),
+                "WKST" => rule.week_start = parse_weekday_code(value)?,
+                "BYMONTH" => {
+                    rule.by_month =
+                        parse_numbers(value, 12, false)?.iter().map(|&n| n as u32).collect()
+                }
+                "BYWEEKNO" => rule.by_week_no = parse_numbers(value, 53, true)?,
+                "BYMONTHDAY" => rule.by_month_day = parse_numbers(value, 31, true)?,
+                "BYSETPOS" => rule.by_set_pos = parse_numbers(value, 366, true)?,
+                "BYDAY" => {
+                    rule.by_day.clear();
+                    for item in value.split(',') {
+                        let item = item.trim();
+                        if item.len() < 2 || !item.is_char_boundary(item.len() - 2) {
+                            return Err(INVALID);
+                        }
+                        let (n, weekday) = item.split_at(item.len() - 2);
+                        let weekday = parse_weekday_code(weekday)?;
+                        if n.is_empty() {
+                            rule.by_day.push(NthWeekday::every(weekday));
+                        } else {
+                            let n =
+                                n.trim_start_matches('+').parse::<i32>().map_err(|_| INVALID)?;
+                            if n == 0 || n.abs() > 53 {
+                                return Err(OUT_OF_RANGE);
+                            }
+                            rule.by_day.push(NthWeekday::nth(weekday, n));
+                        }
+                    }
+                }
+                _ => return Err(INVALID),
+            }
+        }
+        rule.freq = freq.ok_or(NOT_ENOUGH)?;
+        // RFC 5545 does not allow both ends in one rule
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err(INVALID);
+        }
+        Ok(rule)
+    }
+}
+
+/// A set of recurring occurrences as defined by RFC 5545:
+/// the start (`DTSTART`), an optional rule (`RRULE`),
+/// additional occurrences (`RDATE`) and excluded occurrences (`EXDATE`).
+///
+/// The start is always the first occurrence (unless excluded),
+/// even when it does not match the rule.
+/// The `COUNT` of the rule applies before the exclusions.
+#[derive(Clone, Debug)]
+pub struct Recurrence<Tz: TimeZone> {
+    start: DateTime<Tz>,
+    rule: Option<RRule>,
+    rdates: Vec<DateTime<Tz>>,
+    exdates: Vec<DateTime<Tz>>,
+}
+
+impl<Tz: TimeZone> Recurrence<Tz> {
+    /// Makes a new recurrence with a single occurrence at `start`.
+    pub fn new(start: DateTime<Tz>) -> Recurrence<Tz> {
+        Recurrence { start: start, rule: None, rdates: Vec::new(), exdates: Vec::new() }
+    }
+
+    /// Sets the recurrence rule.
+    pub fn with_rule(mut self, rule: RRule) -> Recurrence<Tz> {
+        self.rule = Some(rule);
+        self
+    }
+
+    /// Adds an occurrence.
+    pub fn with_rdate(mut self, rdate: DateTime<Tz>) -> Recurrence<Tz> {
+        self.rdates.push(rdate);
+        self
+    }
+
+    /// Excludes an occurrence. It is compared as an instant.
+    pub fn with_exdate(mut self, exdate: DateTime<Tz>) -> Recurrence<Tz> {
+        self.exdates.push(exdate);
+        self
+    }
+
+    /// Returns the start of the recurrence.
+    #[inline]
+    pub fn start(&self) -> &DateTime<Tz> {
+        &self.start
+    }
+
+    /// Returns the recurrence rule, if any.
+    #[inline]
+    pub fn rule(&self) -> Option<&RRule> {
+        self.rule.as_ref()
+    }
+
+    /// Returns an iterator over all occurrences in chronological order, without duplicates.
+    pub fn iter(&self) -> RecurrenceIter<Tz> {
+        let mut rdates = self.rdates.clone();
+        rdates.push(self.start.clone());
+        // popped from the end
+        rdates.sort_by(|a, b| b.cmp(a));
+        let mut exdates = self.exdates.clone();
+        exdates.sort();
+        RecurrenceIter {
+            rule: self.rule.as_ref().map(|rule| rule.iter(&self.start)),
+            peeked: None,
+            rdates: rdates,
+            exdates: exdates,
+            last: None,
+        }
+    }
+}
+
+/// Iterator over the occurrences of a [`Recurrence`](./struct.Recurrence.html).
+///
+/// This is created by [`Recurrence::iter`](./struct.Recurrence.html#method.iter).
+#[derive(Clone, Debug)]
+pub struct RecurrenceIter<Tz: TimeZone> {
+    rule: Option<RRuleIter<Tz>>,
+    peeked: Option<DateTime<Tz>>,
+    /// sorted in the reverse order
+    rdates: Vec<DateTime<Tz>>,
+    exdates: Vec<DateTime<Tz>>,
+    last: Option<DateTime<Tz>>,
+}
+
+impl<Tz: TimeZone> Iterator for RecurrenceIter<Tz> {
+    type Item = DateTime<Tz>;
+
+    fn next(&mut self) -> Option<DateTime<Tz>> {
+        loop {
+            if self.peeked.is_none() {
+                self.peeked = self.rule.as_mut().and_then(|rule| rule.next());
+            }
+            let from_rule = match (self.peeked.as_ref(), self.rdates.last()) {
+                (Some(peeked), Some(rdate)) => peeked <= rdate,
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            let next = if from_rule { self.peeked.take() } else { self.rdates.pop() };
+            let next = try_opt!(next);
+            if let Some(ref last) = self.last {
+                if next <= *last {
+                    continue;
+                }
+            }
+            self.last = Some(next.clone());
+            if self.exdates.binary_search(&next).is_err() {
+                return Some(next);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{week_number, Frequency, NthWeekday, RRule, Recurrence};
+    use naive::NaiveDate;
+    use offset::test_zone::Eastern;
+    use offset::{TimeZone, Utc};
+    use oldtime::Duration;
+    use {DateTime, Datelike, Weekday};
+
+    fn local(y: i32, m: u32, d: u32, h: u32, n: u32) -> DateTime<Eastern> {
+        Eastern.from_local_datetime(&NaiveDate::from_ymd(y, m, d).and_hms(h, n, 0)).unwrap()
+    }
+
+    fn dates(rule: &str, start: DateTime<Eastern>, limit: usize) -> Vec<NaiveDate> {
+        let rule = rule.parse::<RRule>().unwrap();
+        rule.iter(&start).take(limit).map(|dt| dt.naive_local().date()).collect()
+    }
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd(y, m, d)
+    }
+
+    #[test]
+    fn test_rrule_rfc5545_examples() {
+        let start = local(1997, 9, 2, 9, 0);
+        assert_eq!(
+            dates("FREQ=DAILY;COUNT=10", start, 100),
+            (2..12).map(|d| ymd(1997, 9, d)).collect::<Vec<_>>()
+        );
+        assert_eq!(dates("FREQ=DAILY;UNTIL=19971224T000000Z", start, 200).len(), 113);
+        assert_eq!(
+            dates("FREQ=DAILY;INTERVAL=10;COUNT=5", start, 100),
+            [
+                ymd(1997, 9, 2),
+                ymd(1997, 9, 12),
+                ymd(1997, 9, 22),
+                ymd(1997, 10, 2),
+                ymd(1997, 10, 12)
+            ]
+        );
+        assert_eq!(
+            dates("FREQ=WEEKLY;COUNT=10;WKST=SU;BYDAY=TU,TH", start, 100),
+            [
+                ymd(1997, 9, 2),
+                ymd(1997, 9, 4),
+                ymd(1997, 9, 9),
+                ymd(1997, 9, 11),
+                ymd(1997, 9, 16),
+                ymd(1997, 9, 18),
+                ymd(1997, 9, 23),
+                ymd(1997, 9, 25),
+                ymd(1997, 9, 30),
+                ymd(1997, 10, 2)
+            ]
+        );
+        assert_eq!(
+            dates(
+                "FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=MO",
+                local(1997, 8, 5, 9, 0),
+                100
+            ),
+            [ymd(1997, 8, 5), ymd(1997, 8, 10), ymd(1997, 8, 19), ymd(1997, 8, 24)]
+        );
+        assert_eq!(
+            dates(
+                "FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=SU",
+                local(1997, 8, 5, 9, 0),
+                100
+            ),
+            [ymd(1997, 8, 5), ymd(1997, 8, 17), ymd(1997, 8, 19), ymd(1997, 8, 31)]
+        );
+        assert_eq!(
+            dates("FREQ=MONTHLY;COUNT=10;BYDAY=1FR", local(1997, 9, 5, 9, 0), 100),
+            [
+                ymd(1997, 9, 5),
+                ymd(1997, 10, 3),
+                ymd(1997, 11, 7),
+                ymd(1997, 12, 5),
+                ymd(1998, 1, 2),
+                ymd(1998, 2, 6),
+                ymd(1998, 3, 6),
+                ymd(1998, 4, 3),
+                ymd(1998, 5, 1),
+                ymd(1998, 6, 5)
+            ]
+        );
+        assert_eq!(
+            dates("FREQ=MONTHLY;COUNT=6;BYDAY=-2MO", local(1997, 9, 22, 9, 0), 100),
+            [
+                ymd(1997, 9, 22),
+                ymd(1997, 10, 20),
+                ymd(1997, 11, 17),
+                ymd(1997, 12, 22),
+                ymd(1998, 1, 19),
+                ymd(1998, 2, 16)
+            ]
+        );
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYMONTHDAY=-3", local(1997, 9, 28, 9, 0), 4),
+            [ymd(1997, 9, 28), ymd(1997, 10, 29), ymd(1997, 11, 28), ymd(1997, 12, 29)]
+        );
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13", start, 5),
+            [
+                ymd(1998, 2, 13),
+                ymd(1998, 3, 13),
+                ymd(1998, 11, 13),
+                ymd(1999, 8, 13),
+                ymd(2000, 10, 13)
+            ]
+        );
+        assert_eq!(
+            dates("FREQ=MONTHLY;COUNT=3;BYDAY=TU,WE,TH;BYSETPOS=3", local(1997, 9, 4, 9, 0), 100),
+            [ymd(1997, 9, 4), ymd(1997, 10, 7), ymd(1997, 11, 6)]
+        );
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-2", local(1997, 9, 29, 9, 0), 4),
+            [ymd(1997, 9, 29), ymd(1997, 10, 30), ymd(1997, 11, 27), ymd(1997, 12, 30)]
+        );
+        assert_eq!(
+            dates("FREQ=YEARLY;COUNT=10;BYMONTH=6,7", local(1997, 6, 10, 9, 0), 100),
+            (1997..2002).flat_map(|y| vec![ymd(y, 6, 10), ymd(y, 7, 10)]).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            dates("FREQ=YEARLY;BYDAY=20MO", local(1997, 5, 19, 9, 0), 3),
+            [ymd(1997, 5, 19), ymd(1998, 5, 18), ymd(1999, 5, 17)]
+        );
+        assert_eq!(
+            dates("FREQ=YEARLY;BYWEEKNO=20;BYDAY=MO", local(1997, 5, 12, 9, 0), 3),
+            [ymd(1997, 5, 12), ymd(1998, 5, 11), ymd(1999, 5, 17)]
+        );
+        assert_eq!(
+            dates("FREQ=YEARLY;BYMONTH=3;BYDAY=TH", local(1997, 3, 13, 9, 0), 4),
+            [ymd(1997, 3, 13), ymd(1997, 3, 20), ymd(1997, 3, 27), ymd(1998, 3, 5)]
+        );
+        assert_eq!(
+            dates(
+                "FREQ=YEARLY;INTERVAL=4;BYMONTH=11;BYDAY=TU;BYMONTHDAY=2,3,4,5,6,7,8",
+                local(1996, 11, 5, 9, 0),
+                3
+            ),
+            [ymd(1996, 11, 5), ymd(2000, 11, 7), ymd(2004, 11, 2)]
+        );
+        // February 29 only exists in leap years
+        assert_eq!(
+            dates("FREQ=YEARLY", local(2000, 2, 29, 9, 0), 3),
+            [ymd(2000, 2, 29), ymd(2004, 2, 29), ymd(2008, 2, 29)]
+        );
+        // a rule which never matches
+        assert_eq!(dates("FREQ=MONTHLY;BYMONTH=2;BYMONTHDAY=30", start, 1), []);
+    }
+
+    #[test]
+    fn test_rrule_sub_daily() {
+        let start = local(1997, 9, 2, 9, 0);
+        let rule = "FREQ=HOURLY;INTERVAL=3;UNTIL=19970902T210000Z".parse::<RRule>().unwrap();
+        let hours = rule.iter(&start).map(|dt| dt.naive_local()).collect::<Vec<_>>();
+        assert_eq!(
+            hours,
+            [
+                ymd(1997, 9, 2).and_hms(9, 0, 0),
+                ymd(1997, 9, 2).and_hms(12, 0, 0),
+                ymd(1997, 9, 2).and_hms(15, 0, 0)
+            ]
+        );
+        let rule = "FREQ=MINUTELY;INTERVAL=20;BYDAY=MO;COUNT=4".parse::<RRule>().unwrap();
+        let minutes =
+            rule.iter(&local(2021, 6, 6, 23, 30)).map(|dt| dt.naive_local()).collect::<Vec<_>>();
+        assert_eq!(
+            minutes,
+            [
+                ymd(2021, 6, 7).and_hms(0, 10, 0),
+                ymd(2021, 6, 7).and_hms(0, 30, 0),
+                ymd(2021, 6, 7).and_hms(0, 50, 0),
+                ymd(2021, 6, 7).and_hms(1, 10, 0)
+            ]
+        );
+
+        let rule = "FREQ=HOURLY;INTERVAL=5;BYDAY=MO;COUNT=2".parse::<RRule>().unwrap();
+        let hours =
+            rule.iter(&local(2021, 6, 5, 9, 0)).map(|dt| dt.naive_local()).collect::<Vec<_>>();
+        assert_eq!(hours, [ymd(2021, 6, 7).and_hms(1, 0, 0), ymd(2021, 6, 7).and_hms(6, 0, 0)]);
+
+        // rules which never match end instead of walking every period of 400 years
+        let rule = RRule::new(Frequency::Secondly).with_by_month(&[2]).with_by_month_day(&[30]);
+        assert_eq!(rule.iter(&start).next(), None);
+        let rule = "FREQ=MINUTELY;INTERVAL=7;BYMONTH=4;BYMONTHDAY=31".parse::<RRule>().unwrap();
+        assert_eq!(rule.iter(&start).next(), None);
+    }
+
+    #[test]
+    fn test_rrule_long_interval() {
+        let start = local(2000, 1, 1, 9, 0);
+        assert_eq!(
+            dates("FREQ=YEARLY;INTERVAL=400;COUNT=3", start, 10),
+            [ymd(2000, 1, 1), ymd(2400, 1, 1), ymd(2800, 1, 1)]
+        );
+        assert_eq!(
+            dates("FREQ=YEARLY;INTERVAL=500;COUNT=3", start, 10),
+            [ymd(2000, 1, 1), ymd(2500, 1, 1), ymd(3000, 1, 1)]
+        );
+        assert_eq!(
+            dates("FREQ=MONTHLY;INTERVAL=6000;COUNT=3", start, 10),
+            [ymd(2000, 1, 1), ymd(2500, 1, 1), ymd(3000, 1, 1)]
+        );
+
+        // only every fourth period is in a leap year, the first one 1500 years later
+        let start = local(2100, 2, 1, 9, 0);
+        assert_eq!(
+            dates("FREQ=YEARLY;INTERVAL=500;BYMONTH=2;BYMONTHDAY=29", start, 2),
+            [ymd(3600, 2, 29), ymd(5600, 2, 29)]
+        );
+        // and never with an interval of 400 years
+        assert_eq!(dates("FREQ=YEARLY;INTERVAL=400;BYMONTH=2;BYMONTHDAY=29", start, 2), []);
+    }
+
+    #[test]
+    fn test_rrule_dst() {
+        // the local time is kept across transitions
+        let days = "FREQ=DAILY;COUNT=3".parse::<RRule>().unwrap();
+        let items = days.iter(&local(2021, 11, 6, 9, 0)).collect::<Vec<_>>();
+        assert_eq!(items[1] - items[0], Duration::hours(25));
+        assert_eq!(items[2] - items[1], Duration::hours(24));
+        assert!(items.iter().all(|dt| dt.naive_local().time() == items[0].naive_local().time()));
+
+        // a nonexistent local time is shifted forward and restored later
+        let items =
+            days.iter(&local(2021, 3, 13, 2, 30)).map(|dt| (dt.naive_local(), *dt.offset()));
+        assert_eq!(
+            items.collect::<Vec<_>>(),
+            [
+                (ymd(2021, 3, 13).and_hms(2, 30, 0), Eastern::est()),
+                (ymd(2021, 3, 14).and_hms(3, 30, 0), Eastern::edt()),
+                (ymd(2021, 3, 15).and_hms(2, 30, 0), Eastern::edt())
+            ]
+        );
+
+        // an ambiguous local time resolves to the first instant
+        let items =
+            days.iter(&local(2021, 11, 6, 1, 30)).map(|dt| *dt.offset()).collect::<Vec<_>>();
+        assert_eq!(items, [Eastern::edt(), Eastern::edt(), Eastern::est()]);
+
+        // an UNTIL in UTC is compared as an instant
+        let rule = RRule::new(Frequency::Daily).with_until(Utc.ymd(2021, 11, 8).and_hms(13, 0, 0));
+        assert_eq!(rule.iter(&local(2021, 11, 6, 9, 0)).count(), 2);
+        let rule = RRule::new(Frequency::Daily).with_until(Utc.ymd(2021, 11, 8).and_hms(14, 0, 0));
+        assert_eq!(rule.iter(&local(2021, 11, 6, 9, 0)).count(), 3);
+    }
+
+    #[test]
+    fn test_rrule_week_number() {
+        let mut date = ymd(1999, 12, 1);
+        while date < ymd(2030, 1, 1) {
+            let iso = date.iso_week();
+            let weeks = if NaiveDate::from_isoywd_opt(iso.year(), 53, Weekday::Mon).is_some() {
+                53
+            } else {
+                52
+            };
+            assert_eq!(week_number(date, Weekday::Mon), Some((iso.week(), weeks)));
+            date += Duration::days(3);
+        }
+        // with weeks starting on Sunday, 2021-01-03 starts the first week of 2021
+        assert_eq!(week_number(ymd(2021, 1, 2), Weekday::Sun), Some((53, 53)));
+        assert_eq!(week_number(ymd(2021, 1, 3), Weekday::Sun), Some((1, 52)));
+    }
+
+    #[test]
+    fn test_rrule_from_str_and_display() {
+        let rule = RRule::new(Frequency::Yearly)
+            .with_interval(2)
+            .with_count(10)
+            .with_week_start(Weekday::Sun)
+            .with_by_month(&[1, 3])
+            .with_by_day(&[NthWeekday::every(Weekday::Mon), NthWeekday::nth(Weekday::Fri, -1)])
+            .with_by_set_pos(&[1, -1]);
+        let s = "FREQ=YEARLY;COUNT=10;INTERVAL=2;WKST=SU;BYMONTH=1,3;BYDAY=MO,-1FR;BYSETPOS=1,-1";
+        assert_eq!(rule.to_string(), s);
+        assert_eq!(s.parse(), Ok(rule.clone()));
+        assert_eq!(
+            "rrule:freq=yearly;count=10;interval=2;wkst=su;bymonth=1,3;byday=mo,-1fr;bysetpos=1,-1"
+                .parse(),
+            Ok(rule)
+        );
+
+        for s in &[
+            "FREQ=DAILY;UNTIL=20211231",
+            "FREQ=DAILY;UNTIL=20211231T235959",
+            "FREQ=DAILY;UNTIL=20211231T235959Z",
+        ] {
+            assert_eq!(s.parse::<RRule>().unwrap().to_string(), *s);
+        }
+        assert_eq!(
+            "FREQ=MONTHLY;BYMONTHDAY=-1;BYWEEKNO=+5".parse::<RRule>().map(|r| r.to_string()),
+            Ok("FREQ=MONTHLY;BYWEEKNO=5;BYMONTHDAY=-1".to_string())
+        );
+
+        for s in &[
+            "",
+            "COUNT=1",
+            "FREQ=FORTNIGHTLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;BYMONTH=0",
+            "FREQ=DAILY;BYMONTH=-1",
+            "FREQ=DAILY;BYMONTHDAY=32",
+            "FREQ=DAILY;BYDAY=0MO",
+            "FREQ=DAILY;BYDAY=1XX",
+            "FREQ=DAILY;BYDAY=é",
+            "FREQ=DAILY;BYHOUR=9",
+            "FREQ=DAILY;UNTIL=2021",
+            "FREQ=DAILY;COUNT",
+            "FREQ=DAILY;COUNT=3;UNTIL=20000110T000000Z",
+        ] {
+            assert!(s.parse::<RRule>().is_err(), "{:?} should not parse", s);
+        }
+    }
+
+    #[test]
+    fn test_recurrence() {
+        let start = local(2021, 1, 4, 10, 0);
+        let rule = "FREQ=WEEKLY;COUNT=4;BYDAY=WE".parse::<RRule>().unwrap();
+        let recurrence = Recurrence::new(start)
+            .with_rule(rule)
+            .with_rdate(local(2021, 1, 8, 10, 0))
+            .with_rdate(local(2021, 1, 6, 10, 0))
+            .with_rdate(local(2021, 2, 1, 10, 0))
+            .with_exdate(local(2021, 1, 13, 10, 0))
+            .with_exdate(local(2021, 1, 20, 9, 0));
+        let dates = recurrence.iter().map(|dt| dt.naive_local().date()).collect::<Vec<_>>();
+        assert_eq!(
+            dates,
+            [
+                ymd(2021, 1, 4),
+                ymd(2021, 1, 6),
+                ymd(2021, 1, 8),
+                ymd(2021, 1, 20),
+                ymd(2021, 1, 27),
+                ymd(2021, 2, 1)
+            ]
+        );
+        assert_eq!(Recurrence::new(start).iter().collect::<Vec<_>>(), [start]);
+        assert_eq!(Recurrence::new(start).with_exdate(start).iter().next(), None);
+    }
+}
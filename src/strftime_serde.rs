@@ -0,0 +1,247 @@
+// This is a part of Chrono.
+// See README.md and LICENSE.txt for details.
+
+//! Serde `with`-modules bound to a strftime pattern, re-exported from `chrono::serde`.
+
+use alloc::string::String;
+use core::fmt::{self, Write};
+use core::marker::PhantomData;
+use serdelib::{de, ser};
+
+use format::{parse, DelayedFormat, ParseResult, Parsed, StrftimeItems};
+use naive::{NaiveDate, NaiveDateTime};
+use offset::FixedOffset;
+use DateTime;
+
+/// Types which can be serialized with a strftime pattern.
+///
+/// See [`serde_strftime!`](../macro.serde_strftime.html) for the usual way to use this.
+pub trait StrftimeSerde: Sized {
+    /// Formats `self` with the given items.
+    fn format_items<'a>(&self, items: StrftimeItems<'a>) -> DelayedFormat<StrftimeItems<'a>>;
+
+    /// Parses a value from the given string with the given items.
+    fn parse_items(s: &str, items: StrftimeItems) -> ParseResult<Self>;
+}
+
+impl StrftimeSerde for NaiveDate {
+    fn format_items<'a>(&self, items: StrftimeItems<'a>) -> DelayedFormat<StrftimeItems<'a>> {
+        self.format_with_items(items)
+    }
+
+    fn parse_items(s: &str, items: StrftimeItems) -> ParseResult<NaiveDate> {
+        let mut parsed = Parsed::new();
+        parse(&mut parsed, s, items)?;
+        parsed.to_naive_date()
+    }
+}
+
+impl StrftimeSerde for NaiveDateTime {
+    fn format_items<'a>(&self, items: StrftimeItems<'a>) -> DelayedFormat<StrftimeItems<'a>> {
+        self.format_with_items(items)
+    }
+
+    fn parse_items(s: &str, items: StrftimeItems) -> ParseResult<NaiveDateTime> {
+        let mut parsed = Parsed::new();
+        parse(&mut parsed, s, items)?;
+        parsed.to_naive_datetime_with_offset(0) // no offset adjustment
+    }
+}
+
+impl StrftimeSerde for DateTime<FixedOffset> {
+    fn format_items<'a>(&self, items: StrftimeItems<'a>) -> DelayedFormat<StrftimeItems<'a>> {
+        self.format_with_items(items)
+    }
+
+    fn parse_items(s: &str, items: StrftimeItems) -> ParseResult<DateTime<FixedOffset>> {
+        let mut parsed = Parsed::new();
+        parse(&mut parsed, s, items)?;
+        parsed.to_datetime()
+    }
+}
+
+/// Serializes a value into a string formatted with the strftime pattern `fmt`.
+///
+/// Fails if `fmt` is not a valid pattern, or if the value lacks a field the pattern
+/// requires (such as an offset for a `NaiveDateTime`).
+pub fn serialize_with_format<T, S>(value: &T, fmt: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: StrftimeSerde,
+    S: ser::Serializer,
+{
+    // `collect_str` may panic when formatting fails, so format into a buffer first
+    let mut buf = String::new();
+    write!(buf, "{}", value.format_items(StrftimeItems::new(fmt)))
+        .map_err(|_| ser::Error::custom("invalid strftime pattern or missing field"))?;
+    serializer.serialize_str(&buf)
+}
+
+/// Deserializes a value from a string formatted with the strftime pattern `fmt`.
+///
+/// Parsing errors are reported as custom serde errors.
+pub fn deserialize_with_format<'de, T, D>(fmt: &str, deserializer: D) -> Result<T, D::Error>
+where
+    T: StrftimeSerde,
+    D: de::Deserializer<'de>,
+{
+    deserializer.deserialize_str(StrftimeVisitor { fmt: fmt, marker: PhantomData })
+}
+
+struct StrftimeVisitor<'a, T> {
+    fmt: &'a str,
+    marker: PhantomData<T>,
+}
+
+impl<'a, 'de, T: StrftimeSerde> de::Visitor<'de> for StrftimeVisitor<'a, T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a string formatted as `{}`", self.fmt)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        T::parse_items(value, StrftimeItems::new(self.fmt)).map_err(E::custom)
+    }
+}
+
+/// Declares a serde `with`-module which serializes and deserializes through a strftime pattern.
+///
+/// The module works for [`NaiveDate`](./naive/struct.NaiveDate.html),
+/// [`NaiveDateTime`](./naive/struct.NaiveDateTime.html) and
+/// [`DateTime<FixedOffset>`](./struct.DateTime.html), and has a `FORMAT` constant with the
+/// pattern next to the usual `serialize` and `deserialize` functions.
+/// Parsing errors are reported as serde errors.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate chrono;
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate serde_json;
+/// use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone};
+///
+/// serde_strftime!(mod slashed = "%Y/%m/%d %H:%M:%S %z");
+/// serde_strftime!(mod compact = "%Y%m%d");
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Event {
+///     #[serde(with = "slashed")]
+///     time: DateTime<FixedOffset>,
+///     #[serde(with = "compact")]
+///     day: NaiveDate,
+/// }
+///
+/// # fn main() {
+/// let event = Event {
+///     time: FixedOffset::east(3600).ymd(2020, 1, 31).and_hms(13, 45, 0),
+///     day: NaiveDate::from_ymd(2020, 1, 31),
+/// };
+/// let json = serde_json::to_string(&event).unwrap();
+/// assert_eq!(json, r#"{"time":"2020/01/31 13:45:00 +0100","day":"20200131"}"#);
+/// assert_eq!(serde_json::from_str::<Event>(&json).unwrap(), event);
+/// assert!(serde_json::from_str::<Event>(r#"{"time":"2020-01-31","day":"20200131"}"#).is_err());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! serde_strftime {
+    ($(#[$attr:meta])* $vis:vis mod $name:ident = $fmt:expr) => {
+        $(#[$attr])*
+        $vis mod $name {
+            /// The strftime pattern of this module
+            pub const FORMAT: &'static str = $fmt;
+
+            /// Serializes a value into a string formatted with `FORMAT`
+            pub fn serialize<T, S>(value: &T, serializer: S) -> $crate::serde::__Result<S::Ok, S::Error>
+            where
+                T: $crate::serde::StrftimeSerde,
+                S: $crate::serde::__Serializer,
+            {
+                $crate::serde::serialize_with_format(value, FORMAT, serializer)
+            }
+
+            /// Deserializes a value from a string formatted with `FORMAT`
+            pub fn deserialize<'de, T, D>(deserializer: D) -> $crate::serde::__Result<T, D::Error>
+            where
+                T: $crate::serde::StrftimeSerde,
+                D: $crate::serde::__Deserializer<'de>,
+            {
+                $crate::serde::deserialize_with_format(FORMAT, deserializer)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_derive;
+    extern crate serde_json;
+
+    use self::serde_derive::{Deserialize, Serialize};
+    use naive::{NaiveDate, NaiveDateTime};
+    use offset::{FixedOffset, TimeZone};
+    use DateTime;
+
+    serde_strftime!(mod slashed = "%Y/%m/%d %H:%M:%S %z");
+    serde_strftime!(mod compact = "%Y%m%d");
+    serde_strftime!(mod minutes = "%d.%m.%Y %H:%M");
+    serde_strftime!(mod broken = "%Y-%Q");
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Test {
+        #[serde(with = "slashed")]
+        time: DateTime<FixedOffset>,
+        #[serde(with = "compact")]
+        day: NaiveDate,
+        #[serde(with = "minutes")]
+        local: NaiveDateTime,
+    }
+
+    #[test]
+    fn test_serde_strftime() {
+        assert_eq!(slashed::FORMAT, "%Y/%m/%d %H:%M:%S %z");
+        let value = Test {
+            time: FixedOffset::east(3600).ymd(2020, 1, 31).and_hms(13, 45, 0),
+            day: NaiveDate::from_ymd(2020, 1, 31),
+            local: NaiveDate::from_ymd(2020, 1, 31).and_hms(8, 5, 0),
+        };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(
+            json,
+            r#"{"time":"2020/01/31 13:45:00 +0100","day":"20200131","local":"31.01.2020 08:05"}"#
+        );
+        let decoded: Test = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, value);
+        assert_eq!(decoded.time.offset(), &FixedOffset::east(3600));
+
+        let err = serde_json::from_str::<Test>(
+            r#"{"time":"2020/01/31 13:45:00","day":"20200131","local":"31.01.2020 08:05"}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("premature end of input"));
+        let err = serde_json::from_str::<Test>(
+            r#"{"time":"2020/01/31 13:45:00 +0100","day":"20200231","local":"31.01.2020 08:05"}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("input is out of range"));
+        let err = serde_json::from_str::<Test>(
+            r#"{"time":"2020/01/31 13:45:00 +0100","day":20200131,"local":"31.01.2020 08:05"}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("a string formatted as `%Y%m%d`"));
+    }
+
+    #[test]
+    fn test_serde_strftime_invalid() {
+        let mut buf = Vec::new();
+        let mut serializer = serde_json::Serializer::new(&mut buf);
+        assert!(broken::serialize(&NaiveDate::from_ymd(2020, 1, 31), &mut serializer).is_err());
+        // a naive value can not be formatted with an offset
+        let naive = NaiveDate::from_ymd(2020, 1, 31).and_hms(13, 45, 0);
+        assert!(slashed::serialize(&naive, &mut serializer).is_err());
+        let mut deserializer = serde_json::Deserializer::from_str(r#""2020-""#);
+        assert!(broken::deserialize::<NaiveDate, _>(&mut deserializer).is_err());
+    }
+}